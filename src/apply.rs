@@ -1,151 +1,688 @@
-use ::surrealdb::{engine::remote::ws::Client, Surreal};
-use anyhow::{Context, Result};
-use fs_extra::dir::{DirEntryAttr, DirEntryValue, LsResult};
+use ::surrealdb::{
+    engine::any::Any,
+    sql::{self, Statement},
+    Surreal,
+};
+use anyhow::{anyhow, Context, Result};
+use fs_extra::dir::{DirEntryAttr, DirEntryValue};
 use std::{
     collections::{HashMap, HashSet},
-    path::{Path, PathBuf},
+    path::PathBuf,
+    time::{Duration, Instant},
 };
+use tokio::task::JoinSet;
 
 use crate::{
-    config,
-    constants::{EVENTS_DIR_NAME, MIGRATIONS_DIR_NAME, SCHEMAS_DIR_NAME},
-    definitions,
-    input::SurrealdbConfiguration,
-    models::ScriptMigration,
-    surrealdb::{self, TransactionAction},
+    audit, checksum,
+    clock::{self, Clock},
+    constants::{EVENTS_DIR_NAME, SCHEMAS_DIR_NAME},
+    definition_checksums, definitions, directives,
+    error::MigrationError,
+    health_check, history, idempotent_defines,
+    input::{ConcurrencyControl, HealthCheck, VersionFormat},
+    io::{self, concat_path, FilesLocation, SurqlFile},
+    lock,
+    logger::{log, warn},
+    migration_meta::MigrationMeta,
+    models::{ApplyReport, MigrationProgress, MigrationProgressStatus, ScriptMigration},
+    retry::MigrationRetry,
+    schema_version, server_version,
+    surrealdb::{self, MigrationsTable, QueryParams, TransactionAction},
+    validate_schema_files, validate_version_order, variables, verify,
 };
 
+pub enum ApplyOperation {
+    Up,
+    UpTo(String),
+    UpStep(usize),
+    /// Apply the migrations between the two named migrations, both included.
+    UpRange {
+        from: String,
+        to: String,
+    },
+    /// Apply the pending migrations one at a time, none being started once the deadline is reached.
+    UpUntilDeadline(Instant),
+    SchemasOnly,
+}
+
 pub struct ApplyArgs<'a> {
-    pub up: Option<String>,
-    pub db_configuration: &'a SurrealdbConfiguration,
-    pub display_logs: bool,
+    pub operation: ApplyOperation,
+    pub client: &'a Surreal<Any>,
+    pub migrations_table: MigrationsTable<'a>,
+    pub transactional: bool,
+    pub location: FilesLocation,
+    pub logger: Option<&'a (dyn Fn(&str) + Send + Sync)>,
     pub dry_run: bool,
+    pub force_schema: bool,
+    pub idempotent_defines: bool,
+    pub schema_concurrency: usize,
+    pub strict_schema: bool,
+    pub continue_on_error: bool,
+    pub health_check: Option<&'a HealthCheck>,
+    pub min_server_version: Option<&'a str>,
+    pub allow_checksum_mismatch: bool,
+    pub migration_timeout: Option<Duration>,
+    pub migration_retry: MigrationRetry<'a>,
+    pub version_format: VersionFormat,
+    pub validate_before_apply: bool,
+    pub applied_by: Option<String>,
+    pub applied_from_host: Option<String>,
+    pub clock: Option<&'a Clock>,
+    pub on_progress: Option<&'a (dyn Fn(MigrationProgress) + Send + Sync)>,
+    pub statement_progress: bool,
+    pub lock_timeout_ms: Option<u64>,
+    pub lock_ttl_ms: Option<u64>,
+    pub concurrency_control: ConcurrencyControl,
+    pub vars: Option<&'a HashMap<String, String>>,
+    pub enabled_tags: &'a [String],
+    pub skip: &'a [String],
+    pub log_statements: bool,
+    pub redacted_vars: &'a [String],
+}
+
+pub struct ApplyOutput {
+    pub report: ApplyReport,
+    /// Definitions and migrations applied.
+    pub queries: Vec<String>,
+    /// Definitions and migrations applied, along with the queries recording them.
+    pub script: Vec<String>,
 }
 
-pub async fn main<'a>(args: ApplyArgs<'a>) -> Result<()> {
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "up", skip_all, fields(dry_run = args.dry_run))
+)]
+pub async fn main<'a>(args: ApplyArgs<'a>) -> Result<ApplyOutput> {
+    // A dry run does not persist anything, so it does not need the lock
+    if args.dry_run {
+        return apply(args).await;
+    }
+
+    let client = args.client;
+    let migrations_table = args.migrations_table;
+
+    match args.concurrency_control {
+        ConcurrencyControl::Lock => {
+            let owner = lock::acquire(
+                client,
+                args.migrations_table,
+                args.lock_timeout_ms,
+                args.lock_ttl_ms,
+            )
+            .await?;
+            let result = apply(args).await;
+            let release_result = lock::release(client, migrations_table, &owner).await;
+
+            let output = result?;
+            release_result?;
+
+            Ok(output)
+        }
+        ConcurrencyControl::VersionCheck => {
+            let schema_version = schema_version::get(client, migrations_table).await?;
+            let output = apply(args).await?;
+
+            // Nothing has been applied, so there is no change to protect from concurrent ones
+            if output.report.has_changes {
+                schema_version::increment(client, migrations_table, schema_version).await?;
+            }
+
+            Ok(output)
+        }
+    }
+}
+
+/// Apply migrations without taking the migrations lock, for callers already holding it.
+pub async fn apply<'a>(args: ApplyArgs<'a>) -> Result<ApplyOutput> {
     let ApplyArgs {
-        up,
-        db_configuration,
-        display_logs,
+        operation,
+        client,
+        migrations_table,
+        transactional,
+        location,
+        logger,
         dry_run,
+        force_schema,
+        idempotent_defines,
+        schema_concurrency,
+        strict_schema,
+        continue_on_error,
+        health_check,
+        min_server_version,
+        allow_checksum_mismatch,
+        migration_timeout,
+        migration_retry,
+        version_format,
+        validate_before_apply,
+        applied_by,
+        applied_from_host,
+        clock,
+        on_progress,
+        statement_progress,
+        vars,
+        enabled_tags,
+        skip,
+        log_statements,
+        redacted_vars,
+        ..
     } = args;
 
-    let display_logs = match dry_run {
-        true => false,
-        false => display_logs,
+    let logger = match dry_run {
+        true => None,
+        false => logger,
     };
 
-    let client = surrealdb::create_surrealdb_client(&db_configuration).await?;
+    let statement_logger = StatementLogger {
+        enabled: log_statements,
+        logger,
+        redacted_vars,
+    };
+
+    // Checked before anything is read, to avoid cryptic errors from an incompatible server
+    if let Some(min_server_version) = min_server_version {
+        server_version::check_min_version(client, min_server_version).await?;
+    }
+
+    // Checked before anything is read, to make sure the migrations target the right database
+    if let Some(health_check) = health_check {
+        health_check::run(client, health_check).await?;
+    }
+
+    // Checked before anything is applied, so that no schema file is applied if any is invalid
+    if strict_schema {
+        let schemas_files = io::extract_definition_files(&location, &location.dir_names.schemas)?;
+        validate_schema_files::main(&schemas_files)?;
+    }
+
+    // The migrations folder is not read at all, so no migration is executed nor recorded
+    if let ApplyOperation::SchemasOnly = operation {
+        let AppliedDefinitions {
+            queries,
+            script,
+            schema_files_count,
+            ..
+        } = apply_definitions(
+            client,
+            migrations_table,
+            &location,
+            force_schema,
+            idempotent_defines,
+            schema_concurrency,
+            None,
+            dry_run,
+            logger,
+            statement_logger,
+        )
+        .await?;
+
+        let report = ApplyReport {
+            has_changes: !queries.is_empty(),
+            applied_migrations: Vec::new(),
+            schema_files_count,
+        };
+
+        return Ok(ApplyOutput {
+            report,
+            queries,
+            script,
+        });
+    }
+
+    // Checked before anything is applied, since the order of migrations sharing a version is undefined
+    let migrations_files = skip_migrations(io::extract_migrations_files(&location)?, skip, logger);
+    validate_version_order::validate_no_duplicate_versions(&migrations_files, version_format)?;
 
     let migrations_applied =
-        surrealdb::list_script_migration_ordered_by_execution_date(&client).await?;
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    // Checked before anything is applied, so that new migrations never run on top of modified ones
+    if !allow_checksum_mismatch {
+        let checksum_mismatches =
+            verify::find_checksum_mismatches(&migrations_applied, &migrations_files)?;
+
+        if !checksum_mismatches.is_empty() {
+            return Err(MigrationError::ChecksumMismatch(checksum_mismatches).into());
+        }
+    }
+
+    if validate_before_apply {
+        validate_version_order::validate(
+            &migrations_applied,
+            &migrations_files,
+            version_format,
+            enabled_tags,
+        )?;
+    }
 
     let mut config = HashSet::new();
     config.insert(DirEntryAttr::Name);
     config.insert(DirEntryAttr::Path);
     config.insert(DirEntryAttr::IsFile);
 
-    let folder_path = config::retrieve_folder_path();
+    let deadline = match operation {
+        ApplyOperation::UpUntilDeadline(deadline) => Some(deadline),
+        _ => None,
+    };
+
+    let migration_files_to_execute = get_migration_files_to_execute(
+        migrations_files,
+        operation,
+        &migrations_applied,
+        enabled_tags,
+    )?;
 
-    let schemas_dir_path = concat_path(&folder_path, SCHEMAS_DIR_NAME);
-    let events_dir_path = concat_path(&folder_path, EVENTS_DIR_NAME);
-    let migrations_dir_path = concat_path(&folder_path, MIGRATIONS_DIR_NAME);
+    // Reverting the first migration of this apply operation goes back before the new event files
+    let introduced_by = migration_files_to_execute
+        .first()
+        .map(|migration_file| migration_file.name.to_string());
 
-    let schemas_files = fs_extra::dir::ls(schemas_dir_path, &config)?;
-    let schema_definitions = extract_schema_definitions(schemas_files);
-    apply_schema_definitions(&client, &schema_definitions, dry_run).await?;
+    let AppliedDefinitions {
+        mut queries,
+        mut script,
+        schema_files_count,
+        schema_definitions,
+        event_definitions,
+    } = apply_definitions(
+        client,
+        migrations_table,
+        &location,
+        force_schema,
+        idempotent_defines,
+        schema_concurrency,
+        introduced_by.as_deref(),
+        dry_run,
+        logger,
+        statement_logger,
+    )
+    .await?;
+
+    // Definition files cannot be written inside an embedded directory nor a migration source,
+    // so they are not tracked, and a dry run does not write them
+    let folder_path = &location.folder_path;
+
+    let has_definition_changes = match (location.embedded_dir, &location.source_files) {
+        (Some(_), _) | (None, Some(_)) => false,
+        (None, None) if dry_run => false,
+        (None, None) => {
+            let last_migration_applied = migrations_applied.last();
+
+            let definitions_path =
+                concat_path(folder_path, &location.dir_names.migrations).join("definitions");
+            let initial_definition_path = definitions_path.join("_initial.json");
+
+            ensures_folder_exists(&definitions_path)?;
+
+            create_definition_files(
+                last_migration_applied,
+                initial_definition_path,
+                definitions_path,
+                &config,
+                schema_definitions,
+                event_definitions,
+            )?
+        }
+    };
 
-    if display_logs {
-        println!("Schema files successfully executed!");
+    let migrations_executed = apply_migrations(
+        migration_files_to_execute,
+        logger,
+        client,
+        migrations_table,
+        transactional,
+        dry_run,
+        on_progress,
+        statement_progress,
+        vars,
+        continue_on_error,
+        migration_timeout,
+        migration_retry,
+        &audit::get_audit_params(applied_by, applied_from_host),
+        clock,
+        deadline,
+        statement_logger,
+    )
+    .await?;
+
+    log(logger, "Migration files successfully executed!");
+
+    let mut applied_migrations = Vec::new();
+
+    for migration_executed in migrations_executed {
+        script.push(format!(
+            "{}\n{}\n{}",
+            migration_executed.query,
+            surrealdb::get_params_definition_query(&migration_executed.record_params)?,
+            migration_executed.record_query
+        ));
+        applied_migrations.push(migration_executed.name);
+        queries.push(migration_executed.query);
     }
 
-    let event_definitions = if events_dir_path.try_exists()? {
-        let events_files = fs_extra::dir::ls(events_dir_path, &config)?;
-        let event_definitions = extract_event_definitions(events_files);
-        apply_event_definitions(&client, &event_definitions, dry_run).await?;
+    let report = ApplyReport {
+        has_changes: has_definition_changes || !applied_migrations.is_empty(),
+        applied_migrations,
+        schema_files_count,
+    };
 
-        if display_logs {
-            println!("Event files successfully executed!");
+    Ok(ApplyOutput {
+        report,
+        queries,
+        script,
+    })
+}
+
+struct AppliedDefinitions {
+    queries: Vec<String>,
+    script: Vec<String>,
+    schema_files_count: usize,
+    schema_definitions: String,
+    event_definitions: String,
+}
+
+/// Apply the schema files, then the event files if any.
+/// New event files are saved as introduced by the given migration, if any.
+#[allow(clippy::too_many_arguments)]
+async fn apply_definitions(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+    force_schema: bool,
+    idempotent_defines: bool,
+    schema_concurrency: usize,
+    introduced_by: Option<&str>,
+    dry_run: bool,
+    logger: Option<&(dyn Fn(&str) + Send + Sync)>,
+    statement_logger: StatementLogger<'_>,
+) -> Result<AppliedDefinitions> {
+    let applied_checksums = definition_checksums::list(client, migrations_table).await?;
+
+    let schemas_files = io::extract_definition_files(location, &location.dir_names.schemas)?;
+    let schema_files_count = schemas_files.len();
+    let schema_definitions = concat_files_content(&schemas_files)?;
+
+    let schema_definition_queries = get_definition_queries(
+        migrations_table,
+        &schemas_files,
+        SCHEMAS_DIR_NAME,
+        &applied_checksums,
+        force_schema,
+        None,
+    )?;
+
+    // Only the queries sent are rewritten, so that checksums and definition files stay unchanged
+    let schema_definition_queries =
+        match idempotent_defines && supports_if_not_exists(client, logger).await? {
+            true => schema_definition_queries
+                .into_iter()
+                .map(|(definition, save_checksum_query)| {
+                    let definition = idempotent_defines::add_if_not_exists(&definition)?;
+                    Ok((definition, save_checksum_query))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            false => schema_definition_queries,
+        };
+    let (schema_definitions_applied, schema_checksums_saved) = apply_definition_queries(
+        client,
+        migrations_table,
+        schema_definition_queries,
+        dry_run,
+        schema_concurrency,
+        statement_logger,
+    )
+    .await?;
+
+    #[cfg(feature = "tracing")]
+    tracing::info!(count = schema_files_count, "Schema files applied");
+
+    let mut queries = Vec::new();
+    let mut script = Vec::new();
+
+    if !schema_definitions_applied.is_empty() {
+        script.push(format!(
+            "{}\n{}",
+            schema_definitions_applied, schema_checksums_saved
+        ));
+        queries.push(schema_definitions_applied);
+    }
+
+    log(logger, "Schema files successfully executed!");
+
+    let events_dir_name = &location.dir_names.events;
+
+    let event_definitions = if io::definition_folder_exists(location, events_dir_name)? {
+        let events_files = io::extract_definition_files(location, events_dir_name)?;
+        let event_definitions = concat_files_content(&events_files)?;
+
+        let event_definition_queries = get_definition_queries(
+            migrations_table,
+            &events_files,
+            EVENTS_DIR_NAME,
+            &applied_checksums,
+            force_schema,
+            introduced_by,
+        )?;
+        let (event_definitions_applied, event_checksums_saved) = apply_definition_queries(
+            client,
+            migrations_table,
+            event_definition_queries,
+            dry_run,
+            1,
+            statement_logger,
+        )
+        .await?;
+
+        #[cfg(feature = "tracing")]
+        tracing::info!("Event files applied");
+
+        if !event_definitions_applied.is_empty() {
+            script.push(format!(
+                "{}\n{}",
+                event_definitions_applied, event_checksums_saved
+            ));
+            queries.push(event_definitions_applied);
         }
 
+        log(logger, "Event files successfully executed!");
+
         event_definitions
     } else {
         String::new()
     };
 
-    let last_migration_applied = migrations_applied.last();
-
-    const INITIAL_DEFINITION_FOLDER: &str = "migrations/definitions/_initial.json";
-    let initial_definition_path = concat_path(&folder_path, INITIAL_DEFINITION_FOLDER);
+    Ok(AppliedDefinitions {
+        queries,
+        script,
+        schema_files_count,
+        schema_definitions,
+        event_definitions,
+    })
+}
 
-    const DEFINITIONS_FOLDER: &str = "migrations/definitions";
-    let definitions_path = concat_path(&folder_path, DEFINITIONS_FOLDER);
+/// Check if the `IF NOT EXISTS` clause is supported, warning that definitions are not rewritten otherwise.
+async fn supports_if_not_exists(
+    client: &Surreal<Any>,
+    logger: Option<&(dyn Fn(&str) + Send + Sync)>,
+) -> Result<bool> {
+    let server_version = server_version::get(client).await?;
+
+    let is_supported = idempotent_defines::is_supported(&server_version);
+
+    if !is_supported {
+        warn(
+            logger,
+            &format!(
+                "idempotent defines are ignored, SurrealDB server version {} does not support IF NOT EXISTS before {}",
+                server_version,
+                idempotent_defines::MIN_SERVER_VERSION
+            ),
+        );
+    }
 
-    ensures_folder_exists(&definitions_path)?;
+    Ok(is_supported)
+}
 
-    create_definition_files(
-        last_migration_applied,
-        initial_definition_path,
-        definitions_path,
-        &config,
-        schema_definitions,
-        event_definitions,
-        folder_path,
-    )?;
+/// Logs each query right before it is sent to SurrealDB, when statements are logged.
+#[derive(Clone, Copy)]
+struct StatementLogger<'a> {
+    enabled: bool,
+    logger: Option<&'a (dyn Fn(&str) + Send + Sync)>,
+    /// Names of the variables whose values are hidden from the logged queries.
+    redacted_vars: &'a [String],
+}
 
-    let migrations_files = fs_extra::dir::ls(migrations_dir_path, &config)?;
-    let migration_files_to_execute =
-        get_migration_files_to_execute(&migrations_files, up, &migrations_applied);
+impl StatementLogger<'_> {
+    fn log(&self, query: &str) {
+        if !self.enabled {
+            return;
+        }
 
-    apply_migrations(migration_files_to_execute, display_logs, client, dry_run).await?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(query, "Executing statement");
 
-    if display_logs {
-        println!("Migration files successfully executed!");
+        log(self.logger, &format!("Executing statement:\n{}", query));
     }
 
-    Ok(())
-}
+    /// Log a migration whose variables have been substituted, with the redacted values hidden.
+    fn log_migration(&self, content: &str, vars: Option<&HashMap<String, String>>) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let query = match vars {
+            Some(vars) => {
+                let logged_vars = vars
+                    .iter()
+                    .map(|(name, value)| match self.redacted_vars.contains(name) {
+                        true => (name.to_string(), REDACTED_VALUE.to_string()),
+                        false => (name.to_string(), value.to_string()),
+                    })
+                    .collect::<HashMap<_, _>>();
+
+                variables::substitute_variables(content, &logged_vars)?
+            }
+            None => content.to_string(),
+        };
+
+        self.log(&query);
 
-fn concat_path(folder_path: &Option<String>, dir_name: &str) -> PathBuf {
-    match folder_path.to_owned() {
-        Some(folder_path) => Path::new(&folder_path).join(dir_name),
-        None => Path::new(dir_name).to_path_buf(),
+        Ok(())
     }
 }
 
-fn extract_schema_definitions(schemas_files: LsResult) -> String {
-    concat_files_content(schemas_files)
+const REDACTED_VALUE: &str = "***";
+
+fn concat_files_content(files: &[SurqlFile]) -> Result<String> {
+    let contents = files
+        .iter()
+        .map(|file| file.get_content())
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(contents.join("\n"))
 }
 
-fn extract_event_definitions(events_files: LsResult) -> String {
-    concat_files_content(events_files)
+/// Get the definitions of the schema/event files that changed since they were last applied,
+/// or all of them if forced, along with the queries saving their checksum.
+fn get_definition_queries(
+    migrations_table: MigrationsTable<'_>,
+    files: &[SurqlFile],
+    dir_name: &str,
+    applied_checksums: &HashMap<String, String>,
+    force_schema: bool,
+    introduced_by: Option<&str>,
+) -> Result<Vec<(String, String)>> {
+    let mut definition_queries = Vec::new();
+
+    for file in files {
+        let content = file.get_content()?;
+        let checksum = checksum::compute_checksum(&content);
+        let key = format!("{}/{}", dir_name, file.name);
+
+        if !force_schema && applied_checksums.get(&key) == Some(&checksum) {
+            continue;
+        }
+
+        let save_checksum_query = match dir_name {
+            EVENTS_DIR_NAME => definition_checksums::get_save_event_query(
+                migrations_table,
+                &key,
+                &checksum,
+                &definition_checksums::extract_event_definitions(&content),
+                !applied_checksums.contains_key(&key),
+                introduced_by,
+            )?,
+            _ => definition_checksums::get_save_query(migrations_table, &key, &checksum)?,
+        };
+
+        definition_queries.push((content, save_checksum_query));
+    }
+
+    Ok(definition_queries)
 }
 
-fn concat_files_content(files: LsResult) -> String {
-    files
-        .items
+/// Apply the definitions along with the queries saving their checksum, in a single transaction,
+/// or in one transaction per file with at most `concurrency` transactions running at once.
+/// Returns the definitions applied and the queries saving their checksum.
+async fn apply_definition_queries(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    definition_queries: Vec<(String, String)>,
+    dry_run: bool,
+    concurrency: usize,
+    statement_logger: StatementLogger<'_>,
+) -> Result<(String, String)> {
+    if definition_queries.is_empty() {
+        return Ok((String::new(), String::new()));
+    }
+
+    let definitions = definition_queries
         .iter()
-        .map(|file| map_to_file_content(file).unwrap_or("".to_string())) // TODO : fail when one file fails
+        .map(|(definition, _)| definition.to_string())
         .collect::<Vec<_>>()
-        .join("\n")
-}
+        .join("\n");
+    let save_checksum_queries = definition_queries
+        .iter()
+        .map(|(_, save_checksum_query)| save_checksum_query.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
 
-async fn apply_schema_definitions(
-    client: &Surreal<Client>,
-    schema_definitions: &String,
-    dry_run: bool,
-) -> Result<()> {
-    let action = get_transaction_action(dry_run);
-    surrealdb::apply_in_transaction(client, schema_definitions, action).await
-}
+    // A dry run cancels its changes, which is only possible with a single transaction
+    if concurrency <= 1 || dry_run {
+        let query = format!("{}\n{}", definitions, save_checksum_queries);
+        statement_logger.log(&query);
 
-async fn apply_event_definitions(
-    client: &Surreal<Client>,
-    event_definitions: &String,
-    dry_run: bool,
-) -> Result<()> {
-    let action = get_transaction_action(dry_run);
-    surrealdb::apply_in_transaction(client, event_definitions, action).await
+        let action = get_transaction_action(dry_run);
+        surrealdb::apply_in_transaction(client, &query, action).await?;
+
+        return Ok((definitions, save_checksum_queries));
+    }
+
+    // Defined upfront, so that concurrent transactions do not all create it when saving their checksum
+    definition_checksums::define_table(client, migrations_table).await?;
+
+    let mut tasks = JoinSet::new();
+
+    for (definition, save_checksum_query) in definition_queries {
+        if tasks.len() >= concurrency {
+            if let Some(result) = tasks.join_next().await {
+                result??;
+            }
+        }
+
+        let client = client.clone();
+        let query = format!("{}\n{}", definition, save_checksum_query);
+        statement_logger.log(&query);
+
+        tasks.spawn(async move {
+            surrealdb::apply_in_transaction(&client, &query, TransactionAction::Commit).await
+        });
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        result??;
+    }
+
+    Ok((definitions, save_checksum_queries))
 }
 
 fn get_transaction_action(dry_run: bool) -> TransactionAction {
@@ -157,7 +694,7 @@ fn get_transaction_action(dry_run: bool) -> TransactionAction {
 
 fn ensures_folder_exists(dir_path: &PathBuf) -> Result<()> {
     if !dir_path.exists() {
-        fs_extra::dir::create_all(&dir_path, false)?;
+        fs_extra::dir::create_all(dir_path, false)?;
     }
 
     Ok(())
@@ -215,8 +752,13 @@ fn create_definition_files(
     config: &HashSet<DirEntryAttr>,
     schema_definitions: String,
     event_definitions: String,
-    folder_path: Option<String>,
-) -> Result<()> {
+) -> Result<bool> {
+    let has_definition_changes;
+
+    // Migrations can be marked as applied without any definition saved yet (e.g. a baseline)
+    let last_migration_applied =
+        last_migration_applied.filter(|_| initial_definition_path.exists());
+
     match last_migration_applied {
         Some(last_migration_applied) => {
             let initial_definition = fs_extra::file::read_to_string(initial_definition_path)?;
@@ -225,7 +767,7 @@ fn create_definition_files(
             )?;
 
             // calculate new definition based on all definitions files
-            let diff_definition_files = fs_extra::dir::ls(definitions_path, &config)?;
+            let diff_definition_files = fs_extra::dir::ls(&definitions_path, config)?;
 
             let definition_diffs = diff_definition_files
                 .items
@@ -234,7 +776,7 @@ fn create_definition_files(
                 .take_while(|file| {
                     take_while_not_applied(file, last_migration_applied).unwrap_or(false)
                 })
-                .map(|file| map_to_file_content(file))
+                .map(map_to_file_content)
                 .collect::<Vec<_>>();
 
             let mut last_definition = initial_definition;
@@ -245,23 +787,7 @@ fn create_definition_files(
                 let definition_diff =
                     serde_json::from_str::<definitions::DefinitionDiff>(&diff_definition)?;
 
-                let schemas = match definition_diff.schemas {
-                    Some(schemas_diff) => {
-                        let schemas_patch = diffy::Patch::from_str(&schemas_diff)?;
-                        diffy::apply(&last_definition.schemas, &schemas_patch)?
-                    }
-                    _ => last_definition.schemas,
-                };
-
-                let events = match definition_diff.events {
-                    Some(events_diff) => {
-                        let events_patch = diffy::Patch::from_str(&events_diff)?;
-                        diffy::apply(&last_definition.events, &events_patch)?
-                    }
-                    _ => last_definition.events,
-                };
-
-                last_definition = definitions::SchemaMigrationDefinition { schemas, events };
+                last_definition = apply_definition_diff(last_definition, definition_diff)?;
             }
 
             // make diff between new definition and last definition
@@ -271,15 +797,26 @@ fn create_definition_files(
             };
 
             // save definition if any changes
-            let definition_filepath = format!(
-                "migrations/definitions/{}.json",
-                last_migration_applied.script_name
-            );
-            let definition_filepath = match folder_path.to_owned() {
-                Some(folder_path) => Path::new(&folder_path).join(definition_filepath),
-                None => Path::new(&definition_filepath).to_path_buf(),
+            let definition_filepath =
+                definitions_path.join(format!("{}.json", last_migration_applied.script_name));
+
+            // retrieve definition saved during the previous apply
+            let previous_definition = match definition_filepath.exists() {
+                true => {
+                    let previous_definition_diff =
+                        fs_extra::file::read_to_string(&definition_filepath)?;
+                    let previous_definition_diff = serde_json::from_str::<
+                        definitions::DefinitionDiff,
+                    >(&previous_definition_diff)?;
+
+                    apply_definition_diff(last_definition.clone(), previous_definition_diff)?
+                }
+                false => last_definition.clone(),
             };
 
+            has_definition_changes =
+                are_definitions_different(&previous_definition, &current_definition);
+
             let has_schema_diffs =
                 last_definition.schemas.trim() != current_definition.schemas.trim();
             let has_event_diffs = last_definition.events.trim() != current_definition.events.trim();
@@ -315,8 +852,6 @@ fn create_definition_files(
                 }
                 false => {
                     // remove definition file if exists
-                    let definition_filepath = Path::new(&definition_filepath);
-
                     if definition_filepath.exists() {
                         fs_extra::file::remove(definition_filepath)?;
                     }
@@ -334,147 +869,685 @@ fn create_definition_files(
                 events: event_definitions,
             };
 
+            // retrieve definition saved during the previous apply
+            let previous_definition = match initial_definition_path.exists() {
+                true => {
+                    let previous_definition =
+                        fs_extra::file::read_to_string(&initial_definition_path)?;
+                    serde_json::from_str::<definitions::SchemaMigrationDefinition>(
+                        &previous_definition,
+                    )?
+                }
+                false => definitions::SchemaMigrationDefinition {
+                    schemas: String::new(),
+                    events: String::new(),
+                },
+            };
+
+            has_definition_changes =
+                are_definitions_different(&previous_definition, &current_definition);
+
             let serialized_definition = serde_json::to_string(&current_definition)?;
 
             fs_extra::file::write_all(&initial_definition_path, &serialized_definition)?;
         }
     }
 
-    Ok(())
+    Ok(has_definition_changes)
+}
+
+fn apply_definition_diff(
+    definition: definitions::SchemaMigrationDefinition,
+    definition_diff: definitions::DefinitionDiff,
+) -> Result<definitions::SchemaMigrationDefinition> {
+    let schemas = match definition_diff.schemas {
+        Some(schemas_diff) => {
+            let schemas_patch = diffy::Patch::from_str(&schemas_diff)?;
+            diffy::apply(&definition.schemas, &schemas_patch)?
+        }
+        _ => definition.schemas,
+    };
+
+    let events = match definition_diff.events {
+        Some(events_diff) => {
+            let events_patch = diffy::Patch::from_str(&events_diff)?;
+            diffy::apply(&definition.events, &events_patch)?
+        }
+        _ => definition.events,
+    };
+
+    Ok(definitions::SchemaMigrationDefinition { schemas, events })
 }
 
-fn get_migration_files_to_execute<'a>(
-    migrations_files: &'a LsResult,
-    up: Option<String>,
-    migrations_applied: &'a Vec<ScriptMigration>,
-) -> Vec<&'a HashMap<DirEntryAttr, DirEntryValue>> {
-    get_sorted_migrations_files(&migrations_files)
+fn are_definitions_different(
+    definition: &definitions::SchemaMigrationDefinition,
+    other_definition: &definitions::SchemaMigrationDefinition,
+) -> bool {
+    definition.schemas.trim() != other_definition.schemas.trim()
+        || definition.events.trim() != other_definition.events.trim()
+}
+
+/// Remove the skipped migrations, warning about the ones found.
+fn skip_migrations(
+    migrations_files: Vec<SurqlFile>,
+    skip: &[String],
+    logger: Option<&(dyn Fn(&str) + Send + Sync)>,
+) -> Vec<SurqlFile> {
+    let (skipped_files, migrations_files): (Vec<_>, Vec<_>) = migrations_files
         .into_iter()
-        .filter(|migration_file| {
-            filter_migration_file_to_execute(migration_file, up.to_owned(), &migrations_applied)
-                .unwrap_or(false)
-        })
-        .collect::<Vec<_>>()
+        .partition(|migration_file| skip.contains(&migration_file.name));
+
+    if !skipped_files.is_empty() {
+        let skipped_names = skipped_files
+            .iter()
+            .map(|migration_file| migration_file.name.to_string())
+            .collect::<Vec<_>>();
+
+        warn(
+            logger,
+            &format!(
+                "the following migrations are skipped: {}",
+                skipped_names.join(", ")
+            ),
+        );
+    }
+
+    migrations_files
 }
 
-fn get_sorted_migrations_files(
-    migrations_files: &LsResult,
-) -> Vec<&HashMap<DirEntryAttr, DirEntryValue>> {
-    let mut sorted_migrations_files = migrations_files.items.iter().collect::<Vec<_>>();
-    sorted_migrations_files.sort_by(|a, b| {
-        let a = a.get(&DirEntryAttr::Name);
-        let b = b.get(&DirEntryAttr::Name);
-
-        let a = match a {
-            Some(DirEntryValue::String(a)) => Some(a),
-            _ => None,
-        };
+fn get_migration_files_to_execute(
+    migrations_files: Vec<SurqlFile>,
+    operation: ApplyOperation,
+    migrations_applied: &[ScriptMigration],
+    enabled_tags: &[String],
+) -> Result<Vec<SurqlFile>> {
+    let migrations_files = match &operation {
+        ApplyOperation::UpRange { from, to } => {
+            select_migration_range(migrations_files, from, to, migrations_applied)?
+        }
+        _ => migrations_files,
+    };
 
-        let b = match b {
-            Some(DirEntryValue::String(b)) => Some(b),
-            _ => None,
-        };
+    let mut migration_files_not_applied = Vec::new();
 
-        a.cmp(&b)
-    });
+    for migration_file in migrations_files {
+        if !filter_migration_file_to_execute(&migration_file, &operation, migrations_applied) {
+            continue;
+        }
 
-    sorted_migrations_files
+        // Migrations with disabled tags are skipped, so they are applied once their tags are enabled
+        if !MigrationMeta::parse(&migration_file.get_content()?)?.is_enabled(enabled_tags) {
+            continue;
+        }
+
+        migration_files_not_applied.push(migration_file);
+    }
+
+    if let ApplyOperation::UpStep(step) = operation {
+        migration_files_not_applied.truncate(step);
+    }
+
+    Ok(migration_files_not_applied)
 }
 
-fn filter_migration_file_to_execute(
-    migration_file: &&std::collections::HashMap<DirEntryAttr, DirEntryValue>,
-    up: Option<String>,
-    migrations_applied: &Vec<ScriptMigration>,
-) -> Result<bool> {
-    let is_file = migration_file
-        .get(&DirEntryAttr::IsFile)
-        .context("Cannot detect if the migration file is a file or a folder")?;
-    let is_file = match is_file {
-        DirEntryValue::Boolean(is_file) => Some(is_file),
-        _ => None,
+/// Keep the migrations between `from` and `to`, both included, none of them being already applied.
+fn select_migration_range(
+    migrations_files: Vec<SurqlFile>,
+    from: &str,
+    to: &str,
+    migrations_applied: &[ScriptMigration],
+) -> Result<Vec<SurqlFile>> {
+    let find_position = |name: &str| {
+        migrations_files
+            .iter()
+            .position(|migration_file| migration_file.name == name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Cannot apply migrations from '{}' to '{}': migration '{}' does not exist",
+                    from,
+                    to,
+                    name
+                )
+            })
     };
-    let is_file = is_file.context("Cannot detect if the migration file is a file or a folder")?;
 
-    if !is_file {
-        return Ok(false);
+    let from_position = find_position(from)?;
+    let to_position = find_position(to)?;
+
+    if from_position > to_position {
+        return Err(anyhow!(
+            "Cannot apply migrations from '{}' to '{}': '{}' must precede '{}'",
+            from,
+            to,
+            from,
+            to
+        ));
     }
 
-    let name = migration_file
-        .get(&DirEntryAttr::Name)
-        .context("Cannot get name of the migration file")?;
-    let name = match name {
-        DirEntryValue::String(name) => Some(name),
-        _ => None,
-    };
-    let name = name.context("Cannot get name of the migration file")?;
+    let range_files = migrations_files
+        .into_iter()
+        .skip(from_position)
+        .take(to_position - from_position + 1)
+        .collect::<Vec<_>>();
 
-    match &up {
-        Some(max_migration) => {
-            if name > max_migration {
-                return Ok(false);
-            }
+    let migrations_already_applied = range_files
+        .iter()
+        .filter(|migration_file| {
+            migrations_applied
+                .iter()
+                .any(|migration_applied| migration_applied.script_name == migration_file.name)
+        })
+        .map(|migration_file| migration_file.name.to_string())
+        .collect::<Vec<_>>();
+
+    if !migrations_already_applied.is_empty() {
+        return Err(MigrationError::RangeAlreadyApplied {
+            migrations: migrations_already_applied,
+        }
+        .into());
+    }
+
+    Ok(range_files)
+}
+
+fn filter_migration_file_to_execute(
+    migration_file: &SurqlFile,
+    operation: &ApplyOperation,
+    migrations_applied: &[ScriptMigration],
+) -> bool {
+    let name = &migration_file.name;
+
+    if let ApplyOperation::UpTo(max_migration) = operation {
+        if name > max_migration {
+            return false;
         }
-        None => {}
     }
 
     let has_already_been_applied = migrations_applied
         .iter()
         .any(|migration_applied| &migration_applied.script_name == name);
 
-    if has_already_been_applied {
-        return Ok(false);
-    }
+    !has_already_been_applied
+}
 
-    return Ok(true);
+struct ExecutedMigration {
+    name: String,
+    query: String,
+    /// Query recording the migration as applied.
+    record_query: String,
+    /// Values bound to the parameters of the record query.
+    record_params: QueryParams,
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn apply_migrations(
-    migration_files_to_execute: Vec<&HashMap<DirEntryAttr, DirEntryValue>>,
-    display_logs: bool,
-    client: Surreal<Client>,
+    migration_files_to_execute: Vec<SurqlFile>,
+    logger: Option<&(dyn Fn(&str) + Send + Sync)>,
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    transactional: bool,
     dry_run: bool,
-) -> Result<()> {
-    for migration_file in migration_files_to_execute {
-        let name = migration_file
-            .get(&DirEntryAttr::Name)
-            .context("Cannot get name of the migration file")?;
-        let name: Option<&String> = match name {
-            DirEntryValue::String(name) => Some(name),
-            _ => None,
+    on_progress: Option<&(dyn Fn(MigrationProgress) + Send + Sync)>,
+    statement_progress: bool,
+    vars: Option<&HashMap<String, String>>,
+    continue_on_error: bool,
+    migration_timeout: Option<Duration>,
+    migration_retry: MigrationRetry<'_>,
+    audit_params: &QueryParams,
+    clock: Option<&Clock>,
+    deadline: Option<Instant>,
+    statement_logger: StatementLogger<'_>,
+) -> Result<Vec<ExecutedMigration>> {
+    let mut migrations_executed = Vec::new();
+    let mut migrations_failed = Vec::new();
+
+    // Only fetched if a migration has directives
+    let mut cached_server_version = None;
+
+    // Only fetched if a migration has a `@use` header
+    let mut cached_default_target = None;
+
+    let total = migration_files_to_execute.len();
+
+    for (index, migration_file) in migration_files_to_execute.into_iter().enumerate() {
+        // A migration already started is never interrupted, the next ones are left pending
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            log(
+                logger,
+                &format!(
+                    "Deadline reached, {} migrations left pending",
+                    total - index
+                ),
+            );
+            break;
+        }
+
+        let name = migration_file.name.to_string();
+        let content = migration_file.get_content()?;
+        let checksum = checksum::compute_checksum(&content);
+
+        let content = match directives::has_directives(&content) {
+            true => {
+                let server_version = match &mut cached_server_version {
+                    Some(server_version) => server_version,
+                    None => cached_server_version.insert(server_version::get(client).await?),
+                };
+
+                directives::apply_version_directives(&content, server_version)
+                    .with_context(|| format!("Cannot apply migration '{}'", name))?
+            }
+            false => content,
         };
-        let name = name.context("Cannot get name of the migration file")?;
-
-        let path = migration_file
-            .get(&DirEntryAttr::Path)
-            .context("Cannot get path of the migration file")?;
-        let path = match path {
-            DirEntryValue::String(path) => Some(path),
-            _ => None,
+
+        let inner_query = match vars {
+            Some(vars) => variables::substitute_variables(&content, vars)
+                .with_context(|| format!("Cannot apply migration '{}'", name))?,
+            None => content.to_string(),
         };
-        let path = path.context("Cannot get path of the migration file")?;
 
-        let inner_query = fs_extra::file::read_to_string(path)?;
+        // The record of the migration is still created in the configured namespace and database
+        let inner_query = match MigrationMeta::parse(&content)?.target {
+            Some(target) => {
+                let default_target = match &mut cached_default_target {
+                    Some(default_target) => default_target,
+                    None => {
+                        cached_default_target.insert(surrealdb::get_current_target(client).await?)
+                    }
+                };
 
-        let query = format!(
-            "{}
-CREATE script_migration SET script_name = '{}';",
-            inner_query, name
+                target.wrap_query(&inner_query, default_target)
+            }
+            None => inner_query,
+        };
+
+        let now = clock::get_now_expression(clock);
+        let script_migration_query = format!(
+            "CREATE {} SET {} = $migration_name, checksum = $migration_checksum{}, {} = {};
+{}",
+            migrations_table,
+            migrations_table.script_name,
+            audit::get_audit_fields_query(audit_params),
+            migrations_table.executed_at,
+            migrations_table.executed_at_value(&now),
+            history::get_applied_query(migrations_table, &now)
         );
 
+        let mut record_params = audit_params.clone();
+        record_params.insert("migration_name", name.to_string());
+        record_params.insert("migration_checksum", checksum.to_string());
+
         let script_display_name = name
-            .split("_")
+            .split('_')
             .skip(2)
             .map(|s| s.to_string())
             .collect::<Vec<_>>()
             .join("_");
 
-        if display_logs {
-            println!("Executing migration {}...", script_display_name);
+        log(
+            logger,
+            &format!("Executing migration {}...", script_display_name),
+        );
+
+        let report_progress = |status| {
+            if let Some(on_progress) = on_progress {
+                on_progress(MigrationProgress {
+                    name: name.to_string(),
+                    index,
+                    total,
+                    status,
+                });
+            }
+        };
+
+        report_progress(MigrationProgressStatus::Started);
+
+        let report_statement_progress = |statement_index, statements_total| {
+            report_progress(MigrationProgressStatus::StatementExecuted {
+                index: statement_index,
+                total: statements_total,
+            })
+        };
+        let on_statement_executed = match statement_progress && on_progress.is_some() {
+            true => Some(&report_statement_progress as &(dyn Fn(usize, usize) + Send + Sync)),
+            false => None,
+        };
+
+        statement_logger.log_migration(&content, vars)?;
+        statement_logger.log(&script_migration_query);
+
+        let start = Instant::now();
+
+        // The database may still complete a query after its wait is aborted on timeout,
+        // so the migration is only recorded once it completed in time, outside of its transaction
+        let record_separately = migration_timeout.is_some() && !dry_run;
+
+        let migration_future = apply_migration_with_retries(
+            client,
+            &name,
+            &inner_query,
+            (!record_separately).then_some(&script_migration_query),
+            &record_params,
+            transactional,
+            dry_run,
+            migration_retry,
+            logger,
+            on_statement_executed,
+        );
+
+        #[cfg(feature = "tracing")]
+        let migration_future = tracing::Instrument::instrument(
+            migration_future,
+            tracing::info_span!("migration", name = %name),
+        );
+
+        let migration_result = match migration_timeout {
+            Some(migration_timeout) => {
+                match tokio::time::timeout(migration_timeout, migration_future).await {
+                    Ok(result) => result,
+                    Err(_) => Err(MigrationError::Timeout {
+                        name: name.to_string(),
+                    }
+                    .into()),
+                }
+            }
+            None => migration_future.await,
+        };
+
+        let migration_result = match migration_result {
+            Ok(()) if record_separately => {
+                surrealdb::apply_query_with_params(client, &script_migration_query, &record_params)
+                    .await
+            }
+            result => result,
+        };
+
+        if let Err(source) = migration_result {
+            let error = match source.downcast::<MigrationError>() {
+                Ok(error) => error,
+                Err(source) => MigrationError::MigrationFailed {
+                    name: name.to_string(),
+                    source,
+                },
+            };
+
+            match continue_on_error {
+                true => {
+                    migrations_failed.push(error);
+                    continue;
+                }
+                false => return Err(error.into()),
+            }
+        }
+
+        if !dry_run {
+            let duration_ms = start.elapsed().as_millis();
+            let duration_query = format!(
+                "UPDATE {} SET duration_ms = {} WHERE {} = $migration_name;",
+                migrations_table, duration_ms, migrations_table.script_name
+            );
+            statement_logger.log(&duration_query);
+            surrealdb::apply_query_with_params(client, &duration_query, &record_params).await?;
         }
 
+        report_progress(MigrationProgressStatus::Completed);
+
+        migrations_executed.push(ExecutedMigration {
+            name,
+            query: inner_query,
+            record_query: script_migration_query,
+            record_params,
+        });
+    }
+
+    if !migrations_failed.is_empty() {
+        return Err(MigrationError::MigrationsFailed(migrations_failed).into());
+    }
+
+    Ok(migrations_executed)
+}
+
+/// Apply a migration, retrying it while it fails with a transient error.
+/// The migration is recorded in the same query when a record query is given.
+/// The migration timeout applies to all the attempts.
+#[allow(clippy::too_many_arguments)]
+async fn apply_migration_with_retries(
+    client: &Surreal<Any>,
+    name: &str,
+    inner_query: &String,
+    script_migration_query: Option<&String>,
+    record_params: &QueryParams,
+    transactional: bool,
+    dry_run: bool,
+    migration_retry: MigrationRetry<'_>,
+    logger: Option<&(dyn Fn(&str) + Send + Sync)>,
+    on_statement_executed: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+) -> Result<()> {
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        let result = apply_migration(
+            client,
+            name,
+            inner_query,
+            script_migration_query,
+            record_params,
+            transactional,
+            dry_run,
+            on_statement_executed,
+        )
+        .await;
+
+        match result {
+            Err(error) if migration_retry.should_retry(&error, attempts) => {
+                log(
+                    logger,
+                    &format!(
+                        "Migration {} failed with a transient error, retrying ({}/{})...",
+                        name, attempts, migration_retry.retries
+                    ),
+                );
+
+                tokio::time::sleep(migration_retry.get_delay(attempts)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn apply_migration(
+    client: &Surreal<Any>,
+    name: &str,
+    inner_query: &String,
+    script_migration_query: Option<&String>,
+    record_params: &QueryParams,
+    transactional: bool,
+    dry_run: bool,
+    on_statement_executed: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+) -> Result<()> {
+    #[cfg(feature = "tracing")]
+    tracing::info!("Migration started");
+
+    // A dry run relies on a cancelled transaction, so it is always transactional
+    let result = if transactional || dry_run {
+        let query = match script_migration_query {
+            Some(script_migration_query) => format!("{}\n{}", inner_query, script_migration_query),
+            None => inner_query.to_string(),
+        };
+
         let transaction_action = get_transaction_action(dry_run);
-        surrealdb::apply_in_transaction(&client, &query, transaction_action).await?;
+        surrealdb::apply_in_transaction_with_params(
+            client,
+            &query,
+            transaction_action,
+            record_params,
+        )
+        .await
+    } else {
+        let statements_result = match on_statement_executed {
+            Some(on_statement_executed) => {
+                apply_migration_statements_one_by_one(
+                    client,
+                    name,
+                    inner_query,
+                    on_statement_executed,
+                )
+                .await
+            }
+            None => apply_migration_statements(client, name, inner_query).await,
+        };
+
+        match (statements_result, script_migration_query) {
+            (Ok(()), Some(script_migration_query)) => {
+                surrealdb::apply_query_with_params(client, script_migration_query, record_params)
+                    .await
+            }
+            (result, _) => result,
+        }
+    };
+
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(()) => tracing::info!("Migration finished"),
+        Err(error) => tracing::error!(%error, "Migration failed"),
     }
 
-    Ok(())
+    result
+}
+
+/// Apply the statements of a migration outside of a transaction, where a failed statement does not
+/// prevent the others from being applied. Reports the applied statements if some statements failed.
+async fn apply_migration_statements(
+    client: &Surreal<Any>,
+    name: &str,
+    query: &String,
+) -> Result<()> {
+    let mut response = client.query(query).await?;
+    let statements_count = response.num_statements();
+    let mut errors = response.take_errors();
+
+    let Some(statement_index) = errors.keys().min().copied() else {
+        return Ok(());
+    };
+    let source = errors
+        .remove(&statement_index)
+        .context("Cannot get the error of the failed statement")?;
+
+    if errors.len() + 1 == statements_count {
+        return Err(source.into());
+    }
+
+    let statements = sql::parse(query)
+        .context(format!(
+            "Migration '{}' has been partially applied, but its statements cannot be parsed to report them",
+            name
+        ))?
+        .0
+         .0;
+
+    let applied_statements = (0..statements_count)
+        .filter(|index| *index != statement_index && !errors.contains_key(index))
+        .map(|index| {
+            statements
+                .get(index)
+                .map(|statement| statement.to_string())
+                .context(format!(
+                    "Cannot get the statement {} of migration '{}'",
+                    index, name
+                ))
+        })
+        .collect::<Result<_>>()?;
+
+    Err(MigrationError::PartiallyApplied {
+        name: name.to_string(),
+        statement_index,
+        applied_statements,
+        source: source.into(),
+    }
+    .into())
+}
+
+/// Apply the statements of a migration one by one outside of a transaction, reporting each statement executed.
+/// `USE` statements are sent again with each next statement, as a query does not change the namespace
+/// and database of the next ones. Migrations defining variables or managing their own transactions
+/// are applied as a single query, as variables and transactions are not shared between queries.
+async fn apply_migration_statements_one_by_one(
+    client: &Surreal<Any>,
+    name: &str,
+    query: &String,
+    on_statement_executed: &(dyn Fn(usize, usize) + Send + Sync),
+) -> Result<()> {
+    let statements = match sql::parse(query) {
+        Ok(query) => query.0 .0,
+        Err(_) => return apply_migration_statements(client, name, query).await,
+    };
+
+    if statements.iter().any(|statement| {
+        matches!(
+            statement,
+            Statement::Set(_) | Statement::Begin(_) | Statement::Commit(_) | Statement::Cancel(_)
+        )
+    }) {
+        return apply_migration_statements(client, name, query).await;
+    }
+
+    let statements_total = statements
+        .iter()
+        .filter(|statement| !matches!(statement, Statement::Use(_)))
+        .count();
+
+    let mut use_statement = None;
+    let mut statement_index = 0;
+    let mut applied_statements = Vec::new();
+    let mut first_error = None;
+
+    for statement in statements {
+        if let Statement::Use(_) = statement {
+            use_statement = Some(statement.to_string());
+            continue;
+        }
+
+        let statement_query = match &use_statement {
+            Some(use_statement) => format!("{};\n{};", use_statement, statement),
+            None => format!("{};", statement),
+        };
+
+        // The statements already applied are still reported when the query cannot be sent
+        let mut response = match client.query(statement_query).await {
+            Ok(response) => response,
+            Err(error) => {
+                first_error.get_or_insert((statement_index, error));
+                break;
+            }
+        };
+        let error = response.take_errors().into_values().next();
+
+        match error {
+            Some(error) => {
+                first_error.get_or_insert((statement_index, error));
+            }
+            None => applied_statements.push(statement.to_string()),
+        }
+
+        on_statement_executed(statement_index, statements_total);
+        statement_index += 1;
+    }
+
+    let Some((statement_index, source)) = first_error else {
+        return Ok(());
+    };
+
+    if applied_statements.is_empty() {
+        return Err(source.into());
+    }
+
+    Err(MigrationError::PartiallyApplied {
+        name: name.to_string(),
+        statement_index,
+        applied_statements,
+        source: source.into(),
+    }
+    .into())
 }
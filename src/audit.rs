@@ -0,0 +1,85 @@
+use std::env;
+
+use crate::surrealdb::QueryParams;
+
+/// Get the name of the OS user running the current process, if known.
+pub fn get_username() -> Option<String> {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .ok()
+        .filter(|username| !username.is_empty())
+}
+
+/// Get the name of the host running the current process, if known.
+pub fn get_hostname() -> Option<String> {
+    env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
+
+/// Get who applied a migration and from where, bound to the parameters of the audit fields.
+pub fn get_audit_params(
+    applied_by: Option<String>,
+    applied_from_host: Option<String>,
+) -> QueryParams {
+    let mut params = QueryParams::new();
+
+    if let Some(applied_by) = applied_by.or_else(get_username) {
+        params.insert("migration_applied_by", applied_by);
+    }
+    if let Some(applied_from_host) = applied_from_host.or_else(get_hostname) {
+        params.insert("migration_applied_from_host", applied_from_host);
+    }
+
+    params
+}
+
+/// Get the fields to set on a migration record to know who applied it and from where,
+/// for the audit parameters bound to the query.
+pub fn get_audit_fields_query(audit_params: &QueryParams) -> String {
+    let mut fields = Vec::new();
+
+    if audit_params.contains_key("migration_applied_by") {
+        fields.push("applied_by = $migration_applied_by");
+    }
+    if audit_params.contains_key("migration_applied_from_host") {
+        fields.push("applied_from_host = $migration_applied_from_host");
+    }
+
+    fields
+        .into_iter()
+        .map(|field| format!(", {}", field))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_audit_params_should_use_explicit_values() {
+        let result = get_audit_params(Some("admin".to_string()), Some("server-1".to_string()));
+
+        assert_eq!(
+            result,
+            QueryParams::from([
+                ("migration_applied_by", "admin".to_string()),
+                ("migration_applied_from_host", "server-1".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn get_audit_fields_query_should_bind_values() {
+        let audit_params =
+            get_audit_params(Some("admin".to_string()), Some("server-1".to_string()));
+
+        assert_eq!(
+            get_audit_fields_query(&audit_params),
+            ", applied_by = $migration_applied_by, applied_from_host = $migration_applied_from_host"
+        );
+    }
+}
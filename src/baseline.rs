@@ -0,0 +1,81 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{Context, Result};
+
+use crate::{
+    checksum,
+    clock::{self, Clock},
+    io::{self, FilesLocation},
+    lock,
+    surrealdb::{self, MigrationsTable, TransactionAction},
+};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    name: &str,
+    location: &FilesLocation,
+    lock_timeout_ms: Option<u64>,
+    lock_ttl_ms: Option<u64>,
+    clock: Option<&Clock>,
+) -> Result<()> {
+    let owner = lock::acquire(client, migrations_table, lock_timeout_ms, lock_ttl_ms).await?;
+    let result = baseline(client, migrations_table, name, location, clock).await;
+    let release_result = lock::release(client, migrations_table, &owner).await;
+
+    result?;
+    release_result?;
+
+    Ok(())
+}
+
+async fn baseline(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    name: &str,
+    location: &FilesLocation,
+    clock: Option<&Clock>,
+) -> Result<()> {
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    let position = migrations_files
+        .iter()
+        .position(|migration_file| migration_file.name == name)
+        .context(format!(
+            "Cannot baseline to migration '{}': this migration does not exist",
+            name
+        ))?;
+
+    let mut queries = Vec::new();
+
+    for migration_file in migrations_files.iter().take(position + 1) {
+        let has_already_been_applied = migrations_applied
+            .iter()
+            .any(|migration_applied| migration_applied.script_name == migration_file.name);
+
+        if has_already_been_applied {
+            continue;
+        }
+
+        let checksum = checksum::compute_checksum(&migration_file.get_content()?);
+
+        queries.push(format!(
+            "CREATE {} SET {} = '{}', checksum = '{}', {} = {};",
+            migrations_table,
+            migrations_table.script_name,
+            migration_file.name,
+            checksum,
+            migrations_table.executed_at,
+            migrations_table.executed_at_value(&clock::get_now_expression(clock))
+        ));
+    }
+
+    if queries.is_empty() {
+        return Ok(());
+    }
+
+    surrealdb::apply_in_transaction(client, &queries.join("\n"), TransactionAction::Commit).await
+}
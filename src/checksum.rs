@@ -0,0 +1,20 @@
+use sha2::{Digest, Sha256};
+
+pub fn compute_checksum(content: &str) -> String {
+    let hash = Sha256::digest(content.as_bytes());
+    format!("{:x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_checksum_should_return_sha256_hex_digest() {
+        let result = compute_checksum("CREATE post;");
+
+        assert_eq!(result.len(), 64);
+        assert_eq!(result, compute_checksum("CREATE post;"));
+        assert_ne!(result, compute_checksum("CREATE post; "));
+    }
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// A function returning the current date, used to stamp the records instead of the date of the database.
+pub type Clock = dyn Fn() -> DateTime<Utc> + Send + Sync;
+
+/// Get the SurrealQL expression of the current date, given by the clock if any, otherwise by the database.
+pub fn get_now_expression(clock: Option<&Clock>) -> String {
+    match clock {
+        Some(clock) => format!(
+            "<datetime> '{}'",
+            clock().to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        ),
+        None => "time::now()".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn get_now_expression_should_use_database_date_without_clock() {
+        assert_eq!(get_now_expression(None), "time::now()");
+    }
+
+    #[test]
+    fn get_now_expression_should_use_date_of_clock() {
+        let clock = || Utc.with_ymd_and_hms(2023, 3, 17, 15, 32, 1).unwrap();
+
+        assert_eq!(
+            get_now_expression(Some(&clock)),
+            "<datetime> '2023-03-17T15:32:01Z'"
+        );
+    }
+}
@@ -1,21 +1,63 @@
+use anyhow::{anyhow, Context, Result};
 use ini::Ini;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub struct DbConfig {
+const CONFIG_FILE_NAME: &str = ".surrealdb";
+
+/// The settings of the `.surrealdb` project config file, read from the current folder.
+///
+/// These settings are the ones used by the CLI and the library when a value is not set otherwise.
+/// The precedence is, from highest to lowest:
+/// 1. the value set in the `SurrealdbConfiguration`, e.g. `url` or `folder`,
+/// 2. the value set in the `.surrealdb` config file,
+/// 3. the default value documented in `SurrealdbConfiguration`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use surrealdb_migrations::ProjectConfig;
+///
+/// let project_config = ProjectConfig::load();
+///
+/// if let Some(url) = project_config.url {
+///     println!("Migrations are applied to {}", url);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectConfig {
+    /// Root folder containing the `schemas`, `events` and `migrations` folders (`path` of the `core` section).
+    pub path: Option<String>,
+    /// Url of the surrealdb instance (`url` of the `db` section).
     pub url: Option<String>,
+    /// Username used to authenticate to the surrealdb instance (`username` of the `db` section).
     pub username: Option<String>,
+    /// Password used to authenticate to the surrealdb instance (`password` of the `db` section).
     pub password: Option<String>,
+    /// Namespace to use inside the surrealdb instance (`ns` of the `db` section).
     pub ns: Option<String>,
+    /// Name of the database to use inside the surrealdb instance (`db` of the `db` section).
     pub db: Option<String>,
 }
 
-fn load_config() -> Option<Ini> {
-    let surrealdb_config_file = Path::new(".surrealdb");
-    Ini::load_from_file(surrealdb_config_file).ok()
+impl ProjectConfig {
+    /// Load the `.surrealdb` config file of the current folder.
+    /// Every setting is `None` if the file does not exist or cannot be parsed, like when it is used by the crate.
+    pub fn load() -> ProjectConfig {
+        match Ini::load_from_file(Path::new(CONFIG_FILE_NAME)) {
+            Ok(config) => ProjectConfig {
+                path: retrieve_config_value(&config, "core", "path"),
+                url: retrieve_config_value(&config, "db", "url"),
+                username: retrieve_config_value(&config, "db", "username"),
+                password: retrieve_config_value(&config, "db", "password"),
+                ns: retrieve_config_value(&config, "db", "ns"),
+                db: retrieve_config_value(&config, "db", "db"),
+            },
+            Err(_) => ProjectConfig::default(),
+        }
+    }
 }
 
-fn retrieve_config_value(section: &str, key: &str) -> Option<String> {
-    let config = load_config()?;
+fn retrieve_config_value(config: &Ini, section: &str, key: &str) -> Option<String> {
     let section = config.section(Some(section))?;
     let value = section.get(key)?;
 
@@ -23,15 +65,26 @@ fn retrieve_config_value(section: &str, key: &str) -> Option<String> {
 }
 
 pub fn retrieve_folder_path() -> Option<String> {
-    retrieve_config_value("core", "path")
+    ProjectConfig::load().path
 }
 
-pub fn retrieve_db_config() -> DbConfig {
-    DbConfig {
-        url: retrieve_config_value("db", "url"),
-        username: retrieve_config_value("db", "username"),
-        password: retrieve_config_value("db", "password"),
-        ns: retrieve_config_value("db", "ns"),
-        db: retrieve_config_value("db", "db"),
+/// Use the root folder provided, if any, instead of the one set in the config file.
+pub fn resolve_folder_path(folder: &Option<PathBuf>) -> Result<Option<String>> {
+    match folder {
+        Some(folder) => {
+            if !folder.is_dir() {
+                return Err(anyhow!(
+                    "The migrations root folder '{}' does not exist",
+                    folder.display()
+                ));
+            }
+
+            let folder_path = folder
+                .to_str()
+                .context("The migrations root folder path is not valid unicode")?;
+
+            Ok(Some(folder_path.to_string()))
+        }
+        None => Ok(retrieve_folder_path()),
     }
 }
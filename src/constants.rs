@@ -1,3 +1,16 @@
 pub const SCHEMAS_DIR_NAME: &str = "schemas";
 pub const EVENTS_DIR_NAME: &str = "events";
 pub const MIGRATIONS_DIR_NAME: &str = "migrations";
+pub const SEEDS_DIR_NAME: &str = "seeds";
+pub const UP_MIGRATION_SUFFIX: &str = ".up";
+pub const DOWN_MIGRATION_SUFFIX: &str = ".down";
+pub const MIGRATIONS_TABLE_NAME: &str = "script_migration";
+pub const SCRIPT_NAME_FIELD_NAME: &str = "script_name";
+pub const EXECUTED_AT_FIELD_NAME: &str = "executed_at";
+pub const IN_MEMORY_URL: &str = "mem://";
+pub const REMOTE_URL_SCHEMES: [&str; 4] = ["ws", "wss", "http", "https"];
+pub const MIGRATIONS_LOCK_TABLE_SUFFIX: &str = "_lock";
+pub const DEFINITION_CHECKSUMS_TABLE_SUFFIX: &str = "_definition_checksum";
+pub const SCHEMA_VERSION_TABLE_SUFFIX: &str = "_schema_version";
+pub const MIGRATIONS_HISTORY_TABLE_SUFFIX: &str = "_history";
+pub const REDACTED_SECRET: &str = "********";
@@ -0,0 +1,50 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+use serde::{de::IgnoredAny, Deserialize};
+
+use crate::{
+    io::{self, FilesLocation},
+    models::MigrationCounts,
+    surrealdb::MigrationsTable,
+};
+
+#[derive(Deserialize)]
+struct CountResult {
+    count: usize,
+}
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<MigrationCounts> {
+    let migrations_names = io::extract_migrations_files(location)?
+        .into_iter()
+        .map(|migration_file| migration_file.name)
+        .collect::<Vec<_>>();
+
+    // A migration recorded more than once is counted once as applied with its file
+    let query = format!(
+        "SELECT count() FROM {table} GROUP ALL;
+SELECT {script_name} FROM {table} WHERE {script_name} INSIDE $names GROUP BY {script_name};",
+        table = migrations_table,
+        script_name = migrations_table.script_name
+    );
+
+    let mut response = client
+        .query(query)
+        .bind(("names", &migrations_names))
+        .await?;
+
+    let applied: Option<CountResult> = response.take(0)?;
+    let applied_with_file: Vec<IgnoredAny> = response.take(1)?;
+
+    let applied = applied.map_or(0, |result| result.count);
+    let total = migrations_names.len();
+
+    Ok(MigrationCounts {
+        applied,
+        pending: total.saturating_sub(applied_with_file.len()),
+        total,
+    })
+}
@@ -4,6 +4,7 @@ use std::path::Path;
 use crate::{
     config,
     constants::{EVENTS_DIR_NAME, MIGRATIONS_DIR_NAME, SCHEMAS_DIR_NAME},
+    create_migration,
 };
 
 pub enum CreateOperation {
@@ -39,13 +40,7 @@ pub fn main(
         CreateOperation::Schema => format!("{}.surql", name),
         CreateOperation::Event => format!("{}.surql", name),
         CreateOperation::Migration => {
-            let now = chrono::Local::now();
-            format!(
-                "{}_{}_{}.surql",
-                now.format("%Y%m%d"),
-                now.format("%H%M%S"),
-                name
-            )
+            format!("{}.surql", create_migration::get_migration_file_stem(&name))
         }
     };
 
@@ -0,0 +1,98 @@
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+use crate::{
+    constants::DOWN_MIGRATION_SUFFIX,
+    input::VersionFormat,
+    io::{self, FilesLocation},
+    version,
+};
+
+const INVALID_NAME_CHARACTERS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Create an empty migration file, and its down script if asked, in the migrations folder.
+/// Returns the path of the migration file.
+pub fn main(
+    location: &FilesLocation,
+    name: &str,
+    with_down_script: bool,
+    version_format: VersionFormat,
+) -> Result<PathBuf> {
+    if location.embedded_dir.is_some() {
+        return Err(anyhow!(
+            "Cannot create migration '{}' in an embedded directory",
+            name
+        ));
+    }
+
+    validate_name(name)?;
+
+    let migrations_dir_name = &location.dir_names.migrations;
+    let migrations_path = io::concat_path(&location.folder_path, migrations_dir_name);
+
+    if !migrations_path.exists() {
+        return Err(anyhow!("Directory {} doesn't exist", migrations_dir_name));
+    }
+
+    let file_stem = match version_format {
+        VersionFormat::Timestamp => get_migration_file_stem(name),
+        VersionFormat::Sequential => {
+            let migrations_files = io::extract_migrations_files(location)?;
+
+            let mut last_version = 0;
+            for migration_file in migrations_files {
+                let version = version::parse_version(&migration_file.name, version_format)?;
+                last_version = last_version.max(version);
+            }
+
+            format!("{:04}_{}", last_version + 1, name)
+        }
+    };
+
+    let file_path = migrations_path.join(format!("{}.surql", file_stem));
+    let down_file_path =
+        migrations_path.join(format!("{}{}.surql", file_stem, DOWN_MIGRATION_SUFFIX));
+
+    let mut file_paths = vec![&file_path];
+    if with_down_script {
+        file_paths.push(&down_file_path);
+    }
+
+    for file_path in &file_paths {
+        if file_path.exists() {
+            return Err(anyhow!("File {} already exists", file_path.display()));
+        }
+    }
+
+    for file_path in file_paths {
+        fs_extra::file::write_all(file_path, "")?;
+    }
+
+    Ok(file_path)
+}
+
+/// Get the name of a new migration file, without extension, prefixed with the current date and time.
+pub fn get_migration_file_stem(name: &str) -> String {
+    let now = chrono::Local::now();
+    format!("{}_{}_{}", now.format("%Y%m%d"), now.format("%H%M%S"), name)
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(anyhow!("The migration name cannot be empty"));
+    }
+
+    let invalid_character = name
+        .chars()
+        .find(|c| INVALID_NAME_CHARACTERS.contains(c) || c.is_control());
+
+    if let Some(invalid_character) = invalid_character {
+        return Err(anyhow!(
+            "Invalid migration name '{}': the character '{}' is not allowed in a file name",
+            name,
+            invalid_character.escape_default()
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use ::surrealdb::{
+    engine::any::Any,
+    sql::{self, statements::DefineStatement, Statement},
+    Surreal,
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::surrealdb::{self, MigrationsTable, QueryParams};
+
+#[derive(Deserialize)]
+struct DefinitionChecksum {
+    file: String,
+    checksum: String,
+}
+
+/// An event defined by an event file, removed when the migration that introduced the file is reverted.
+#[derive(Serialize, Deserialize)]
+pub struct EventDefinition {
+    pub name: String,
+    pub table: String,
+}
+
+#[derive(Deserialize)]
+struct IntroducedEvents {
+    #[serde(default)]
+    events: Vec<EventDefinition>,
+}
+
+/// List the checksum of each schema/event file when it was last applied, by file.
+pub async fn list(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<HashMap<String, String>> {
+    let definition_checksums: Vec<DefinitionChecksum> = client
+        .select(migrations_table.definition_checksums_table())
+        .await?;
+
+    let definition_checksums = definition_checksums
+        .into_iter()
+        .map(|definition_checksum| (definition_checksum.file, definition_checksum.checksum))
+        .collect();
+
+    Ok(definition_checksums)
+}
+
+/// Define the checksums table, which is otherwise created along with its first record.
+pub async fn define_table(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<()> {
+    let query = format!(
+        "DEFINE TABLE {} SCHEMALESS;",
+        migrations_table.definition_checksums_table()
+    );
+    client.query(query).await?.check()?;
+
+    Ok(())
+}
+
+/// Get the query saving the checksum of a schema/event file that has just been applied.
+/// The queries of several files are applied together, so the values are defined as parameters
+/// by the query itself rather than bound to it.
+pub fn get_save_query(
+    migrations_table: MigrationsTable<'_>,
+    file: &str,
+    checksum: &str,
+) -> Result<String> {
+    let params = QueryParams::from([
+        ("definition_file", file.to_string()),
+        ("definition_checksum", checksum.to_string()),
+    ]);
+
+    Ok(format!(
+        "{params}
+DELETE {table} WHERE file = $definition_file;
+CREATE {table} SET file = $definition_file, checksum = $definition_checksum;",
+        params = surrealdb::get_params_definition_query(&params)?,
+        table = migrations_table.definition_checksums_table(),
+    ))
+}
+
+/// Get the query saving the checksum of an event file that has just been applied, with the events it defines.
+/// The first time the file is applied, the migration introducing it is saved too,
+/// so that its events are removed when this migration is reverted.
+pub fn get_save_event_query(
+    migrations_table: MigrationsTable<'_>,
+    file: &str,
+    checksum: &str,
+    events: &[EventDefinition],
+    is_first_apply: bool,
+    introduced_by: Option<&str>,
+) -> Result<String> {
+    let events = serde_json::to_string(events)?;
+
+    let mut params = QueryParams::from([
+        ("definition_file", file.to_string()),
+        ("definition_checksum", checksum.to_string()),
+    ]);
+
+    let query = match is_first_apply {
+        true => {
+            let introduced_by = match introduced_by {
+                Some(introduced_by) => {
+                    params.insert("definition_introduced_by", introduced_by.to_string());
+                    "$definition_introduced_by"
+                }
+                None => "NONE",
+            };
+
+            format!(
+                "DELETE {table} WHERE file = $definition_file;
+CREATE {table} SET file = $definition_file, checksum = $definition_checksum, events = {events}, introduced_by = {introduced_by};",
+                table = migrations_table.definition_checksums_table(),
+            )
+        }
+        false => format!(
+            "UPDATE {table} SET checksum = $definition_checksum, events = {events} WHERE file = $definition_file;",
+            table = migrations_table.definition_checksums_table(),
+        ),
+    };
+
+    Ok(format!(
+        "{}\n{}",
+        surrealdb::get_params_definition_query(&params)?,
+        query
+    ))
+}
+
+/// Extract the events defined by an event file.
+/// A file that cannot be parsed defines no event to remove.
+pub fn extract_event_definitions(content: &str) -> Vec<EventDefinition> {
+    let Ok(query) = sql::parse(content) else {
+        return Vec::new();
+    };
+
+    query
+        .0
+         .0
+        .into_iter()
+        .filter_map(|statement| match statement {
+            Statement::Define(DefineStatement::Event(event)) => Some(EventDefinition {
+                name: event.name.to_string(),
+                table: event.what.to_string(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Get the query removing the events introduced by a migration, and forgetting their files
+/// so that they are applied again next time.
+pub async fn get_remove_introduced_events_query(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    migration_name: &str,
+) -> Result<String> {
+    let definition_checksums_table = migrations_table.definition_checksums_table();
+
+    let mut response = client
+        .query(format!(
+            "SELECT events FROM {} WHERE introduced_by = $name;",
+            definition_checksums_table
+        ))
+        .bind(("name", migration_name))
+        .await?;
+    let introduced_events: Vec<IntroducedEvents> = response.take(0)?;
+
+    if introduced_events.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut queries = introduced_events
+        .into_iter()
+        .flat_map(|introduced_events| introduced_events.events)
+        .map(|event| format!("REMOVE EVENT {} ON TABLE {};", event.name, event.table))
+        .collect::<Vec<_>>();
+
+    let params = QueryParams::from([("definition_introduced_by", migration_name.to_string())]);
+    queries.push(surrealdb::get_params_definition_query(&params)?);
+    queries.push(format!(
+        "DELETE {} WHERE introduced_by = $definition_introduced_by;",
+        definition_checksums_table
+    ));
+
+    Ok(queries.join("\n"))
+}
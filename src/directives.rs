@@ -0,0 +1,274 @@
+use anyhow::{anyhow, Context, Result};
+use semver::{BuildMetadata, Version};
+
+/// Prefixes of the comments containing a directive, like `-- @if version >= 1.5`.
+const COMMENT_PREFIXES: [&str; 3] = ["--", "//", "#"];
+
+/// Keywords starting a directive, other comments starting with `@` like `-- @todo` being ignored.
+const DIRECTIVE_KEYWORDS: [&str; 3] = ["@if", "@else", "@endif"];
+
+enum Directive {
+    If(bool),
+    Else,
+    EndIf,
+}
+
+struct Block {
+    is_parent_kept: bool,
+    condition: bool,
+    is_in_else: bool,
+}
+
+impl Block {
+    fn is_kept(&self) -> bool {
+        self.is_parent_kept && self.condition != self.is_in_else
+    }
+}
+
+/// Check if the content of a migration contains directives, so that the server version is needed.
+pub fn has_directives(content: &str) -> bool {
+    content
+        .lines()
+        .any(|line| get_directive_text(line).is_some())
+}
+
+/// Keep or remove the blocks surrounded by `-- @if version <operator> <version>` and `-- @endif` directives,
+/// depending on the version of the SurrealDB server, with an optional `-- @else` block.
+/// Blocks can be nested. The removed lines and the directives are replaced by empty lines,
+/// so that the line numbers of the errors still match the migration file.
+pub fn apply_version_directives(content: &str, server_version: &Version) -> Result<String> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut lines = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let is_kept = blocks.last().is_none_or(Block::is_kept);
+
+        let Some(directive_text) = get_directive_text(line) else {
+            lines.push(if is_kept { line } else { "" });
+            continue;
+        };
+
+        let directive = parse_directive(directive_text, server_version)
+            .with_context(|| format!("Invalid directive at line {}", line_number))?;
+
+        match directive {
+            Directive::If(condition) => blocks.push(Block {
+                is_parent_kept: is_kept,
+                condition,
+                is_in_else: false,
+            }),
+            Directive::Else => match blocks.last_mut() {
+                Some(block) if !block.is_in_else => block.is_in_else = true,
+                _ => return Err(anyhow!("Unexpected @else at line {}", line_number)),
+            },
+            Directive::EndIf => {
+                if blocks.pop().is_none() {
+                    return Err(anyhow!("Unexpected @endif at line {}", line_number));
+                }
+            }
+        }
+
+        lines.push("");
+    }
+
+    if !blocks.is_empty() {
+        return Err(anyhow!("Missing @endif at the end of the migration"));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Get the text of a directive comment, starting with one of the directive keywords.
+fn get_directive_text(line: &str) -> Option<&str> {
+    let line = line.trim();
+
+    COMMENT_PREFIXES
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+        .map(str::trim)
+        .filter(|text| {
+            text.split_whitespace()
+                .next()
+                .is_some_and(|keyword| DIRECTIVE_KEYWORDS.contains(&keyword))
+        })
+}
+
+fn parse_directive(text: &str, server_version: &Version) -> Result<Directive> {
+    let parts = text.split_whitespace().collect::<Vec<_>>();
+
+    match parts[..] {
+        ["@if", "version", operator, version] => {
+            let version = parse_version(version)?;
+            let condition = evaluate(server_version, operator, &version)?;
+            Ok(Directive::If(condition))
+        }
+        ["@if", ..] => Err(anyhow!(
+            "Expected '@if version <operator> <version>', found '{}'",
+            text
+        )),
+        ["@else"] => Ok(Directive::Else),
+        ["@endif"] => Ok(Directive::EndIf),
+        _ => Err(anyhow!("Unexpected text after the directive in '{}'", text)),
+    }
+}
+
+/// Parse a version, the missing minor and patch numbers being 0, like `1.5` for `1.5.0`.
+fn parse_version(version: &str) -> Result<Version> {
+    let position = version.find(['-', '+']).unwrap_or(version.len());
+    let (numbers, suffix) = version.split_at(position);
+
+    let missing_numbers = 3usize.saturating_sub(numbers.split('.').count());
+    let full_version = format!("{}{}{}", numbers, ".0".repeat(missing_numbers), suffix);
+
+    Version::parse(&full_version).context(format!("Invalid version '{}'", version))
+}
+
+/// Compare the server version with a version, build metadata ignored.
+fn evaluate(server_version: &Version, operator: &str, version: &Version) -> Result<bool> {
+    let server_version = Version {
+        build: BuildMetadata::EMPTY,
+        ..server_version.clone()
+    };
+    let version = Version {
+        build: BuildMetadata::EMPTY,
+        ..version.clone()
+    };
+
+    match operator {
+        ">=" => Ok(server_version >= version),
+        ">" => Ok(server_version > version),
+        "<=" => Ok(server_version <= version),
+        "<" => Ok(server_version < version),
+        "==" => Ok(server_version == version),
+        "!=" => Ok(server_version != version),
+        _ => Err(anyhow!(
+            "Unknown operator '{}', expected one of: >=, >, <=, <, ==, !=",
+            operator
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_version() -> Version {
+        Version::parse("1.2.3+20230402").unwrap()
+    }
+
+    #[test]
+    fn has_directives_should_detect_directive_comments() {
+        assert!(has_directives(
+            "CREATE user;\n-- @if version >= 1.0\n-- @endif"
+        ));
+        assert!(!has_directives("-- tags: analytics\nCREATE user; -- @if"));
+        assert!(!has_directives("-- @use ns shared db config\nCREATE user;"));
+        assert!(!has_directives(
+            "-- @todo add an index\n// @author jane\nCREATE user;"
+        ));
+    }
+
+    #[test]
+    fn apply_version_directives_should_keep_matching_blocks() {
+        let content = "CREATE a;
+-- @if version >= 1.2
+CREATE b;
+-- @else
+CREATE c;
+-- @endif
+// @if version < 1
+CREATE d;
+// @endif";
+
+        let result = apply_version_directives(content, &server_version());
+
+        assert_eq!(result.unwrap(), "CREATE a;\n\nCREATE b;\n\n\n\n\n\n");
+    }
+
+    #[test]
+    fn apply_version_directives_should_handle_nested_blocks() {
+        let content = "# @if version > 1
+# @if version == 1.2.3
+CREATE a;
+# @else
+CREATE b;
+# @endif
+# @else
+CREATE c;
+# @endif";
+
+        let result = apply_version_directives(content, &server_version());
+
+        assert_eq!(
+            result
+                .unwrap()
+                .lines()
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>(),
+            vec!["CREATE a;"]
+        );
+    }
+
+    #[test]
+    fn apply_version_directives_should_fail_on_missing_endif() {
+        let result =
+            apply_version_directives("-- @if version >= 1.0\nCREATE a;", &server_version());
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Missing @endif at the end of the migration"
+        );
+    }
+
+    #[test]
+    fn apply_version_directives_should_fail_on_unknown_operator() {
+        let result = apply_version_directives(
+            "CREATE a;\n-- @if version ~ 1.0\n-- @endif",
+            &server_version(),
+        );
+
+        assert_eq!(
+            format!("{:#}", result.unwrap_err()),
+            "Invalid directive at line 2: Unknown operator '~', expected one of: >=, >, <=, <, ==, !="
+        );
+    }
+
+    #[test]
+    fn apply_version_directives_should_keep_other_comments_starting_with_at() {
+        let content = "-- @todo add an index
+-- @if version >= 1.0
+CREATE a; -- @author jane
+-- @endif";
+
+        let result = apply_version_directives(content, &server_version());
+
+        assert_eq!(
+            result.unwrap(),
+            "-- @todo add an index\n\nCREATE a; -- @author jane\n"
+        );
+    }
+
+    #[test]
+    fn apply_version_directives_should_fail_on_malformed_directive() {
+        let result = apply_version_directives(
+            "-- @if version >= 1.0\nCREATE a;\n-- @endif version >= 1.0",
+            &server_version(),
+        );
+
+        assert_eq!(
+            format!("{:#}", result.unwrap_err()),
+            "Invalid directive at line 3: Unexpected text after the directive in '@endif version >= 1.0'"
+        );
+    }
+
+    #[test]
+    fn parse_version_should_complete_partial_versions() {
+        assert_eq!(parse_version("1").unwrap(), Version::new(1, 0, 0));
+        assert_eq!(parse_version("1.5").unwrap(), Version::new(1, 5, 0));
+        assert_eq!(
+            parse_version("1.0-beta.9").unwrap(),
+            Version::parse("1.0.0-beta.9").unwrap()
+        );
+    }
+}
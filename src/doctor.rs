@@ -0,0 +1,294 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::{
+    error::MigrationError,
+    input::{ExecutedAtFormat, VersionFormat},
+    io::{self, concat_path, FilesLocation, SurqlFile},
+    models::{DiagnosticCheck, DiagnosticCheckKind, DiagnosticReport, ScriptMigration},
+    surrealdb::{self, MigrationsTable},
+    validate_version_order, verify,
+};
+
+pub struct DoctorArgs<'a> {
+    pub client: Result<Surreal<Any>, MigrationError>,
+    pub location: Result<FilesLocation>,
+    pub migrations_table: MigrationsTable<'a>,
+    pub version_format: VersionFormat,
+    pub enabled_tags: &'a [String],
+    pub skip: &'a [String],
+}
+
+/// Run every check, a failing check never preventing the next ones from running.
+/// Checks needing the database or the migration files fail when they cannot be read.
+pub async fn main(args: DoctorArgs<'_>) -> DiagnosticReport {
+    let DoctorArgs {
+        client,
+        location,
+        migrations_table,
+        version_format,
+        enabled_tags,
+        skip,
+    } = args;
+
+    let mut checks = Vec::new();
+
+    checks.push(to_check(
+        DiagnosticCheckKind::Connection,
+        client
+            .as_ref()
+            .map(|_| Vec::new())
+            .map_err(|error| error.to_string()),
+    ));
+
+    let location = location.map_err(format_error);
+
+    checks.push(to_check(
+        DiagnosticCheckKind::Folders,
+        location
+            .as_ref()
+            .map_err(|error| error.to_string())
+            .and_then(|location| find_missing_folders(location).map_err(format_error)),
+    ));
+
+    let client = client.map_err(|_| "Not checked: cannot connect to the SurrealDB instance");
+
+    checks.push(to_check(
+        DiagnosticCheckKind::MigrationsTable,
+        match &client {
+            Ok(client) => check_migrations_table(client, migrations_table)
+                .await
+                .map_err(format_error),
+            Err(error) => Err(error.to_string()),
+        },
+    ));
+
+    let migrations_applied = match &client {
+        Ok(client) => {
+            surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+                .await
+                .map_err(|error| {
+                    format!(
+                        "Cannot list the migrations applied: {}",
+                        format_error(error)
+                    )
+                })
+        }
+        Err(error) => Err(error.to_string()),
+    };
+
+    let migrations_files = location.and_then(|location| {
+        io::extract_migrations_files(&location)
+            .map(|migrations_files| {
+                migrations_files
+                    .into_iter()
+                    .filter(|migration_file| !skip.contains(&migration_file.name))
+                    .collect::<Vec<_>>()
+            })
+            .map_err(|error| format!("Cannot read the migration files: {}", format_error(error)))
+    });
+
+    let inputs = match (&migrations_applied, &migrations_files) {
+        (Ok(migrations_applied), Ok(migrations_files)) => {
+            Ok((migrations_applied, migrations_files))
+        }
+        (Err(error), _) | (_, Err(error)) => Err(error.to_string()),
+    };
+
+    checks.push(to_check(
+        DiagnosticCheckKind::OrphanedMigrations,
+        inputs
+            .clone()
+            .map(|(migrations_applied, migrations_files)| {
+                find_orphaned_migrations(migrations_applied, migrations_files)
+            }),
+    ));
+
+    checks.push(to_check(
+        DiagnosticCheckKind::ChecksumMismatches,
+        inputs
+            .clone()
+            .and_then(|(migrations_applied, migrations_files)| {
+                verify::find_checksum_mismatches(migrations_applied, migrations_files)
+                    .map_err(format_error)
+            })
+            .map(|checksum_mismatches| {
+                // Migrations whose file no longer exists are reported as orphaned
+                checksum_mismatches
+                    .into_iter()
+                    .filter(|checksum_mismatch| checksum_mismatch.actual_checksum.is_some())
+                    .map(|checksum_mismatch| checksum_mismatch.script_name)
+                    .collect()
+            }),
+    ));
+
+    checks.push(to_check(
+        DiagnosticCheckKind::VersionOrder,
+        inputs.and_then(|(migrations_applied, migrations_files)| {
+            check_version_order(
+                migrations_applied,
+                migrations_files,
+                version_format,
+                enabled_tags,
+            )
+        }),
+    ));
+
+    DiagnosticReport { checks }
+}
+
+/// Create a check from the problems found, or from the error preventing it from running.
+fn to_check(kind: DiagnosticCheckKind, result: Result<Vec<String>, String>) -> DiagnosticCheck {
+    match result {
+        Ok(problems) => DiagnosticCheck {
+            kind,
+            passed: problems.is_empty(),
+            details: problems,
+        },
+        Err(error) => DiagnosticCheck {
+            kind,
+            passed: false,
+            details: vec![error],
+        },
+    }
+}
+
+fn format_error(error: anyhow::Error) -> String {
+    format!("{:#}", error)
+}
+
+fn find_missing_folders(location: &FilesLocation) -> Result<Vec<String>> {
+    let dir_names = &location.dir_names;
+    let mut missing_folders = Vec::new();
+
+    if !io::definition_folder_exists(location, &dir_names.schemas)? {
+        missing_folders.push(format_folder(location, &dir_names.schemas));
+    }
+
+    if location.folders.is_empty() {
+        if !io::definition_folder_exists(location, &dir_names.migrations)? {
+            missing_folders.push(format_folder(location, &dir_names.migrations));
+        }
+    } else {
+        for folder in &location.folders {
+            let migrations_path = Path::new(folder).join(&dir_names.migrations);
+
+            if !migrations_path.try_exists()? {
+                missing_folders.push(format!(
+                    "Folder '{}' does not exist",
+                    migrations_path.display()
+                ));
+            }
+        }
+    }
+
+    Ok(missing_folders)
+}
+
+fn format_folder(location: &FilesLocation, dir_name: &str) -> String {
+    let folder = match (location.embedded_dir, &location.source_files) {
+        (None, None) => concat_path(&location.folder_path, dir_name)
+            .display()
+            .to_string(),
+        _ => dir_name.to_string(),
+    };
+
+    format!("Folder '{}' does not exist", folder)
+}
+
+/// Check that the migrations table is defined, with the configured fields if it is schemafull.
+async fn check_migrations_table(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<Vec<String>> {
+    let mut response = client.query("INFO FOR DB;").await?;
+    let db_info: Option<serde_json::Value> = response.take(0)?;
+
+    let table_definition = db_info
+        .as_ref()
+        .and_then(|db_info| db_info["tb"][migrations_table.name].as_str());
+
+    let Some(table_definition) = table_definition else {
+        return Ok(vec![format!(
+            "The migrations table '{}' does not exist",
+            migrations_table
+        )]);
+    };
+
+    if !table_definition.contains("SCHEMAFULL") {
+        return Ok(Vec::new());
+    }
+
+    let mut response = client
+        .query(format!("INFO FOR TABLE {};", migrations_table))
+        .await?;
+    let table_info: Option<serde_json::Value> = response.take(0)?;
+    let table_info = table_info.context("Cannot get the definition of the migrations table")?;
+
+    let expected_executed_at_type = match migrations_table.executed_at_format {
+        ExecutedAtFormat::Datetime => "datetime",
+        ExecutedAtFormat::EpochMillis => "int",
+        ExecutedAtFormat::IsoString => "string",
+    };
+
+    let mut problems = Vec::new();
+
+    for (field, expected_type) in [
+        (migrations_table.script_name, "string"),
+        (migrations_table.executed_at, expected_executed_at_type),
+    ] {
+        match table_info["fd"][field].as_str() {
+            Some(field_definition) => {
+                if !field_definition.contains(&format!("TYPE {}", expected_type)) {
+                    problems.push(format!(
+                        "The field '{}' of the migrations table is not of type {}",
+                        field, expected_type
+                    ));
+                }
+            }
+            None => problems.push(format!(
+                "The field '{}' of the schemafull migrations table is not defined",
+                field
+            )),
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Find the migrations applied whose file no longer exists.
+fn find_orphaned_migrations(
+    migrations_applied: &[ScriptMigration],
+    migrations_files: &[SurqlFile],
+) -> Vec<String> {
+    migrations_applied
+        .iter()
+        .filter(|migration_applied| {
+            !migrations_files
+                .iter()
+                .any(|migration_file| migration_file.name == migration_applied.script_name)
+        })
+        .map(|migration_applied| migration_applied.script_name.to_string())
+        .collect()
+}
+
+fn check_version_order(
+    migrations_applied: &[ScriptMigration],
+    migrations_files: &[SurqlFile],
+    version_format: VersionFormat,
+    enabled_tags: &[String],
+) -> Result<Vec<String>, String> {
+    let result = validate_version_order::validate(
+        migrations_applied,
+        migrations_files,
+        version_format,
+        enabled_tags,
+    );
+
+    match result.map_err(MigrationError::from) {
+        Ok(()) => Ok(Vec::new()),
+        Err(MigrationError::VersionOrderViolation { migrations }) => Ok(migrations),
+        Err(error) => Err(error.to_string()),
+    }
+}
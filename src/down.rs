@@ -0,0 +1,187 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{Context, Result};
+
+use crate::{
+    clock::{self, Clock},
+    definition_checksums,
+    error::MigrationError,
+    history,
+    io::{self, FilesLocation},
+    lock,
+    migration_meta::MigrationMeta,
+    models::ScriptMigration,
+    surrealdb::{self, MigrationsTable, QueryParams, TransactionAction},
+};
+
+pub enum DownOperation {
+    Last,
+    To(String),
+    All,
+}
+
+pub struct DownArgs<'a> {
+    pub operation: DownOperation,
+    pub client: &'a Surreal<Any>,
+    pub migrations_table: MigrationsTable<'a>,
+    pub location: FilesLocation,
+    pub dry_run: bool,
+    pub clock: Option<&'a Clock>,
+    pub lock_timeout_ms: Option<u64>,
+    pub lock_ttl_ms: Option<u64>,
+}
+
+/// Revert migrations using their down scripts.
+/// Returns the down queries of the migrations reverted, or that would be reverted on a dry run.
+pub async fn main(args: DownArgs<'_>) -> Result<Vec<String>> {
+    // A dry run does not persist anything, so it does not need the lock
+    if args.dry_run {
+        return revert(args).await;
+    }
+
+    let client = args.client;
+    let migrations_table = args.migrations_table;
+
+    let owner = lock::acquire(
+        client,
+        args.migrations_table,
+        args.lock_timeout_ms,
+        args.lock_ttl_ms,
+    )
+    .await?;
+    let result = revert(args).await;
+    let release_result = lock::release(client, migrations_table, &owner).await;
+
+    let queries = result?;
+    release_result?;
+
+    Ok(queries)
+}
+
+/// Revert migrations without taking the migrations lock, for callers already holding it.
+pub async fn revert(args: DownArgs<'_>) -> Result<Vec<String>> {
+    let DownArgs {
+        operation,
+        client,
+        migrations_table,
+        location,
+        dry_run,
+        clock,
+        ..
+    } = args;
+
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let migrations_to_revert = get_migrations_to_revert(&migrations_applied, operation)?;
+
+    let mut down_scripts = Vec::new();
+    let mut migrations_without_down_script = Vec::new();
+
+    for migration in migrations_to_revert {
+        let name = &migration.script_name;
+
+        match io::get_down_migration_content(&location, name)? {
+            Some(inner_query) => down_scripts.push((name, inner_query)),
+            None => migrations_without_down_script.push(name.to_string()),
+        }
+    }
+
+    if !migrations_without_down_script.is_empty() {
+        return Err(MigrationError::MissingDownScript {
+            migrations: migrations_without_down_script,
+        }
+        .into());
+    }
+
+    let mut queries = Vec::new();
+
+    // Only fetched if a down script has a `@use` header
+    let mut cached_default_target = None;
+
+    for (name, inner_query) in down_scripts {
+        let inner_query = match MigrationMeta::parse(&inner_query)?.target {
+            Some(target) => {
+                let default_target = match &mut cached_default_target {
+                    Some(default_target) => default_target,
+                    None => {
+                        cached_default_target.insert(surrealdb::get_current_target(client).await?)
+                    }
+                };
+
+                target.wrap_query(&inner_query, default_target)
+            }
+            None => inner_query,
+        };
+
+        let remove_events_query = definition_checksums::get_remove_introduced_events_query(
+            client,
+            migrations_table,
+            name,
+        )
+        .await?;
+
+        let down_query = match remove_events_query.is_empty() {
+            true => inner_query,
+            false => format!("{}\n{}", inner_query, remove_events_query),
+        };
+
+        if !dry_run {
+            let query = format!(
+                "{}
+DELETE {} WHERE {} = $migration_name;
+{}",
+                down_query,
+                migrations_table,
+                migrations_table.script_name,
+                history::get_rolled_back_query(migrations_table, &clock::get_now_expression(clock))
+            );
+            let params = QueryParams::from([("migration_name", name.to_string())]);
+
+            surrealdb::apply_in_transaction_with_params(
+                client,
+                &query,
+                TransactionAction::Commit,
+                &params,
+            )
+            .await?;
+        }
+
+        queries.push(down_query);
+    }
+
+    Ok(queries)
+}
+
+fn get_migrations_to_revert(
+    migrations_applied: &[ScriptMigration],
+    operation: DownOperation,
+) -> Result<Vec<&ScriptMigration>> {
+    match operation {
+        DownOperation::Last => {
+            let last_migration_applied = migrations_applied
+                .last()
+                .context("Cannot revert migration: no migration has been applied yet")?;
+
+            Ok(vec![last_migration_applied])
+        }
+        DownOperation::To(name) => {
+            let position = migrations_applied
+                .iter()
+                .position(|migration| migration.script_name == name)
+                .context(format!(
+                    "Cannot revert to migration '{}': this migration has not been applied",
+                    name
+                ))?;
+
+            let migrations_to_revert = migrations_applied
+                .iter()
+                .skip(position + 1)
+                .rev()
+                .collect::<Vec<_>>();
+
+            Ok(migrations_to_revert)
+        }
+        DownOperation::All => Ok(migrations_applied.iter().rev().collect()),
+    }
+}
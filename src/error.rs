@@ -0,0 +1,163 @@
+use std::{collections::HashMap, time::Duration};
+use thiserror::Error;
+
+use crate::models::{ApplyReport, ChecksumMismatch};
+
+/// The errors that can occur when using the library.
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    /// Cannot connect, authenticate or select the namespace/database of the SurrealDB instance.
+    #[error(transparent)]
+    ConnectionFailed(anyhow::Error),
+    /// Cannot connect to the SurrealDB instance within the configured timeout.
+    #[error("Cannot connect to the SurrealDB instance within {timeout:?}")]
+    ConnectionTimeout {
+        /// Time waited for each connection attempt.
+        timeout: Duration,
+    },
+    /// Some migrations have not been applied while later migrations have been.
+    #[error("The following migrations have not been applied: {}", .migrations.join(", "))]
+    VersionOrderViolation {
+        /// Names of the migrations not applied.
+        migrations: Vec<String>,
+    },
+    /// Several migrations share the same version, so their order is undefined.
+    #[error("The following migrations share the same version: {}", .migrations.join(", "))]
+    DuplicateVersions {
+        /// File names of the migrations sharing a version.
+        migrations: Vec<String>,
+    },
+    /// Some migrations of the range to apply have already been applied.
+    #[error("The following migrations of the range have already been applied: {}", .migrations.join(", "))]
+    RangeAlreadyApplied {
+        /// Names of the migrations of the range already applied.
+        migrations: Vec<String>,
+    },
+    /// The health check query did not return the expected result.
+    #[error("Health check query '{query}' returned {actual} instead of {expected}")]
+    HealthCheckFailed {
+        /// The health check query.
+        query: String,
+        /// Expected result of the query.
+        expected: serde_json::Value,
+        /// Actual result of the query.
+        actual: serde_json::Value,
+    },
+    /// The SurrealDB server is older than the configured minimum version.
+    #[error("SurrealDB server version {version} is older than the minimum version {min_version}")]
+    ServerVersionTooOld {
+        /// Version of the SurrealDB server.
+        version: String,
+        /// Minimum version configured.
+        min_version: String,
+    },
+    /// A schema file does not define exactly one table, when schema files are strictly validated.
+    #[error("Schema file '{file}' defines {tables_count} tables instead of one")]
+    InvalidSchemaFile {
+        /// Path of the schema file.
+        file: String,
+        /// Number of tables defined by the schema file.
+        tables_count: usize,
+    },
+    /// Some migrations have been modified since they were applied.
+    #[error(
+        "The following migrations have been modified since they were applied: {}",
+        .0.iter().map(|mismatch| mismatch.script_name.to_string()).collect::<Vec<_>>().join(", ")
+    )]
+    ChecksumMismatch(Vec<ChecksumMismatch>),
+    /// Some migrations to revert do not have a down script.
+    #[error("The following migrations have no down script: {}", .migrations.join(", "))]
+    MissingDownScript {
+        /// Names of the migrations without down script.
+        migrations: Vec<String>,
+    },
+    /// A migration failed to be applied.
+    #[error("Failed to apply migration '{name}': {source}")]
+    MigrationFailed {
+        /// Name of the migration.
+        name: String,
+        /// The underlying error.
+        source: anyhow::Error,
+    },
+    /// Some statements of a migration failed while others were applied, outside of a transaction.
+    /// The database is left partially migrated, with the applied statements kept.
+    #[error(
+        "Migration '{name}' was partially applied: statement {statement_index} failed while {} other statements were applied: {source}",
+        .applied_statements.len()
+    )]
+    PartiallyApplied {
+        /// Name of the migration.
+        name: String,
+        /// Index of the first failed statement of the migration, starting from 0.
+        statement_index: usize,
+        /// Statements of the migration that were applied, in order.
+        applied_statements: Vec<String>,
+        /// The error of the first failed statement.
+        source: anyhow::Error,
+    },
+    /// A migration did not complete within the configured timeout.
+    #[error("Migration '{name}' did not complete within the configured timeout")]
+    Timeout {
+        /// Name of the migration.
+        name: String,
+    },
+    /// Some migrations failed to be applied, when continuing on error.
+    #[error(
+        "Some migrations failed to be applied: {}",
+        .0.iter().map(|error| error.to_string()).collect::<Vec<_>>().join("; ")
+    )]
+    MigrationsFailed(Vec<MigrationError>),
+    /// Migrations failed to be applied to some databases, when applying them to several databases.
+    #[error(
+        "Failed to apply migrations to some databases: {}",
+        format_database_errors(.errors)
+    )]
+    DatabasesFailed {
+        /// Reports of the databases the migrations were applied to.
+        reports: HashMap<String, ApplyReport>,
+        /// Errors of the databases the migrations failed to be applied to.
+        errors: HashMap<String, MigrationError>,
+    },
+    /// The migrations lock is held by another process and was not released in time.
+    #[error("Cannot acquire the migrations lock within {timeout_ms} ms, it is held by '{owner}'")]
+    LockTimeout {
+        /// Owner id of the process holding the lock.
+        owner: String,
+        /// Time waited for the lock, in milliseconds.
+        timeout_ms: u64,
+    },
+    /// The schema version changed while applying migrations, because another process applied migrations concurrently.
+    #[error(
+        "The schema version changed from {expected_version} to {actual_version} while applying migrations"
+    )]
+    ConcurrentModification {
+        /// Schema version read before applying migrations.
+        expected_version: u64,
+        /// Schema version found after applying migrations.
+        actual_version: u64,
+    },
+    /// Any other error.
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+/// Format the errors of each database, ordered by database name.
+fn format_database_errors(errors: &HashMap<String, MigrationError>) -> String {
+    let mut errors = errors.iter().collect::<Vec<_>>();
+    errors.sort_by(|a, b| a.0.cmp(b.0));
+
+    errors
+        .into_iter()
+        .map(|(database, error)| format!("{}: {}", database, error))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl From<anyhow::Error> for MigrationError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<MigrationError>() {
+            Ok(error) => error,
+            Err(error) => MigrationError::Other(error),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{anyhow, Result};
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{
+    models::SchemaDefinitionKind,
+    schema_diff::{self, Definitions},
+    surrealdb::MigrationsTable,
+};
+
+/// Write the table, field and index definitions of the database in one schema file per table.
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    out_dir: &Path,
+    schemas_dir_name: &str,
+) -> Result<()> {
+    let db_definitions = schema_diff::list_db_definitions(client, migrations_table).await?;
+    let tables_content = get_tables_content(db_definitions);
+
+    let schemas_path = out_dir.join(schemas_dir_name);
+
+    let files = tables_content
+        .into_iter()
+        .map(|(table, content)| (schemas_path.join(format!("{}.surql", table)), content))
+        .collect::<Vec<_>>();
+
+    for (file_path, _) in &files {
+        if file_path.exists() {
+            return Err(anyhow!("File {} already exists", file_path.display()));
+        }
+    }
+
+    fs_extra::dir::create_all(&schemas_path, false)?;
+
+    for (file_path, content) in files {
+        fs_extra::file::write_all(file_path, &content)?;
+    }
+
+    Ok(())
+}
+
+/// Get the content of the schema file of each table,
+/// the table definition followed by its fields then its indexes.
+fn get_tables_content(db_definitions: Definitions) -> BTreeMap<String, String> {
+    let mut tables_statements: BTreeMap<String, Vec<(SchemaDefinitionKind, String)>> =
+        BTreeMap::new();
+
+    // Definitions are sorted by kind, table then name, so the statements of a kind are sorted by name
+    for ((kind, table, _), definition) in db_definitions {
+        if kind == SchemaDefinitionKind::Event {
+            continue;
+        }
+
+        tables_statements
+            .entry(table)
+            .or_default()
+            .push((kind, format!("{};", definition)));
+    }
+
+    tables_statements
+        .into_iter()
+        .map(|(table, statements)| {
+            let blocks = [
+                SchemaDefinitionKind::Table,
+                SchemaDefinitionKind::Field,
+                SchemaDefinitionKind::Index,
+            ]
+            .into_iter()
+            .map(|block_kind| {
+                statements
+                    .iter()
+                    .filter(|(kind, _)| *kind == block_kind)
+                    .map(|(_, statement)| statement.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>();
+
+            (table, format!("{}\n", blocks.join("\n\n")))
+        })
+        .collect()
+}
@@ -0,0 +1,22 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+
+use crate::{models::ScriptMigration, surrealdb::MigrationsTable};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    name: &str,
+) -> Result<Option<ScriptMigration>> {
+    let query = format!(
+        "SELECT {} FROM {} WHERE {} = $name LIMIT 1;",
+        migrations_table.select_fields(),
+        migrations_table,
+        migrations_table.script_name
+    );
+
+    let mut response = client.query(query).bind(("name", name)).await?;
+    let migration_applied = response.take(0)?;
+
+    Ok(migration_applied)
+}
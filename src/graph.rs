@@ -0,0 +1,83 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+
+use crate::{
+    checksum,
+    input::VersionFormat,
+    io::{self, FilesLocation},
+    models::{MigrationGraph, MigrationNode},
+    surrealdb::{self, MigrationsTable},
+    version,
+};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+    version_format: VersionFormat,
+) -> Result<MigrationGraph> {
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    let mut nodes = Vec::new();
+
+    for migration_file in &migrations_files {
+        let migration_applied = migrations_applied
+            .iter()
+            .find(|migration_applied| migration_applied.script_name == migration_file.name);
+
+        nodes.push(MigrationNode {
+            name: migration_file.name.to_string(),
+            version: version::get_version_prefix(&migration_file.name, version_format)?,
+            is_applied: migration_applied.is_some(),
+            executed_at: migration_applied
+                .map(|migration_applied| migration_applied.executed_at.to_string()),
+            checksum: Some(checksum::compute_checksum(&migration_file.get_content()?)),
+            applied_checksum: migration_applied
+                .and_then(|migration_applied| migration_applied.checksum.to_owned()),
+            depends_on: None,
+        });
+    }
+
+    let orphaned_migrations = migrations_applied.iter().filter(|migration_applied| {
+        !migrations_files
+            .iter()
+            .any(|migration_file| migration_file.name == migration_applied.script_name)
+    });
+
+    for migration_applied in orphaned_migrations {
+        nodes.push(MigrationNode {
+            name: migration_applied.script_name.to_string(),
+            version: version::get_version_prefix(&migration_applied.script_name, version_format)?,
+            is_applied: true,
+            executed_at: Some(migration_applied.executed_at.to_string()),
+            checksum: None,
+            applied_checksum: migration_applied.checksum.to_owned(),
+            depends_on: None,
+        });
+    }
+
+    let mut versioned_nodes = nodes
+        .into_iter()
+        .map(|node| Ok((version::parse_version(&node.name, version_format)?, node)))
+        .collect::<Result<Vec<_>>>()?;
+
+    versioned_nodes.sort_by(|(a_version, a), (b_version, b)| {
+        a_version.cmp(b_version).then_with(|| a.name.cmp(&b.name))
+    });
+
+    // Each migration depends on the migration of the previous version
+    let mut nodes: Vec<MigrationNode> = Vec::with_capacity(versioned_nodes.len());
+
+    for (_, node) in versioned_nodes {
+        let depends_on = nodes
+            .last()
+            .map(|previous_node| previous_node.name.to_string());
+        nodes.push(MigrationNode { depends_on, ..node });
+    }
+
+    Ok(MigrationGraph { nodes })
+}
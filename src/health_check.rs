@@ -0,0 +1,31 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{Context, Result};
+
+use crate::{error::MigrationError, input::HealthCheck};
+
+/// Run the health check query, failing if the result of its last statement is not the expected one.
+pub async fn run(client: &Surreal<Any>, health_check: &HealthCheck) -> Result<()> {
+    let HealthCheck { query, expected } = health_check;
+
+    let mut response = client
+        .query(query.as_str())
+        .await
+        .context("Cannot run the health check query")?;
+
+    let last_statement_index = response.num_statements().saturating_sub(1);
+    let values: Vec<serde_json::Value> = response
+        .take(last_statement_index)
+        .context("Cannot run the health check query")?;
+    let actual = serde_json::Value::Array(values);
+
+    if &actual != expected {
+        return Err(MigrationError::HealthCheckFailed {
+            query: query.to_string(),
+            expected: expected.clone(),
+            actual,
+        }
+        .into());
+    }
+
+    Ok(())
+}
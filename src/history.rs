@@ -0,0 +1,40 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+
+use crate::{models::MigrationEvent, surrealdb::MigrationsTable};
+
+/// Get the query recording that a migration has just been applied,
+/// as re-applied if it has been rolled back before.
+/// The name of the migration is bound to the `$migration_name` parameter.
+pub fn get_applied_query(migrations_table: MigrationsTable<'_>, now: &str) -> String {
+    format!(
+        "CREATE {table} SET script_name = $migration_name, operation = IF array::len((SELECT id FROM {table} WHERE script_name = $migration_name AND operation = 'rolled_back')) > 0 THEN 'reapplied' ELSE 'applied' END, executed_at = {now};",
+        table = migrations_table.history_table(),
+        now = now
+    )
+}
+
+/// Get the query recording that a migration has just been rolled back.
+/// The name of the migration is bound to the `$migration_name` parameter.
+pub fn get_rolled_back_query(migrations_table: MigrationsTable<'_>, now: &str) -> String {
+    format!(
+        "CREATE {} SET script_name = $migration_name, operation = 'rolled_back', executed_at = {};",
+        migrations_table.history_table(),
+        now
+    )
+}
+
+pub async fn list(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<Vec<MigrationEvent>> {
+    let query = format!(
+        "SELECT * FROM {} ORDER BY executed_at;",
+        migrations_table.history_table()
+    );
+
+    let mut response = client.query(query).await?;
+    let history = response.take(0)?;
+
+    Ok(history)
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use regex::{Captures, Regex};
+use semver::{BuildMetadata, Version};
+
+/// First version of SurrealDB supporting the `IF NOT EXISTS` clause of `DEFINE` statements.
+pub const MIN_SERVER_VERSION: Version = Version::new(1, 3, 0);
+
+/// Check if the SurrealDB server supports the `IF NOT EXISTS` clause, build metadata ignored.
+pub fn is_supported(server_version: &Version) -> bool {
+    let server_version = Version {
+        build: BuildMetadata::EMPTY,
+        ..server_version.clone()
+    };
+
+    server_version >= MIN_SERVER_VERSION
+}
+
+/// Add the `IF NOT EXISTS` clause to the `DEFINE TABLE` and `DEFINE FIELD` statements,
+/// so that applying them again does not fail on existing definitions.
+/// Statements with an `IF NOT EXISTS` or `OVERWRITE` clause are kept as is.
+pub fn add_if_not_exists(content: &str) -> Result<String> {
+    let regex = Regex::new(r"(?i)\b(DEFINE\s+(?:TABLE|FIELD))(\s+)(\S+)")?;
+
+    let result = regex.replace_all(content, |captures: &Captures| {
+        let next_word = captures[3].to_uppercase();
+
+        match next_word.as_str() {
+            "IF" | "OVERWRITE" => captures[0].to_string(),
+            _ => format!(
+                "{}{}IF NOT EXISTS {}",
+                &captures[1], &captures[2], &captures[3]
+            ),
+        }
+    });
+
+    Ok(result.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_if_not_exists_should_rewrite_table_and_field_definitions() {
+        let content = "DEFINE TABLE post SCHEMALESS;
+define field title ON post TYPE string;
+DEFINE INDEX title_index ON post COLUMNS title;
+DEFINE EVENT post_created ON post WHEN $event = \"CREATE\" THEN (CREATE log);";
+
+        let result = add_if_not_exists(content);
+
+        assert_eq!(
+            result.unwrap(),
+            "DEFINE TABLE IF NOT EXISTS post SCHEMALESS;
+define field IF NOT EXISTS title ON post TYPE string;
+DEFINE INDEX title_index ON post COLUMNS title;
+DEFINE EVENT post_created ON post WHEN $event = \"CREATE\" THEN (CREATE log);"
+        );
+    }
+
+    #[test]
+    fn add_if_not_exists_should_keep_existing_clauses() {
+        let content = "DEFINE TABLE IF NOT EXISTS post;
+DEFINE FIELD OVERWRITE title ON post TYPE string;";
+
+        let result = add_if_not_exists(content);
+
+        assert_eq!(result.unwrap(), content);
+    }
+
+    #[test]
+    fn is_supported_should_compare_with_min_server_version() {
+        assert!(!is_supported(
+            &Version::parse("1.0.0-beta.9+20230402").unwrap()
+        ));
+        assert!(!is_supported(&Version::parse("1.2.2").unwrap()));
+        assert!(is_supported(&Version::parse("1.3.0+20240305").unwrap()));
+        assert!(is_supported(&Version::parse("2.0.0").unwrap()));
+    }
+}
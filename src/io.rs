@@ -0,0 +1,378 @@
+use anyhow::{anyhow, Context, Result};
+use fs_extra::dir::{DirEntryAttr, DirEntryValue};
+use include_dir::Dir;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    constants::{
+        DOWN_MIGRATION_SUFFIX, EVENTS_DIR_NAME, MIGRATIONS_DIR_NAME, SCHEMAS_DIR_NAME,
+        SEEDS_DIR_NAME, UP_MIGRATION_SUFFIX,
+    },
+    input::VersionFormat,
+    version,
+};
+
+pub struct SurqlFile {
+    pub name: String,
+    pub path: PathBuf,
+    /// Content of the file when it is embedded in the binary or fetched from a migration source,
+    /// instead of read from the disk.
+    pub content: Option<String>,
+}
+
+impl SurqlFile {
+    pub fn get_content(&self) -> Result<String> {
+        if let Some(content) = &self.content {
+            return Ok(content.to_string());
+        }
+
+        fs_extra::file::read_to_string(&self.path).context(format!(
+            "Cannot read content of the file '{}'",
+            self.path.display()
+        ))
+    }
+}
+
+/// Files fetched from a migration source, as file names and contents by folder name.
+pub type SourceFiles = HashMap<String, Vec<(String, String)>>;
+
+/// Where the schemas, events and migrations files are read from.
+#[derive(Clone)]
+pub struct FilesLocation {
+    /// Root folder containing the `schemas`, `events` and `migrations` folders.
+    pub folder_path: Option<String>,
+    /// Directory embedded at compile time, read instead of the root folder.
+    pub embedded_dir: Option<&'static Dir<'static>>,
+    /// Files fetched from a migration source, read instead of the root folder.
+    pub source_files: Option<Arc<SourceFiles>>,
+    /// Folders whose `migrations` folders are merged, read instead of the one of the root folder.
+    pub folders: Vec<String>,
+    /// Names of the `schemas`, `events` and `migrations` folders.
+    pub dir_names: DirNames,
+    /// Format of the migration versions, which must be unique across the merged folders.
+    pub version_format: VersionFormat,
+}
+
+/// Names of the folders containing the schema, event, migration and seed files.
+#[derive(Clone)]
+pub struct DirNames {
+    pub schemas: String,
+    pub events: String,
+    pub migrations: String,
+    pub seeds: String,
+}
+
+impl Default for DirNames {
+    fn default() -> Self {
+        DirNames {
+            schemas: SCHEMAS_DIR_NAME.to_string(),
+            events: EVENTS_DIR_NAME.to_string(),
+            migrations: MIGRATIONS_DIR_NAME.to_string(),
+            seeds: SEEDS_DIR_NAME.to_string(),
+        }
+    }
+}
+
+pub fn concat_path(folder_path: &Option<String>, dir_name: &str) -> PathBuf {
+    match folder_path.to_owned() {
+        Some(folder_path) => Path::new(&folder_path).join(dir_name),
+        None => Path::new(dir_name).to_path_buf(),
+    }
+}
+
+/// Extract migration files (down scripts excluded), sorted by name.
+/// The name of a `.up.surql` file is the name of the migration, without the `.up` suffix.
+/// Files are read from the embedded directory or the migration source if any,
+/// otherwise from the migrations folder(s).
+pub fn extract_migrations_files(location: &FilesLocation) -> Result<Vec<SurqlFile>> {
+    let migrations_dir_name = &location.dir_names.migrations;
+
+    let migrations_files = match (location.embedded_dir, &location.source_files) {
+        (Some(embedded_dir), _) => extract_embedded_files(embedded_dir, migrations_dir_name)?,
+        (None, Some(source_files)) => extract_source_files(source_files, migrations_dir_name),
+        (None, None) if location.folders.is_empty() => {
+            extract_folder_files(&location.folder_path, migrations_dir_name)?
+        }
+        (None, None) => extract_merged_folders_files(
+            &location.folders,
+            migrations_dir_name,
+            location.version_format,
+        )?,
+    };
+
+    let mut migrations_files = migrations_files
+        .into_iter()
+        .filter(|file| !file.name.ends_with(DOWN_MIGRATION_SUFFIX))
+        .map(|file| match file.name.strip_suffix(UP_MIGRATION_SUFFIX) {
+            Some(name) => SurqlFile {
+                name: name.to_string(),
+                ..file
+            },
+            None => file,
+        })
+        .collect::<Vec<_>>();
+
+    migrations_files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let duplicated_migration = migrations_files
+        .windows(2)
+        .find(|files| files[0].name == files[1].name);
+
+    if let Some(files) = duplicated_migration {
+        return Err(anyhow!(
+            "Migration '{name}' is defined by both '{name}.surql' and '{name}{}.surql'",
+            UP_MIGRATION_SUFFIX,
+            name = files[0].name,
+        ));
+    }
+
+    Ok(migrations_files)
+}
+
+/// Extract the `.surql` files of a sub-directory of an embedded directory, sorted by path.
+/// The name of each file is its file name without the `.surql` extension.
+pub fn extract_embedded_files(
+    embedded_dir: &'static Dir<'static>,
+    dir_name: &str,
+) -> Result<Vec<SurqlFile>> {
+    let Some(dir) = embedded_dir.get_dir(dir_name) else {
+        return Ok(Vec::new());
+    };
+
+    let mut files = dir
+        .files()
+        .filter(|file| file.path().extension().is_some_and(|ext| ext == "surql"))
+        .map(|file| {
+            let path = file.path().to_path_buf();
+
+            let name = path
+                .file_stem()
+                .and_then(|name| name.to_str())
+                .context("Cannot get name of the embedded file")?
+                .to_string();
+
+            let content = file.contents_utf8().context(format!(
+                "Cannot read content of the embedded file '{}'",
+                path.display()
+            ))?;
+
+            Ok(SurqlFile {
+                name,
+                path,
+                content: Some(content.to_string()),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(files)
+}
+
+/// Extract the `.surql` files of a folder fetched from a migration source, sorted by file name.
+/// The name of each file is its file name without the `.surql` extension.
+fn extract_source_files(source_files: &SourceFiles, dir_name: &str) -> Vec<SurqlFile> {
+    let Some(files) = source_files.get(dir_name) else {
+        return Vec::new();
+    };
+
+    let mut files = files
+        .iter()
+        .map(|(file_name, content)| SurqlFile {
+            name: file_name.trim_end_matches(".surql").to_string(),
+            path: Path::new(dir_name).join(file_name),
+            content: Some(content.to_string()),
+        })
+        .collect::<Vec<_>>();
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    files
+}
+
+/// Extract the files of a definitions folder (`schemas` or `events`) or of the `seeds` folder.
+/// Files are read from the embedded directory or the migration source if any,
+/// otherwise from the migrations root folder.
+pub fn extract_definition_files(
+    location: &FilesLocation,
+    dir_name: &str,
+) -> Result<Vec<SurqlFile>> {
+    match (location.embedded_dir, &location.source_files) {
+        (Some(embedded_dir), _) => extract_embedded_files(embedded_dir, dir_name),
+        (None, Some(source_files)) => Ok(extract_source_files(source_files, dir_name)),
+        (None, None) => extract_folder_files(&location.folder_path, dir_name),
+    }
+}
+
+/// Check if a definitions folder (`schemas` or `events`) or the `seeds` folder exists,
+/// in the embedded directory or the migration source if any, otherwise in the migrations root folder.
+pub fn definition_folder_exists(location: &FilesLocation, dir_name: &str) -> Result<bool> {
+    match (location.embedded_dir, &location.source_files) {
+        (Some(embedded_dir), _) => Ok(embedded_dir.get_dir(dir_name).is_some()),
+        (None, Some(source_files)) => Ok(source_files.contains_key(dir_name)),
+        (None, None) => Ok(concat_path(&location.folder_path, dir_name).try_exists()?),
+    }
+}
+
+/// Extract the migration files of several folders, failing if a version is defined in more than one folder.
+fn extract_merged_folders_files(
+    folders: &[String],
+    migrations_dir_name: &str,
+    version_format: VersionFormat,
+) -> Result<Vec<SurqlFile>> {
+    let mut migrations_files: Vec<SurqlFile> = Vec::new();
+    let mut versions: HashMap<String, String> = HashMap::new();
+
+    for folder in folders {
+        let folder_migrations_files =
+            extract_folder_files(&Some(folder.to_string()), migrations_dir_name)?;
+
+        let folder_versions = folder_migrations_files
+            .iter()
+            .filter_map(|file| version::get_version_prefix(&file.name, version_format).ok())
+            .collect::<HashSet<_>>();
+
+        for version in folder_versions {
+            if let Some(other_folder) = versions.get(&version) {
+                return Err(anyhow!(
+                    "Migration version '{}' is defined in both '{}' and '{}' folders",
+                    version,
+                    other_folder,
+                    folder
+                ));
+            }
+
+            versions.insert(version, folder.to_string());
+        }
+
+        migrations_files.extend(folder_migrations_files);
+    }
+
+    Ok(migrations_files)
+}
+
+fn extract_folder_files(folder_path: &Option<String>, dir_name: &str) -> Result<Vec<SurqlFile>> {
+    let dir_path = concat_path(folder_path, dir_name);
+
+    let mut config = HashSet::new();
+    config.insert(DirEntryAttr::Name);
+    config.insert(DirEntryAttr::Path);
+    config.insert(DirEntryAttr::IsFile);
+
+    let files = fs_extra::dir::ls(dir_path, &config)?;
+
+    let files = files
+        .items
+        .iter()
+        .map(to_surql_file)
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    Ok(files)
+}
+
+fn get_down_migration_file_path(
+    folder_path: &Option<String>,
+    migrations_dir_name: &str,
+    name: &str,
+) -> PathBuf {
+    concat_path(folder_path, migrations_dir_name)
+        .join(format!("{}{}.surql", name, DOWN_MIGRATION_SUFFIX))
+}
+
+/// Get the content of the down script of a migration, if it exists.
+/// The script is read from the embedded directory or the migration source if any,
+/// otherwise from the migrations folder(s).
+pub fn get_down_migration_content(location: &FilesLocation, name: &str) -> Result<Option<String>> {
+    if let Some(source_files) = &location.source_files {
+        let file_name = format!("{}{}.surql", name, DOWN_MIGRATION_SUFFIX);
+
+        let content = source_files
+            .get(&location.dir_names.migrations)
+            .and_then(|files| files.iter().find(|(name, _)| *name == file_name))
+            .map(|(_, content)| content.to_string());
+
+        return Ok(content);
+    }
+
+    match location.embedded_dir {
+        Some(embedded_dir) => {
+            let path = Path::new(&location.dir_names.migrations)
+                .join(format!("{}{}.surql", name, DOWN_MIGRATION_SUFFIX));
+
+            let content = match embedded_dir.get_file(&path) {
+                Some(file) => Some(
+                    file.contents_utf8()
+                        .context(format!(
+                            "Cannot read content of the embedded file '{}'",
+                            path.display()
+                        ))?
+                        .to_string(),
+                ),
+                None => None,
+            };
+
+            Ok(content)
+        }
+        None => {
+            let folder_paths = match location.folders.is_empty() {
+                true => vec![location.folder_path.to_owned()],
+                false => location
+                    .folders
+                    .iter()
+                    .map(|folder| Some(folder.to_string()))
+                    .collect(),
+            };
+
+            for folder_path in folder_paths {
+                let path = get_down_migration_file_path(
+                    &folder_path,
+                    &location.dir_names.migrations,
+                    name,
+                );
+
+                if path.exists() {
+                    return Ok(Some(fs_extra::file::read_to_string(&path)?));
+                }
+            }
+
+            Ok(None)
+        }
+    }
+}
+
+fn to_surql_file(entry: &HashMap<DirEntryAttr, DirEntryValue>) -> Result<Option<SurqlFile>> {
+    let is_file = match entry.get(&DirEntryAttr::IsFile) {
+        Some(DirEntryValue::Boolean(is_file)) => Some(*is_file),
+        _ => None,
+    };
+    let is_file = is_file.context("Cannot detect if the surql file is a file or a folder")?;
+
+    if !is_file {
+        return Ok(None);
+    }
+
+    let name = match entry.get(&DirEntryAttr::Name) {
+        Some(DirEntryValue::String(name)) => Some(name.to_string()),
+        _ => None,
+    };
+    let name = name.context("Cannot get name of the surql file")?;
+
+    let path = match entry.get(&DirEntryAttr::Path) {
+        Some(DirEntryValue::String(path)) => Some(PathBuf::from(path)),
+        _ => None,
+    };
+    let path = path.context("Cannot get path of the surql file")?;
+
+    Ok(Some(SurqlFile {
+        name,
+        path,
+        content: None,
+    }))
+}
@@ -0,0 +1,21 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+
+use crate::{models::ScriptMigration, surrealdb::MigrationsTable};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<Option<ScriptMigration>> {
+    let query = format!(
+        "SELECT {} FROM {} ORDER BY {} DESC LIMIT 1;",
+        migrations_table.select_fields(),
+        migrations_table,
+        migrations_table.executed_at
+    );
+
+    let mut response = client.query(query).await?;
+    let last_applied = response.take(0)?;
+
+    Ok(last_applied)
+}
@@ -15,6 +15,13 @@
 //! The list of events can be seen as the Command model (in a CQRS pattern).
 //! The `events` folder can be seen as a view of the different ways to update the data model.
 //!
+//! The first time an event file is applied, its record in the definition checksums table
+//! (`script_migration_definition_checksum`, named after the migrations table)
+//! stores the events it defines (`events`, a list of `{ name, table }`)
+//! and the first migration applied along with it (`introduced_by`).
+//! When this migration is reverted, these events are removed with `REMOVE EVENT`
+//! and the record is deleted, so that the event file is applied again by the next `up()`.
+//!
 //! A migration file represents a change in SurrealDB data.
 //! It can be a change in the point of time between two schema changes.
 //! Examples are:
@@ -22,6 +29,15 @@
 //! when a table is renamed or dropped,
 //! when a new data is required (with default value), etc...
 //!
+//! Each file is sent to SurrealDB as a single query, without being split on `;`,
+//! so statements containing semicolons, like function definitions, are applied intact.
+//!
+//! A migration can be tagged with a header comment like `-- tags: analytics`,
+//! so that it is only applied when all its tags are enabled in the configuration.
+//!
+//! A header comment like `-- @use ns shared db config` applies a migration, or a down script,
+//! to another namespace and database, while it is still recorded in the configured ones.
+//!
 //! # Get started
 //!
 //! ```rust,no_run
@@ -41,76 +57,280 @@
 //! ```
 
 mod apply;
+mod audit;
+mod baseline;
+mod checksum;
+mod clock;
 mod config;
 mod constants;
+mod counts;
+mod create_migration;
+mod definition_checksums;
 mod definitions;
+mod directives;
+mod doctor;
+mod down;
+mod error;
+mod export_schema;
+mod get_applied;
+mod graph;
+mod health_check;
+mod history;
+mod idempotent_defines;
 mod input;
+mod io;
+mod last_applied;
+mod list_between;
+mod list_stream;
+mod lock;
+mod logger;
+mod mark_applied;
+mod migration_meta;
 mod models;
+mod pending;
+mod redo;
+mod refresh_checksums;
+mod retry;
+mod run_script;
+mod schema_diff;
+mod schema_version;
+mod seed;
+mod server_version;
+mod source;
+mod squash;
+mod status;
 mod surrealdb;
+mod tls;
+mod unmark;
+mod validate_offline;
+mod validate_schema_files;
 mod validate_version_order;
+mod variables;
+mod verify;
+mod version;
 
-use anyhow::Result;
-use apply::ApplyArgs;
-pub use input::SurrealdbConfiguration;
-use models::ScriptMigration;
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{anyhow, Context, Result};
+use apply::{ApplyArgs, ApplyOperation, ApplyOutput};
+use chrono::{DateTime, Utc};
+use clock::Clock;
+pub use config::ProjectConfig;
+use doctor::DoctorArgs;
+use down::{DownArgs, DownOperation};
+pub use error::MigrationError;
+use futures::{future::BoxFuture, stream, Future, Stream, TryStreamExt};
+use include_dir::Dir;
+pub use input::{
+    AuthLevel, ConcurrencyControl, ExecutedAtFormat, HealthCheck, ListOrder,
+    SurrealdbConfiguration, SurrealdbConfigurationBuilder, VersionFormat,
+};
+use io::{DirNames, FilesLocation};
+use mark_applied::MarkAppliedArgs;
+pub use models::{
+    ApplyReport, ChecksumMismatch, DiagnosticCheck, DiagnosticCheckKind, DiagnosticReport,
+    MigrationCounts, MigrationEvent, MigrationGraph, MigrationNode, MigrationOperation,
+    MigrationProgress, MigrationProgressStatus, MigrationStatus, MigrationStatusEntry,
+    ResolvedConfig, SchemaDefinition, SchemaDefinitionChange, SchemaDefinitionKind, SchemaDiff,
+    ScriptMigration,
+};
+use retry::{MigrationRetry, TransientErrorClassifier};
+#[cfg(feature = "archive-source")]
+pub use source::ArchiveSource;
+#[cfg(feature = "http-source")]
+pub use source::HttpSource;
+pub use source::{FolderSource, MigrationSource};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+use surrealdb::MigrationsTable;
+use tokio::sync::OnceCell;
 
-impl SurrealdbConfiguration {
-    /// Create an instance of SurrealdbConfiguration with default values.
-    ///
-    /// ## Examples
-    ///
-    /// ```
-    /// use surrealdb_migrations::SurrealdbConfiguration;
-    ///
-    /// let db_configuration = SurrealdbConfiguration::default();
-    /// ```
-    pub fn default() -> SurrealdbConfiguration {
-        SurrealdbConfiguration {
-            url: None,
-            ns: None,
-            db: None,
-            username: None,
-            password: None,
-        }
-    }
-}
+/// A function called with each log message of the apply operations.
+type Logger = dyn Fn(&str) + Send + Sync;
+
+/// A function called once before the migrations are applied.
+type BeforeApplyHook = dyn Fn() -> BoxFuture<'static, ()> + Send + Sync;
+
+/// A function called once after the migrations are applied, with whether any migration has been applied.
+type AfterApplyHook = dyn Fn(bool) -> BoxFuture<'static, ()> + Send + Sync;
 
 /// The main entry point for the library, used to apply migrations.
 pub struct SurrealdbMigrations {
     db_configuration: SurrealdbConfiguration,
+    client: OnceCell<Surreal<Any>>,
+    embedded_dir: Option<&'static Dir<'static>>,
+    source: Option<Arc<dyn MigrationSource>>,
+    logger: Option<Box<Logger>>,
+    clock: Option<Box<Clock>>,
+    transient_error_classifier: Option<Box<TransientErrorClassifier>>,
+    before_apply: Option<Box<BeforeApplyHook>>,
+    after_apply: Option<Box<AfterApplyHook>>,
 }
 
 impl SurrealdbMigrations {
     /// Create a new instance of SurrealdbMigrations.
     pub fn new(db_configuration: SurrealdbConfiguration) -> SurrealdbMigrations {
-        SurrealdbMigrations { db_configuration }
+        SurrealdbMigrations {
+            db_configuration,
+            client: OnceCell::new(),
+            embedded_dir: None,
+            source: None,
+            logger: None,
+            clock: None,
+            transient_error_classifier: None,
+            before_apply: None,
+            after_apply: None,
+        }
     }
 
-    /// Validate the version order of the migrations so that you cannot run migrations if there are
-    /// gaps in the migrations history.
+    /// Create a new instance of SurrealdbMigrations from an existing SurrealDB client.
+    ///
+    /// The client is reused for every operation instead of opening a new connection.
+    /// It should already be authenticated and have its namespace and database selected.
     ///
     /// ## Examples
     ///
     /// ```rust,no_run
-    /// # use anyhow::Result;
+    /// use surrealdb::{engine::any::connect, opt::auth::Root};
+    /// use surrealdb_migrations::SurrealdbMigrations;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = connect("ws://localhost:8000").await?;
+    /// client
+    ///     .signin(Root {
+    ///         username: "root",
+    ///         password: "root",
+    ///     })
+    ///     .await?;
+    /// client.use_ns("test").use_db("test").await?;
+    ///
+    /// SurrealdbMigrations::from_client(client).up().await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub fn from_client(client: Surreal<Any>) -> SurrealdbMigrations {
+        SurrealdbMigrations {
+            db_configuration: SurrealdbConfiguration::default(),
+            client: OnceCell::new_with(Some(client)),
+            embedded_dir: None,
+            source: None,
+            logger: None,
+            clock: None,
+            transient_error_classifier: None,
+            before_apply: None,
+            after_apply: None,
+        }
+    }
+
+    /// Create a new instance of SurrealdbMigrations reading the migration files from a directory embedded at compile time.
+    ///
+    /// The embedded directory has the same layout as the migrations root folder,
+    /// with `schemas`, `events` and `migrations` sub-directories.
+    /// Nothing is read from the disk, so the binary does not need to ship with its migration files.
+    /// Definition files are not generated when applying migrations from an embedded directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `embedded_dir` - The directory embedded with the `include_dir!` macro
+    /// * `db_configuration` - The configuration used to connect to the SurrealDB instance
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use include_dir::{include_dir, Dir};
     /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
     ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<()> {
+    /// static MIGRATIONS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates/empty");
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let db_configuration = SurrealdbConfiguration::default();
-    /// let runner = SurrealdbMigrations::new(db_configuration);
     ///
-    /// runner.validate_version_order().await?;
-    /// runner.up().await?;
+    /// SurrealdbMigrations::from_embedded(&MIGRATIONS_DIR, db_configuration)
+    ///     .up()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub fn from_embedded(
+        embedded_dir: &'static Dir<'static>,
+        db_configuration: SurrealdbConfiguration,
+    ) -> SurrealdbMigrations {
+        SurrealdbMigrations {
+            db_configuration,
+            client: OnceCell::new(),
+            embedded_dir: Some(embedded_dir),
+            source: None,
+            logger: None,
+            clock: None,
+            transient_error_classifier: None,
+            before_apply: None,
+            after_apply: None,
+        }
+    }
+
+    /// Create a new instance of SurrealdbMigrations reading the migration files from a migration source,
+    /// e.g. an HTTP server with `HttpSource`, instead of the local disk.
+    ///
+    /// The source has the same layout as the migrations root folder,
+    /// with `schemas`, `events` and `migrations` folders.
+    /// Its files are read at the start of each operation.
+    /// Migrations cannot be created nor squashed, and definition files are not generated when applying migrations.
+    ///
+    /// ## Arguments
+    ///
+    /// * `source` - The migration source the files are read from
+    /// * `db_configuration` - The configuration used to connect to the SurrealDB instance
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{FolderSource, SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    /// let source = FolderSource::new("/mnt/artifacts/migrations");
     ///
+    /// SurrealdbMigrations::from_source(source, db_configuration)
+    ///     .up()
+    ///     .await?;
     /// # Ok(())
     /// # }
+    /// # main().await.unwrap();
+    /// # });
     /// ```
-    pub async fn validate_version_order(&self) -> Result<()> {
-        validate_version_order::main(&self.db_configuration).await
+    pub fn from_source(
+        source: impl MigrationSource + 'static,
+        db_configuration: SurrealdbConfiguration,
+    ) -> SurrealdbMigrations {
+        SurrealdbMigrations {
+            db_configuration,
+            client: OnceCell::new(),
+            embedded_dir: None,
+            source: Some(Arc::new(source)),
+            logger: None,
+            clock: None,
+            transient_error_classifier: None,
+            before_apply: None,
+            after_apply: None,
+        }
     }
 
-    /// Apply schema definitions and apply all migrations.
+    /// Set a function called with each log message of the apply operations, and with the warnings,
+    /// which are not displayed otherwise. Warnings are prefixed with `Warning: `.
+    ///
+    /// ## Arguments
+    ///
+    /// * `logger` - The function called with each log message
     ///
     /// ## Examples
     ///
@@ -121,52 +341,89 @@ impl SurrealdbMigrations {
     /// let db_configuration = SurrealdbConfiguration::default();
     ///
     /// SurrealdbMigrations::new(db_configuration)
+    ///     .with_logger(|message| eprintln!("[migrations] {}", message))
     ///     .up()
     ///     .await
     ///     .expect("Failed to apply migrations");
     /// # });
     /// ```
-    pub async fn up(&self) -> Result<()> {
-        let args = ApplyArgs {
-            up: None,
-            db_configuration: &self.db_configuration,
-            display_logs: false,
-            dry_run: false,
-        };
-        apply::main(args).await
+    pub fn with_logger(mut self, logger: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.logger = Some(Box::new(logger));
+        self
     }
 
-    /// Apply schema definitions and all migrations up to and including the named migration.
+    /// Set a function returning the current date, used as the execution date of the migrations
+    /// recorded from now on, instead of the date of the database. Useful to freeze time in tests,
+    /// or to backfill the migrations history with `baseline()`.
+    ///
+    /// Applied migrations are ordered by execution date, so the dates returned should be increasing.
+    /// When the migrations table is defined in a schema file, its `executed_at` field must keep
+    /// the given date, like with `VALUE $before OR $value OR time::now()`.
     ///
     /// ## Arguments
     ///
-    /// * `name` - This parameter allows you to skip ulterior migrations.
+    /// * `clock` - The function returning the current date
     ///
     /// ## Examples
     ///
     /// ```rust,no_run
+    /// use chrono::{TimeZone, Utc};
     /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
     ///
     /// # tokio_test::block_on(async {
     /// let db_configuration = SurrealdbConfiguration::default();
     ///
     /// SurrealdbMigrations::new(db_configuration)
-    ///     .up_to("20230101_120002_AddPost")
+    ///     .with_clock(|| Utc.with_ymd_and_hms(2023, 3, 17, 15, 32, 1).unwrap())
+    ///     .baseline("20230317_153201_AddAdminUser")
+    ///     .await
+    ///     .expect("Failed to baseline migrations");
+    /// # });
+    /// ```
+    pub fn with_clock(mut self, clock: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Set a function telling whether an error is transient, in addition to the known transient errors,
+    /// so that a migration failing with it is retried up to `migration_retries` times.
+    ///
+    /// ## Arguments
+    ///
+    /// * `classifier` - The function returning whether the error of a failed migration is transient
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::builder()
+    ///     .migration_retries(3)
+    ///     .build();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .with_transient_error_classifier(|error| error.to_string().contains("connection reset"))
+    ///     .up()
     ///     .await
     ///     .expect("Failed to apply migrations");
     /// # });
     /// ```
-    pub async fn up_to(&self, name: &str) -> Result<()> {
-        let args = ApplyArgs {
-            up: Some(name.to_string()),
-            db_configuration: &self.db_configuration,
-            display_logs: false,
-            dry_run: false,
-        };
-        apply::main(args).await
+    pub fn with_transient_error_classifier(
+        mut self,
+        classifier: impl Fn(&anyhow::Error) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.transient_error_classifier = Some(Box::new(classifier));
+        self
     }
 
-    /// List script migrations that have been applied to the database.
+    /// Set a function called once before the migrations are applied, before anything is applied.
+    ///
+    /// Not called on dry runs.
+    ///
+    /// ## Arguments
+    ///
+    /// * `hook` - The function returning the future to wait for before applying the migrations
     ///
     /// ## Examples
     ///
@@ -174,20 +431,2070 @@ impl SurrealdbMigrations {
     /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
     ///
     /// # tokio_test::block_on(async {
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let db_configuration = SurrealdbConfiguration::default();
     ///
-    /// let migrations_applied = SurrealdbMigrations::new(db_configuration)
-    ///     .list()
-    ///     .await?;
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .with_before_apply(|| async { println!("Entering maintenance mode") })
+    ///     .up()
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub fn with_before_apply<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.before_apply = Some(Box::new(move || Box::pin(hook())));
+        self
+    }
+
+    /// Set a function called once after the migrations are successfully applied,
+    /// with whether any migration has been applied.
+    ///
+    /// Not called on dry runs, nor when applying the migrations fails.
+    ///
+    /// ## Arguments
+    ///
+    /// * `hook` - The function returning the future to wait for after applying the migrations
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .with_after_apply(|has_applied_migrations| async move {
+    ///         if has_applied_migrations {
+    ///             println!("Clearing cache");
+    ///         }
+    ///     })
+    ///     .up()
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub fn with_after_apply<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(bool) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.after_apply = Some(Box::new(move |has_applied_migrations| {
+            Box::pin(hook(has_applied_migrations))
+        }));
+        self
+    }
+
+    /// Apply the migrations, between the before and after apply hooks unless it is a dry run.
+    async fn apply(&self, args: ApplyArgs<'_>) -> Result<ApplyOutput, MigrationError> {
+        if args.dry_run {
+            return Ok(apply::main(args).await?);
+        }
+
+        if let Some(before_apply) = &self.before_apply {
+            before_apply().await;
+        }
+
+        let output = apply::main(args).await?;
+
+        if let Some(after_apply) = &self.after_apply {
+            after_apply(!output.report.applied_migrations.is_empty()).await;
+        }
+
+        Ok(output)
+    }
+
+    async fn get_client(&self) -> Result<Surreal<Any>, MigrationError> {
+        if let Some(client) = self.client.get() {
+            return Ok(client.clone());
+        }
+
+        let client =
+            surrealdb::create_surrealdb_client(&self.db_configuration, self.logger.as_deref())
+                .await
+                .map_err(|error| match error.downcast::<MigrationError>() {
+                    Ok(error) => error,
+                    Err(error) => MigrationError::ConnectionFailed(error),
+                })?;
+
+        // An in-memory database only lives as long as its client, so keep it for next operations
+        let is_in_memory = self
+            .db_configuration
+            .url
+            .as_deref()
+            .is_some_and(surrealdb::is_in_memory_url);
+
+        if is_in_memory {
+            let _ = self.client.set(client.clone());
+        }
+
+        Ok(client)
+    }
+
+    fn get_migrations_table(&self) -> MigrationsTable<'_> {
+        surrealdb::get_migrations_table(&self.db_configuration)
+    }
+
+    /// Get the arguments of an apply operation, as set in the configuration.
+    fn apply_args<'a>(
+        &'a self,
+        operation: ApplyOperation,
+        client: &'a Surreal<Any>,
+        location: FilesLocation,
+    ) -> ApplyArgs<'a> {
+        ApplyArgs {
+            operation,
+            client,
+            migrations_table: self.get_migrations_table(),
+            transactional: self.db_configuration.transactional,
+            location,
+            logger: self.logger.as_deref(),
+            dry_run: false,
+            force_schema: self.db_configuration.force_schema,
+            idempotent_defines: self.db_configuration.idempotent_defines,
+            schema_concurrency: self.db_configuration.schema_concurrency,
+            strict_schema: self.db_configuration.strict_schema,
+            continue_on_error: self.db_configuration.continue_on_error,
+            health_check: self.db_configuration.health_check.as_ref(),
+            min_server_version: self.db_configuration.min_server_version.as_deref(),
+            allow_checksum_mismatch: self.db_configuration.allow_checksum_mismatch,
+            migration_timeout: self.db_configuration.migration_timeout,
+            migration_retry: self.get_migration_retry(),
+            version_format: self.db_configuration.version_format,
+            validate_before_apply: self.db_configuration.validate_before_apply,
+            applied_by: self.db_configuration.applied_by.to_owned(),
+            applied_from_host: self.db_configuration.applied_from_host.to_owned(),
+            clock: self.clock.as_deref(),
+            on_progress: None,
+            statement_progress: self.db_configuration.statement_progress,
+            lock_timeout_ms: self.db_configuration.lock_timeout_ms,
+            lock_ttl_ms: self.db_configuration.lock_ttl_ms,
+            concurrency_control: self.db_configuration.concurrency_control,
+            vars: None,
+            enabled_tags: &self.db_configuration.enabled_tags,
+            skip: &self.db_configuration.skip,
+            log_statements: self.db_configuration.log_statements,
+            redacted_vars: &self.db_configuration.redacted_vars,
+        }
+    }
+
+    fn get_migration_retry(&self) -> MigrationRetry<'_> {
+        MigrationRetry::new(
+            self.db_configuration.migration_retries,
+            self.db_configuration.migration_retry_delay_ms,
+            self.transient_error_classifier.as_deref(),
+        )
+    }
+
+    fn get_files_location(&self) -> Result<FilesLocation> {
+        // Embedded migration files are never read from the disk
+        if self.embedded_dir.is_some() {
+            return Ok(FilesLocation {
+                folder_path: None,
+                embedded_dir: self.embedded_dir,
+                source_files: None,
+                folders: Vec::new(),
+                dir_names: self.get_dir_names(),
+                version_format: self.db_configuration.version_format,
+            });
+        }
+
+        let folders = self
+            .db_configuration
+            .folders
+            .iter()
+            .map(|folder| {
+                config::resolve_folder_path(&Some(folder.to_owned()))?
+                    .context("Cannot resolve path of the migrations folder")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(FilesLocation {
+            folder_path: config::resolve_folder_path(&self.db_configuration.folder)?,
+            embedded_dir: None,
+            source_files: None,
+            folders,
+            dir_names: self.get_dir_names(),
+            version_format: self.db_configuration.version_format,
+        })
+    }
+
+    /// Get the location of the files, reading every file of the migration source if any.
+    async fn fetch_files_location(&self) -> Result<FilesLocation> {
+        let Some(source) = &self.source else {
+            return self.get_files_location();
+        };
+
+        let dir_names = self.get_dir_names();
+        let source_files = source::fetch_files(source.as_ref(), &dir_names).await?;
+
+        Ok(FilesLocation {
+            folder_path: None,
+            embedded_dir: None,
+            source_files: Some(Arc::new(source_files)),
+            folders: Vec::new(),
+            dir_names,
+            version_format: self.db_configuration.version_format,
+        })
+    }
+
+    fn get_dir_names(&self) -> DirNames {
+        let default = DirNames::default();
+
+        DirNames {
+            schemas: self
+                .db_configuration
+                .schema_dir
+                .to_owned()
+                .unwrap_or(default.schemas),
+            events: self
+                .db_configuration
+                .event_dir
+                .to_owned()
+                .unwrap_or(default.events),
+            migrations: self
+                .db_configuration
+                .migration_dir
+                .to_owned()
+                .unwrap_or(default.migrations),
+            seeds: default.seeds,
+        }
+    }
+
+    /// Validate the version order of the migrations so that you cannot run migrations if there are
+    /// gaps in the migrations history.
+    ///
+    /// Fails with `MigrationError::VersionOrderViolation`, listing the names of the migrations
+    /// not applied while later migrations have been.
+    ///
+    /// Versions are parsed with the `version_format` of the configuration,
+    /// failing if the name of a migration does not match this format.
+    ///
+    /// Migrations whose tags are not all in the `enabled_tags` of the configuration are ignored,
+    /// as well as the migrations listed in `skip`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// # use anyhow::Result;
+    /// use surrealdb_migrations::{MigrationError, SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    /// let runner = SurrealdbMigrations::new(db_configuration);
+    ///
+    /// match runner.validate_version_order().await {
+    ///     Err(MigrationError::VersionOrderViolation { migrations }) => {
+    ///         eprintln!("Migrations not applied in order: {}", migrations.join(", "));
+    ///     }
+    ///     result => {
+    ///         result?;
+    ///         runner.up().await?;
+    ///     }
+    /// }
+    ///
     /// # Ok(())
     /// # }
-    /// # main().await.unwrap();
+    /// ```
+    pub async fn validate_version_order(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        validate_version_order::main(
+            &client,
+            self.get_migrations_table(),
+            &location,
+            self.db_configuration.version_format,
+            &self.db_configuration.enabled_tags,
+            &self.db_configuration.skip,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Validate the migration files without connecting to the SurrealDB instance, e.g. in a pre-commit hook.
+    ///
+    /// Fails if the name of a migration does not match the `version_format` of the configuration,
+    /// if several migrations share the same version, if the migrations are not named in version order,
+    /// if a schema file does not define exactly one table when `strict_schema` is set,
+    /// or if a schema, event, migration or down script file cannot be parsed.
+    /// Files with `${NAME}` variables or version directives are not parsed, as their content depends on the apply operation.
+    /// Files are parsed with the SurrealQL syntax of the SurrealDB version this crate depends on.
+    ///
+    /// Migrations listed in `skip` are ignored.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .validate_offline()
+    ///     .await
+    ///     .expect("Invalid migration files");
+    /// # });
+    /// ```
+    pub async fn validate_offline(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        validate_offline::main(
+            &location,
+            self.db_configuration.version_format,
+            self.db_configuration.strict_schema,
+            &self.db_configuration.skip,
+        )?;
+
+        Ok(())
+    }
+
+    /// Apply schema definitions and apply all migrations.
+    ///
+    /// Fails before applying anything if the health check query does not return the expected result,
+    /// if several migrations share the same version,
+    /// or if an applied migration has been modified since, unless `allow_checksum_mismatch` is set.
+    /// With `validate_before_apply`, also fails if the version order is not valid,
+    /// see `validate_version_order()`.
+    ///
+    /// With `ConcurrencyControl::VersionCheck`, fails with `MigrationError::ConcurrentModification`
+    /// after applying if another process applied migrations in the meantime.
+    ///
+    /// Migrations tagged with a `-- tags: ...` header are skipped, and not recorded,
+    /// unless all their tags are in the `enabled_tags` of the configuration.
+    /// Migrations listed in `skip` are ignored, as if their files did not exist.
+    ///
+    /// A migration with a `-- @use ns <namespace> db <database>` header is applied to that namespace and database,
+    /// then the configured ones are used again, where the migration is recorded.
+    ///
+    /// Statements surrounded by `-- @if version >= 1.5` and `-- @endif` comments,
+    /// with an optional `-- @else` block, are only applied depending on the server version.
+    /// Operators are `>=`, `>`, `<=`, `<`, `==` and `!=`, and blocks can be nested.
+    ///
+    /// With `run_seeds`, the seed files are run afterwards, see `seed()`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .up()
+    ///     .await
+    ///     .expect("Failed to apply migrations");
     /// # });
     /// ```
-    pub async fn list(&self) -> Result<Vec<ScriptMigration>> {
-        let client = surrealdb::create_surrealdb_client(&self.db_configuration).await?;
+    pub async fn up(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = self.apply_args(ApplyOperation::Up, &client, location.to_owned());
+        self.apply(args).await?;
+
+        if self.db_configuration.run_seeds {
+            seed::main(&client, &location).await?;
+        }
+
+        Ok(())
+    }
 
-        surrealdb::list_script_migration_ordered_by_execution_date(&client).await
+    /// Apply schema definitions and all migrations, and return a report of what has been applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let report = SurrealdbMigrations::new(db_configuration)
+    ///     .up_with_report()
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    ///
+    /// println!("Applied {} migrations", report.applied_migrations.len());
+    /// # });
+    /// ```
+    pub async fn up_with_report(&self) -> Result<ApplyReport, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = self.apply_args(ApplyOperation::Up, &client, location);
+        let output = self.apply(args).await?;
+
+        Ok(output.report)
+    }
+
+    /// Apply schema definitions and all migrations to each of the databases, e.g. one database per tenant.
+    ///
+    /// The same connection is used for every database, switching to each one in turn,
+    /// and then back to the database it was using.
+    /// Returns the report of each database, by database name.
+    ///
+    /// A failure on a database does not prevent applying migrations to the next ones.
+    /// If any database failed, a `MigrationError::DatabasesFailed` error is returned
+    /// with the reports of the successful databases and the errors of the failed ones.
+    ///
+    /// ## Arguments
+    ///
+    /// * `databases` - The names of the databases, in the namespace of the configuration.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let databases = vec!["tenant_1".to_string(), "tenant_2".to_string()];
+    ///
+    /// let reports = SurrealdbMigrations::new(db_configuration)
+    ///     .up_all(&databases)
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    ///
+    /// for (database, report) in reports {
+    ///     println!("Applied {} migrations to {}", report.applied_migrations.len(), database);
+    /// }
+    /// # });
+    /// ```
+    pub async fn up_all(
+        &self,
+        databases: &[String],
+    ) -> Result<HashMap<String, ApplyReport>, MigrationError> {
+        let client = self.get_client().await?;
+        let (ns, initial_db) = surrealdb::get_current_ns_db(&client).await?;
+
+        let mut reports = HashMap::new();
+        let mut errors = HashMap::new();
+
+        for database in databases {
+            match self.up_database(&client, &ns, database).await {
+                Ok(report) => {
+                    reports.insert(database.to_string(), report);
+                }
+                Err(error) => {
+                    errors.insert(database.to_string(), error);
+                }
+            }
+        }
+
+        client
+            .use_ns(ns)
+            .use_db(initial_db)
+            .await
+            .context("Cannot switch back to the initial database")?;
+
+        if !errors.is_empty() {
+            return Err(MigrationError::DatabasesFailed { reports, errors });
+        }
+
+        Ok(reports)
+    }
+
+    async fn up_database(
+        &self,
+        client: &Surreal<Any>,
+        ns: &str,
+        database: &str,
+    ) -> Result<ApplyReport, MigrationError> {
+        client
+            .use_ns(ns)
+            .use_db(database)
+            .await
+            .context(format!("Cannot switch to the database '{}'", database))?;
+
+        let location = self.fetch_files_location().await?;
+
+        let args = self.apply_args(ApplyOperation::Up, client, location);
+        let output = self.apply(args).await?;
+
+        Ok(output.report)
+    }
+
+    /// Apply schema definitions and apply all migrations, reporting the progress of each migration.
+    ///
+    /// The callback is called before and after the execution of each migration file.
+    /// With `statement_progress` and `transactional` disabled, it is also called after each statement,
+    /// with `MigrationProgressStatus::StatementExecuted`, as the statements are then sent one by one.
+    ///
+    /// ## Arguments
+    ///
+    /// * `on_progress` - The callback receiving the progress of the migrations.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{MigrationProgressStatus, SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .up_with_progress(|progress| {
+    ///         if progress.status == MigrationProgressStatus::Completed {
+    ///             println!("{}/{} {}", progress.index + 1, progress.total, progress.name);
+    ///         }
+    ///     })
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub async fn up_with_progress(
+        &self,
+        on_progress: impl Fn(MigrationProgress) + Send + Sync,
+    ) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = ApplyArgs {
+            on_progress: Some(&on_progress),
+            ..self.apply_args(ApplyOperation::Up, &client, location)
+        };
+        self.apply(args).await?;
+
+        Ok(())
+    }
+
+    /// Apply schema definitions and apply all migrations,
+    /// replacing `${NAME}` placeholders in migration files with the value of the variable `NAME`.
+    ///
+    /// Fails if a migration file contains a placeholder without value.
+    /// SurrealQL parameters like `$before` are left untouched.
+    ///
+    /// ## Arguments
+    ///
+    /// * `vars` - The values of the variables, by name.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::{collections::HashMap, path::PathBuf};
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let vars = HashMap::from([("ADMIN_EMAIL".to_string(), "admin@example.com".to_string())]);
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .up_with_vars(vars)
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub async fn up_with_vars(&self, vars: HashMap<String, String>) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = ApplyArgs {
+            vars: Some(&vars),
+            ..self.apply_args(ApplyOperation::Up, &client, location)
+        };
+        self.apply(args).await?;
+
+        Ok(())
+    }
+
+    /// Simulate the application of schema definitions and all pending migrations.
+    ///
+    /// Statements are executed inside transactions that are cancelled,
+    /// so that no change is persisted in the database, and definition files are not written.
+    ///
+    /// Returns the list of SurrealQL queries that would be executed, in execution order:
+    /// schema definitions, event definitions and then the content of each pending migration.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let queries = SurrealdbMigrations::new(db_configuration)
+    ///     .up_dry_run()
+    ///     .await
+    ///     .expect("Failed to simulate migrations");
+    ///
+    /// for query in queries {
+    ///     println!("{}", query);
+    /// }
+    /// # });
+    /// ```
+    pub async fn up_dry_run(&self) -> Result<Vec<String>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = ApplyArgs {
+            dry_run: true,
+            ..self.apply_args(ApplyOperation::Up, &client, location)
+        };
+        let output = self.apply(args).await?;
+
+        Ok(output.queries)
+    }
+
+    /// Get the script applying schema definitions and all pending migrations, and recording them,
+    /// to be run by the caller within its own transaction, along with other queries.
+    ///
+    /// The SurrealDB client has no handle to a transaction opened by another query,
+    /// so migrations cannot be applied within a transaction controlled by the caller.
+    /// Instead, the script is computed like `up_dry_run()` does, without persisting anything,
+    /// and the caller commits it, or not, with its own queries.
+    ///
+    /// The migrations lock is not held while the script runs, the duration of the migrations is not
+    /// recorded and the schema version is not incremented, unlike with `up()`.
+    /// The values recorded with each migration are set with `LET` statements before its record query.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb::engine::any::connect;
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = connect("ws://localhost:8000").await?;
+    /// client.use_ns("test").use_db("test").await?;
+    ///
+    /// let script = SurrealdbMigrations::from_client(client.clone())
+    ///     .up_script()
+    ///     .await?;
+    ///
+    /// client
+    ///     .query(format!(
+    ///         "BEGIN TRANSACTION;\n{}\nUPSERT settings:app SET ready = true;\nCOMMIT TRANSACTION;",
+    ///         script
+    ///     ))
+    ///     .await?
+    ///     .check()?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn up_script(&self) -> Result<String, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = ApplyArgs {
+            dry_run: true,
+            ..self.apply_args(ApplyOperation::Up, &client, location)
+        };
+        let output = self.apply(args).await?;
+
+        Ok(output.script.join("\n"))
+    }
+
+    /// Apply schema definitions and all migrations up to and including the named migration.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - This parameter allows you to skip ulterior migrations.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .up_to("20230101_120002_AddPost")
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub async fn up_to(&self, name: &str) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = self.apply_args(ApplyOperation::UpTo(name.to_string()), &client, location);
+        self.apply(args).await?;
+
+        Ok(())
+    }
+
+    /// Apply schema definitions and the next pending migrations, in version order.
+    ///
+    /// If fewer migrations are pending, all of them are applied.
+    /// Returns the names of the migrations applied.
+    ///
+    /// ## Arguments
+    ///
+    /// * `step` - The number of pending migrations to apply.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let applied_migrations = SurrealdbMigrations::new(db_configuration)
+    ///     .up_step(1)
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub async fn up_step(&self, step: usize) -> Result<Vec<String>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = self.apply_args(ApplyOperation::UpStep(step), &client, location);
+        let output = self.apply(args).await?;
+
+        Ok(output.report.applied_migrations)
+    }
+
+    /// Apply schema definitions and the migrations between two named migrations, both included.
+    ///
+    /// Useful to replay a specific window of the migrations history against a fresh database.
+    /// Returns the names of the migrations applied.
+    ///
+    /// Fails before applying anything if a migration does not exist, if `from` does not precede `to`,
+    /// or with `MigrationError::RangeAlreadyApplied` if some migrations of the range have already been applied.
+    ///
+    /// ## Arguments
+    ///
+    /// * `from` - The name of the first migration to apply.
+    /// * `to` - The name of the last migration to apply.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let applied_migrations = SurrealdbMigrations::new(db_configuration)
+    ///     .up_range("20230101_120002_AddPost", "20230101_120003_CommentPost")
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub async fn up_range(&self, from: &str, to: &str) -> Result<Vec<String>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = self.apply_args(
+            ApplyOperation::UpRange {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+            &client,
+            location,
+        );
+        let output = self.apply(args).await?;
+
+        Ok(output.report.applied_migrations)
+    }
+
+    /// Apply schema definitions and the pending migrations one at a time, in version order,
+    /// until the deadline is reached, e.g. to bound the time spent in a maintenance window.
+    ///
+    /// Each migration is recorded once applied, and a migration already started when the deadline
+    /// is reached is never interrupted, so that calling it again later resumes with the next ones.
+    /// Returns the names of the migrations applied.
+    ///
+    /// ## Arguments
+    ///
+    /// * `deadline` - The instant after which no migration is started.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::{Duration, Instant};
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(30 * 60);
+    ///
+    /// let applied_migrations = SurrealdbMigrations::new(db_configuration)
+    ///     .up_until_deadline(deadline)
+    ///     .await
+    ///     .expect("Failed to apply migrations");
+    /// # });
+    /// ```
+    pub async fn up_until_deadline(
+        &self,
+        deadline: Instant,
+    ) -> Result<Vec<String>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = self.apply_args(ApplyOperation::UpUntilDeadline(deadline), &client, location);
+        let output = self.apply(args).await?;
+
+        Ok(output.report.applied_migrations)
+    }
+
+    /// Apply schema and event definitions only, without reading the migrations folder.
+    ///
+    /// No migration is executed nor recorded, which is useful to refresh the schema
+    /// of a development database without caring about the migrations history.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .apply_schemas_only()
+    ///     .await
+    ///     .expect("Failed to apply schemas");
+    /// # });
+    /// ```
+    pub async fn apply_schemas_only(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = self.apply_args(ApplyOperation::SchemasOnly, &client, location);
+        self.apply(args).await?;
+
+        Ok(())
+    }
+
+    /// Revert the last applied migration.
+    ///
+    /// The migration is reverted by executing its paired down script,
+    /// a file named after the migration and suffixed by `.down.surql`
+    /// (e.g. `20230101_120002_AddPost.down.surql`), located next to the migration file.
+    /// The down script and the removal of the migration record are executed inside a transaction.
+    /// The events introduced along with the migration are removed in the same transaction.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .down()
+    ///     .await
+    ///     .expect("Failed to revert the last migration");
+    /// # });
+    /// ```
+    pub async fn down(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = DownArgs {
+            operation: DownOperation::Last,
+            client: &client,
+            migrations_table: self.get_migrations_table(),
+            location,
+            dry_run: false,
+            clock: self.clock.as_deref(),
+            lock_timeout_ms: self.db_configuration.lock_timeout_ms,
+            lock_ttl_ms: self.db_configuration.lock_ttl_ms,
+        };
+        down::main(args).await?;
+
+        Ok(())
+    }
+
+    /// Simulate the revert of the last applied migration.
+    ///
+    /// Nothing is executed and the migration record is kept,
+    /// the down script is resolved the same way as with `down()`.
+    ///
+    /// Returns the list of SurrealQL queries that would be executed:
+    /// the content of the down script, followed by the removal of the events introduced with the migration.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let queries = SurrealdbMigrations::new(db_configuration)
+    ///     .down_dry_run()
+    ///     .await
+    ///     .expect("Failed to simulate the revert of the last migration");
+    ///
+    /// for query in queries {
+    ///     println!("{}", query);
+    /// }
+    /// # });
+    /// ```
+    pub async fn down_dry_run(&self) -> Result<Vec<String>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = DownArgs {
+            operation: DownOperation::Last,
+            client: &client,
+            migrations_table: self.get_migrations_table(),
+            location,
+            dry_run: true,
+            clock: self.clock.as_deref(),
+            lock_timeout_ms: self.db_configuration.lock_timeout_ms,
+            lock_ttl_ms: self.db_configuration.lock_ttl_ms,
+        };
+        let queries = down::main(args).await?;
+
+        Ok(queries)
+    }
+
+    /// Re-run the last applied migration.
+    ///
+    /// The migration is first reverted using its paired down script,
+    /// then applied again from the current content of the migration file.
+    /// Fails if the last applied migration does not have a down script.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .redo()
+    ///     .await
+    ///     .expect("Failed to redo the last migration");
+    /// # });
+    /// ```
+    pub async fn redo(&self) -> Result<(), MigrationError> {
+        self.redo_last_migration(None).await
+    }
+
+    /// Re-run the last applied migration,
+    /// replacing `${NAME}` placeholders in the migration file with the value of the variable `NAME`.
+    ///
+    /// Fails if the migration file contains a placeholder without value, see `up_with_vars()`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `vars` - The values of the variables, by name.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::collections::HashMap;
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let vars = HashMap::from([("ADMIN_EMAIL".to_string(), "admin@example.com".to_string())]);
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .redo_with_vars(vars)
+    ///     .await
+    ///     .expect("Failed to redo the last migration");
+    /// # });
+    /// ```
+    pub async fn redo_with_vars(
+        &self,
+        vars: HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        self.redo_last_migration(Some(&vars)).await
+    }
+
+    async fn redo_last_migration(
+        &self,
+        vars: Option<&HashMap<String, String>>,
+    ) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = ApplyArgs {
+            vars,
+            ..self.apply_args(ApplyOperation::Up, &client, location)
+        };
+        redo::main(args).await?;
+
+        Ok(())
+    }
+
+    /// Revert all migrations applied after the named migration, the most recent first.
+    ///
+    /// Each migration is reverted using its paired down script, in its own transaction.
+    /// Nothing is reverted if any of these migrations does not have a down script.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the migration to revert to. This migration is kept applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .down_to("20230101_120002_AddPost")
+    ///     .await
+    ///     .expect("Failed to revert migrations");
+    /// # });
+    /// ```
+    pub async fn down_to(&self, name: &str) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = DownArgs {
+            operation: DownOperation::To(name.to_string()),
+            client: &client,
+            migrations_table: self.get_migrations_table(),
+            location,
+            dry_run: false,
+            clock: self.clock.as_deref(),
+            lock_timeout_ms: self.db_configuration.lock_timeout_ms,
+            lock_ttl_ms: self.db_configuration.lock_ttl_ms,
+        };
+        down::main(args).await?;
+
+        Ok(())
+    }
+
+    /// Bring the database to exactly the named migration, applying or reverting migrations as needed.
+    ///
+    /// The applied migrations that come after it are reverted, like with `down_to()`,
+    /// then the pending migrations up to and including it are applied, like with `up_to()`.
+    /// Fails if there is no migration file with this name, or if a migration that comes after it
+    /// has been applied before one that does not, as they cannot be reverted without the other.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the migration the database should be at.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .migrate_to("20230101_120002_AddPost")
+    ///     .await
+    ///     .expect("Failed to migrate to the migration");
+    /// # });
+    /// ```
+    pub async fn migrate_to(&self, name: &str) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let migrations_files = io::extract_migrations_files(&location)?;
+
+        if !migrations_files.iter().any(|file| file.name == name) {
+            return Err(anyhow!(
+                "Cannot migrate to migration '{}': this migration does not exist",
+                name
+            )
+            .into());
+        }
+
+        let client = self.get_client().await?;
+        let migrations_applied = surrealdb::list_script_migration_ordered_by_execution_date(
+            &client,
+            self.get_migrations_table(),
+        )
+        .await?;
+
+        // The migrations coming after the target must be the last ones applied to be reverted
+        let first_newer_position = migrations_applied
+            .iter()
+            .position(|migration| migration.script_name.as_str() > name);
+
+        if let Some(first_newer_position) = first_newer_position {
+            let older_migration_applied_after = migrations_applied[first_newer_position..]
+                .iter()
+                .find(|migration| migration.script_name.as_str() <= name);
+
+            if let Some(older_migration) = older_migration_applied_after {
+                return Err(anyhow!(
+                    "Cannot migrate to migration '{}': migration '{}' has been applied after '{}', which comes after it",
+                    name,
+                    older_migration.script_name,
+                    migrations_applied[first_newer_position].script_name
+                )
+                .into());
+            }
+
+            match first_newer_position {
+                0 => self.reset().await?,
+                _ => {
+                    let last_older_migration = &migrations_applied[first_newer_position - 1];
+                    self.down_to(&last_older_migration.script_name).await?
+                }
+            }
+        }
+
+        let has_pending_migrations = migrations_files.iter().any(|file| {
+            file.name.as_str() <= name
+                && !migrations_applied
+                    .iter()
+                    .any(|migration| migration.script_name == file.name)
+        });
+
+        if has_pending_migrations {
+            self.up_to(name).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Revert all applied migrations, the most recent first, to get back to an empty migrations history.
+    ///
+    /// Each migration is reverted using its paired down script, in its own transaction.
+    /// Nothing is reverted if any applied migration does not have a down script.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .reset()
+    ///     .await
+    ///     .expect("Failed to reset migrations");
+    /// # });
+    /// ```
+    pub async fn reset(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = DownArgs {
+            operation: DownOperation::All,
+            client: &client,
+            migrations_table: self.get_migrations_table(),
+            location,
+            dry_run: false,
+            clock: self.clock.as_deref(),
+            lock_timeout_ms: self.db_configuration.lock_timeout_ms,
+            lock_ttl_ms: self.db_configuration.lock_ttl_ms,
+        };
+        down::main(args).await?;
+
+        Ok(())
+    }
+
+    /// Mark the migrations up to and including the one given as applied, without running them.
+    ///
+    /// Useful when adopting the tool on a database that has already been migrated by hand,
+    /// so that next calls to `up()` only apply the migrations that come after.
+    /// The checksum of each migration is computed from its current file.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the last migration to mark as applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .baseline("20230317_153201_AddAdminUser")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn baseline(&self, name: &str) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        baseline::main(
+            &client,
+            self.get_migrations_table(),
+            name,
+            &location,
+            self.db_configuration.lock_timeout_ms,
+            self.db_configuration.lock_ttl_ms,
+            self.clock.as_deref(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a single migration as applied, without running it.
+    ///
+    /// Useful when a migration has been applied by hand.
+    /// Unlike `baseline()`, the migrations before it are left untouched.
+    /// Fails if the migration file does not exist or if the migration has already been applied.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the migration to mark as applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .mark_applied("20230317_153201_AddAdminUser")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn mark_applied(&self, name: &str) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+
+        let args = MarkAppliedArgs {
+            client: &client,
+            migrations_table: self.get_migrations_table(),
+            name,
+            location: &location,
+            lock_timeout_ms: self.db_configuration.lock_timeout_ms,
+            lock_ttl_ms: self.db_configuration.lock_ttl_ms,
+            applied_by: self.db_configuration.applied_by.to_owned(),
+            applied_from_host: self.db_configuration.applied_from_host.to_owned(),
+            clock: self.clock.as_deref(),
+        };
+        mark_applied::main(args).await?;
+
+        Ok(())
+    }
+
+    /// Create an empty migration file named `YYYYMMDD_HHMMSS_<name>.surql` in the migrations folder,
+    /// along with its `.down.surql` file if `create_down_script` is set in the configuration.
+    ///
+    /// With the `VersionFormat::Sequential` version format, the file is named `NNNN_<name>.surql`
+    /// instead, the version following the last version of the existing migrations.
+    ///
+    /// Returns the path of the migration file.
+    /// Fails if the name contains a character not allowed in a file name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the migration, without date and time prefix.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let path = SurrealdbMigrations::new(db_configuration)
+    ///     .create_migration("AddAdminUser")
+    ///     .expect("Failed to create the migration");
+    ///
+    /// println!("Migration created at {}", path.display());
+    /// ```
+    pub fn create_migration(&self, name: &str) -> Result<PathBuf, MigrationError> {
+        if self.source.is_some() {
+            return Err(anyhow!("Cannot create migration '{}' in a migration source", name).into());
+        }
+
+        let location = self.get_files_location()?;
+        let path = create_migration::main(
+            &location,
+            name,
+            self.db_configuration.create_down_script,
+            self.db_configuration.version_format,
+        )?;
+
+        Ok(path)
+    }
+
+    /// Concatenate the migrations up to and including the one given into a single migration file,
+    /// named `<version>_Squashed.surql` after the version of this last migration,
+    /// along with the down scripts in reverse order in its `.down.surql` file if `create_down_script`
+    /// is set in the configuration.
+    ///
+    /// The squashed migrations are left untouched and must be archived before applying migrations,
+    /// since the squashed migration shares the version of the last one.
+    /// Returns the path of the squashed migration file.
+    ///
+    /// ## Arguments
+    ///
+    /// * `up_to` - The name of the last migration to squash.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let path = SurrealdbMigrations::new(db_configuration)
+    ///     .squash("20230317_153201_AddAdminUser")
+    ///     .expect("Failed to squash the migrations");
+    ///
+    /// println!("Migrations squashed into {}", path.display());
+    /// ```
+    pub fn squash(&self, up_to: &str) -> Result<PathBuf, MigrationError> {
+        if self.source.is_some() {
+            return Err(anyhow!("Cannot squash migrations in a migration source").into());
+        }
+
+        let location = self.get_files_location()?;
+        let path = squash::main(
+            &location,
+            up_to,
+            self.db_configuration.create_down_script,
+            self.db_configuration.version_format,
+        )?;
+
+        Ok(path)
+    }
+
+    /// Forget that a migration has been applied, without running any script.
+    ///
+    /// Useful to repair the migrations history when a migration has been reverted by hand.
+    /// Fails if the migration has not been applied.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the migration to forget.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .unmark("20230317_153201_AddAdminUser")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn unmark(&self, name: &str) -> Result<(), MigrationError> {
+        let client = self.get_client().await?;
+        unmark::main(&client, self.get_migrations_table(), name).await?;
+
+        Ok(())
+    }
+
+    /// List the names of the migrations that have not been applied yet, sorted by version.
+    ///
+    /// Fails if a migration has been applied but its file no longer exists.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let pending_migrations = SurrealdbMigrations::new(db_configuration)
+    ///     .pending()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn pending(&self) -> Result<Vec<String>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        let result = pending::main(&client, self.get_migrations_table(), &location).await?;
+
+        Ok(result)
+    }
+
+    /// List the migrations that have not been applied yet with the content of their file, sorted by version.
+    ///
+    /// Unlike `up_dry_run()`, schemas and events are not included.
+    /// Fails if a migration has been applied but its file no longer exists.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let pending_migrations = SurrealdbMigrations::new(db_configuration)
+    ///     .list_pending_with_content()
+    ///     .await?;
+    ///
+    /// for (name, content) in pending_migrations {
+    ///     println!("{}:\n{}", name, content);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn list_pending_with_content(&self) -> Result<Vec<(String, String)>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        let result =
+            pending::main_with_content(&client, self.get_migrations_table(), &location).await?;
+
+        Ok(result)
+    }
+
+    /// Get the status of every migration, applied or pending, sorted by version.
+    ///
+    /// Migrations applied whose file no longer exists are listed separately as orphaned.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let status = SurrealdbMigrations::new(db_configuration).status().await?;
+    ///
+    /// for migration in status.migrations {
+    ///     println!("{} applied: {}", migration.name, migration.is_applied);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn status(&self) -> Result<MigrationStatus, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        let result = status::main(&client, self.get_migrations_table(), &location).await?;
+
+        Ok(result)
+    }
+
+    /// Get the migrations as a graph ordered by version, combining the migration files and the migrations applied,
+    /// e.g. to draw a diagram of the migrations history.
+    ///
+    /// Each migration depends on the migration of the previous version.
+    /// Migrations applied whose file no longer exists are part of the graph, without a file checksum.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let graph = SurrealdbMigrations::new(db_configuration).graph().await?;
+    ///
+    /// for node in graph.nodes {
+    ///     match node.depends_on {
+    ///         Some(depends_on) => println!("{} -> {}", depends_on, node.name),
+    ///         None => println!("{}", node.name),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn graph(&self) -> Result<MigrationGraph, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        let result = graph::main(
+            &client,
+            self.get_migrations_table(),
+            &location,
+            self.db_configuration.version_format,
+        )
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Count the migrations applied and pending, without fetching their records nor their content.
+    ///
+    /// Much cheaper than `status()`, e.g. for a readiness probe run frequently.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let counts = SurrealdbMigrations::new(db_configuration).counts().await?;
+    ///
+    /// println!("{}/{} migrations applied", counts.total - counts.pending, counts.total);
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn counts(&self) -> Result<MigrationCounts, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        let result = counts::main(&client, self.get_migrations_table(), &location).await?;
+
+        Ok(result)
+    }
+
+    /// Compare the table, field, index and event definitions of the schema/event files
+    /// with the ones currently defined in the database.
+    ///
+    /// Useful to detect changes made to the database outside of the migrations before deploying.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let diff = SurrealdbMigrations::new(db_configuration).schema_diff().await?;
+    ///
+    /// for definition in diff.removed {
+    ///     println!("Not defined in the files: {}", definition.definition);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn schema_diff(&self) -> Result<SchemaDiff, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        let result = schema_diff::main(&client, self.get_migrations_table(), &location).await?;
+
+        Ok(result)
+    }
+
+    /// Write the table, field and index definitions of the database in the `schemas` folder of `out_dir`,
+    /// one file per table, so that the tool can be adopted on an existing database.
+    ///
+    /// Fails without writing anything if one of the files already exists.
+    ///
+    /// ## Arguments
+    ///
+    /// * `out_dir` - The folder in which the `schemas` folder (or the configured schema folder) is created.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .export_schema(Path::new("db"))
+    ///     .await
+    ///     .expect("Failed to export schema");
+    /// # });
+    /// ```
+    pub async fn export_schema(&self, out_dir: &Path) -> Result<(), MigrationError> {
+        let client = self.get_client().await?;
+        let dir_names = self.get_dir_names();
+        export_schema::main(
+            &client,
+            self.get_migrations_table(),
+            out_dir,
+            &dir_names.schemas,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Verify that the migrations applied have not been edited since,
+    /// by comparing the checksum stored on apply with the checksum of the current migration file.
+    ///
+    /// Returns the migrations whose content no longer matches.
+    /// Migrations applied without checksum (by an older version) are ignored.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let checksum_mismatches = SurrealdbMigrations::new(db_configuration)
+    ///     .verify()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn verify(&self) -> Result<Vec<ChecksumMismatch>, MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        let result = verify::main(&client, self.get_migrations_table(), &location).await?;
+
+        Ok(result)
+    }
+
+    /// Run every diagnostic check at once, to troubleshoot migrations misbehaving.
+    ///
+    /// Checks the connection to the SurrealDB instance, the schemas and migrations folders,
+    /// the definition of the migrations table, the migrations applied whose file no longer exists,
+    /// the migrations modified since they were applied, and the version order of the migrations.
+    /// A failing check never prevents the next ones from running, so every problem is reported.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let report = SurrealdbMigrations::new(db_configuration)
+    ///     .doctor()
+    ///     .await
+    ///     .expect("Failed to run diagnostic checks");
+    ///
+    /// for check in report.failed_checks() {
+    ///     println!("{:?} failed: {}", check.kind, check.details.join(", "));
+    /// }
+    /// # });
+    /// ```
+    pub async fn doctor(&self) -> Result<DiagnosticReport, MigrationError> {
+        let args = DoctorArgs {
+            client: self.get_client().await,
+            location: self.fetch_files_location().await,
+            migrations_table: self.get_migrations_table(),
+            version_format: self.db_configuration.version_format,
+            enabled_tags: &self.db_configuration.enabled_tags,
+            skip: &self.db_configuration.skip,
+        };
+        let report = doctor::main(args).await;
+
+        Ok(report)
+    }
+
+    /// Update the checksum stored for each applied migration with the checksum of its current file,
+    /// after intentionally editing applied migrations, so that they are no longer reported by `verify()`.
+    ///
+    /// Only the checksums are updated, the migrations are not applied again.
+    /// Applied migrations whose file no longer exists are left untouched.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .refresh_checksums()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn refresh_checksums(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        refresh_checksums::main(
+            &client,
+            self.get_migrations_table(),
+            &location,
+            self.db_configuration.lock_timeout_ms,
+            self.db_configuration.lock_ttl_ms,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Release the migrations lock, even if it is held by another process.
+    ///
+    /// Only use it when the process holding the lock has crashed, and waiting for the lock
+    /// to be older than the configured `lock_ttl_ms` is not an option.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .force_release_lock()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn force_release_lock(&self) -> Result<(), MigrationError> {
+        let client = self.get_client().await?;
+        lock::force_release(&client, self.get_migrations_table()).await?;
+
+        Ok(())
+    }
+
+    /// List script migrations that have been applied to the database.
+    ///
+    /// The migrations are sorted by the database, so that large histories are not sorted in memory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `order` - The order of the migrations, by execution date or by version, ascending or descending.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let migrations_applied = SurrealdbMigrations::new(db_configuration)
+    ///     .list(ListOrder::ExecutionDateDesc)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn list(&self, order: ListOrder) -> Result<Vec<ScriptMigration>, MigrationError> {
+        let client = self.get_client().await?;
+        let migrations_applied =
+            surrealdb::list_script_migration(&client, self.get_migrations_table(), order).await?;
+
+        Ok(migrations_applied)
+    }
+
+    /// List script migrations applied between two dates, bounds included, ordered by execution date.
+    ///
+    /// The date range is filtered by the database, so that the other migrations are not fetched.
+    ///
+    /// ## Arguments
+    ///
+    /// * `from` - The start of the date range.
+    /// * `to` - The end of the date range.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use chrono::{Duration, Utc};
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let now = Utc::now();
+    /// let migrations_applied = SurrealdbMigrations::new(db_configuration)
+    ///     .list_between(now - Duration::days(7), now)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn list_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ScriptMigration>, MigrationError> {
+        let client = self.get_client().await?;
+        let migrations_applied =
+            list_between::main(&client, self.get_migrations_table(), from, to).await?;
+
+        Ok(migrations_applied)
+    }
+
+    /// List the operations applied on migrations, in the order they happened.
+    ///
+    /// Unlike the migrations table, from which reverted migrations are deleted,
+    /// the history keeps every time a migration has been applied, rolled back or re-applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let history = SurrealdbMigrations::new(db_configuration)
+    ///     .history()
+    ///     .await?;
+    ///
+    /// for event in history {
+    ///     println!("{} {:?} at {}", event.script_name, event.operation, event.executed_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn history(&self) -> Result<Vec<MigrationEvent>, MigrationError> {
+        let client = self.get_client().await?;
+        let history = history::list(&client, self.get_migrations_table()).await?;
+
+        Ok(history)
+    }
+
+    /// Get the script migration applied last, by execution date, or `None` if none have been applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let last_applied = SurrealdbMigrations::new(db_configuration)
+    ///     .last_applied()
+    ///     .await?;
+    ///
+    /// if let Some(migration) = last_applied {
+    ///     println!("{} applied at {}", migration.script_name, migration.executed_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn last_applied(&self) -> Result<Option<ScriptMigration>, MigrationError> {
+        let client = self.get_client().await?;
+        let result = last_applied::main(&client, self.get_migrations_table()).await?;
+
+        Ok(result)
+    }
+
+    /// Get the script migration applied with the given name, or `None` if it has not been applied.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The name of the migration, e.g. `20230101_120002_AddPost`
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let migration = SurrealdbMigrations::new(db_configuration)
+    ///     .get_applied("20230101_120002_AddPost")
+    ///     .await?;
+    ///
+    /// if let Some(migration) = migration {
+    ///     println!("{} applied at {}", migration.script_name, migration.executed_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn get_applied(&self, name: &str) -> Result<Option<ScriptMigration>, MigrationError> {
+        let client = self.get_client().await?;
+        let result = get_applied::main(&client, self.get_migrations_table(), name).await?;
+
+        Ok(result)
+    }
+
+    /// List script migrations that have been applied to the database, ordered by execution date,
+    /// as a stream reading the migrations table page by page.
+    ///
+    /// Useful for large migration histories, when not every migration applied is needed.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    /// let runner = SurrealdbMigrations::new(db_configuration);
+    ///
+    /// let mut migrations_applied = runner.list_stream();
+    ///
+    /// while let Some(migration_applied) = migrations_applied.try_next().await? {
+    ///     println!("{}", migration_applied.script_name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub fn list_stream(
+        &self,
+    ) -> impl Stream<Item = Result<ScriptMigration, MigrationError>> + Unpin + '_ {
+        let migrations_table = self.get_migrations_table();
+
+        let migrations_applied = stream::once(self.get_client())
+            .map_ok(move |client| {
+                list_stream::main(client, migrations_table).map_err(MigrationError::from)
+            })
+            .try_flatten();
+
+        Box::pin(migrations_applied)
+    }
+
+    /// Execute an arbitrary `.surql` script with the configured client, e.g. for maintenance tasks.
+    ///
+    /// The script is not recorded as a migration, so it can be run as many times as needed.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the `.surql` file to execute.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .run_script(Path::new("scripts/cleanup.surql"))
+    ///     .await
+    ///     .expect("Failed to run the script");
+    /// # });
+    /// ```
+    pub async fn run_script(&self, path: &Path) -> Result<(), MigrationError> {
+        let client = self.get_client().await?;
+        run_script::main(&client, path).await?;
+
+        Ok(())
+    }
+
+    /// Run the seed files of the `seeds` folder in name order, e.g. to fill a dev database.
+    ///
+    /// Seeds are not recorded as applied, so they can be run again and should be idempotent,
+    /// like `UPSERT` statements. Does nothing if there is no `seeds` folder.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// SurrealdbMigrations::new(db_configuration)
+    ///     .seed()
+    ///     .await
+    ///     .expect("Failed to run the seeds");
+    /// # });
+    /// ```
+    pub async fn seed(&self) -> Result<(), MigrationError> {
+        let location = self.fetch_files_location().await?;
+        let client = self.get_client().await?;
+        seed::main(&client, &location).await?;
+
+        Ok(())
+    }
+
+    /// Get the version of the SurrealDB server, like `1.0.0-beta.9+20230402`.
+    ///
+    /// See `min_server_version` in the configuration to abort the apply operations on older servers.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db_configuration = SurrealdbConfiguration::default();
+    ///
+    /// let version = SurrealdbMigrations::new(db_configuration)
+    ///     .server_version()
+    ///     .await?;
+    ///
+    /// println!("Connected to SurrealDB {}", version);
+    /// # Ok(())
+    /// # }
+    /// # main().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn server_version(&self) -> Result<String, MigrationError> {
+        let client = self.get_client().await?;
+        let version = server_version::get(&client).await?;
+
+        Ok(version.to_string())
+    }
+
+    /// Get the connection details effectively used, after applying the project config file
+    /// and the default values, to diagnose connection or authentication failures.
+    ///
+    /// The password and the token are redacted.
+    /// With `from_client`, the details describe the configuration only, not the client given.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+    ///
+    /// let db_configuration = SurrealdbConfiguration::default();
+    /// let runner = SurrealdbMigrations::new(db_configuration);
+    ///
+    /// let resolved_config = runner.resolved_config();
+    /// println!("Connecting to {} ({}/{})", resolved_config.url, resolved_config.ns, resolved_config.db);
+    /// ```
+    pub fn resolved_config(&self) -> ResolvedConfig {
+        surrealdb::resolve_config(&self.db_configuration, &ProjectConfig::load())
     }
 }
@@ -6,10 +6,13 @@ use cli_table::{format::Border, Cell, ColorChoice, Style, Table};
 use crate::{input::SurrealdbConfiguration, surrealdb};
 
 pub async fn main(db_configuration: &SurrealdbConfiguration, no_color: bool) -> Result<()> {
-    let client = surrealdb::create_surrealdb_client(&db_configuration).await?;
+    let client = surrealdb::create_surrealdb_client(db_configuration, Some(&print_warning)).await?;
+
+    let migrations_table = surrealdb::get_migrations_table(db_configuration);
 
     let migrations_applied =
-        surrealdb::list_script_migration_ordered_by_execution_date(&client).await?;
+        surrealdb::list_script_migration_ordered_by_execution_date(&client, migrations_table)
+            .await?;
 
     if migrations_applied.is_empty() {
         println!("No migrations applied yet!");
@@ -64,3 +67,7 @@ pub async fn main(db_configuration: &SurrealdbConfiguration, no_color: bool) ->
 
     Ok(())
 }
+
+fn print_warning(message: &str) {
+    eprintln!("{}", message);
+}
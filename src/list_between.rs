@@ -0,0 +1,58 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::json;
+
+use crate::{input::ExecutedAtFormat, models::ScriptMigration, surrealdb::MigrationsTable};
+
+/// List the script migrations applied within the date range, bounds included, ordered by execution date.
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<ScriptMigration>> {
+    // Dates stored as numbers are compared with the bounds as numbers, the others as datetimes
+    let (executed_at, bound_cast, from, to) = match migrations_table.executed_at_format {
+        ExecutedAtFormat::Datetime => (
+            migrations_table.executed_at.to_string(),
+            "<datetime> ",
+            json!(format_bound(from)),
+            json!(format_bound(to)),
+        ),
+        ExecutedAtFormat::IsoString => (
+            format!("<datetime> {}", migrations_table.executed_at),
+            "<datetime> ",
+            json!(format_bound(from)),
+            json!(format_bound(to)),
+        ),
+        ExecutedAtFormat::EpochMillis => (
+            migrations_table.executed_at.to_string(),
+            "",
+            json!(from.timestamp_millis()),
+            json!(to.timestamp_millis()),
+        ),
+    };
+
+    let query = format!(
+        "SELECT {fields} FROM {table} WHERE {executed_at} >= {cast}$from AND {executed_at} <= {cast}$to ORDER BY {order_by};",
+        fields = migrations_table.select_fields(),
+        table = migrations_table,
+        executed_at = executed_at,
+        cast = bound_cast,
+        order_by = migrations_table.executed_at
+    );
+
+    let mut response = client
+        .query(query)
+        .bind(("from", from))
+        .bind(("to", to))
+        .await?;
+    let migrations_applied = response.take(0)?;
+
+    Ok(migrations_applied)
+}
+
+fn format_bound(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
@@ -0,0 +1,55 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+use futures::{stream, Stream, TryStreamExt};
+
+use crate::{models::ScriptMigration, surrealdb::MigrationsTable};
+
+const PAGE_SIZE: usize = 100;
+
+/// Stream the script migrations applied, ordered by execution date,
+/// reading the migrations table page by page.
+pub fn main(
+    client: Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> impl Stream<Item = Result<ScriptMigration>> + '_ {
+    let pages = stream::try_unfold(Some(0), move |start| {
+        let client = client.clone();
+
+        async move {
+            let Some(start) = start else {
+                return Ok::<_, anyhow::Error>(None);
+            };
+
+            let page = list_page(&client, migrations_table, start).await?;
+
+            let next_start = match page.len() < PAGE_SIZE {
+                true => None,
+                false => Some(start + PAGE_SIZE),
+            };
+
+            Ok(Some((stream::iter(page.into_iter().map(Ok)), next_start)))
+        }
+    });
+
+    pages.try_flatten()
+}
+
+async fn list_page(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    start: usize,
+) -> Result<Vec<ScriptMigration>> {
+    let query = format!(
+        "SELECT {} FROM {} ORDER BY {} LIMIT {} START {};",
+        migrations_table.select_fields(),
+        migrations_table,
+        migrations_table.executed_at,
+        PAGE_SIZE,
+        start
+    );
+
+    let mut response = client.query(query).await?;
+    let page = response.take(0)?;
+
+    Ok(page)
+}
@@ -0,0 +1,115 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+use serde::Deserialize;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{error::MigrationError, surrealdb::MigrationsTable};
+
+const LOCK_ID: &str = "lock";
+const DEFAULT_LOCK_TIMEOUT_MS: u64 = 30_000;
+const DEFAULT_LOCK_TTL_MS: u64 = 600_000;
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Deserialize)]
+struct MigrationLock {
+    owner: String,
+}
+
+/// Acquire the migrations lock, waiting for it to be released if it is held by someone else.
+/// A lock older than its time to live has been left by a process that crashed, and is taken over.
+/// Returns the owner id used to release the lock.
+pub async fn acquire(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    lock_timeout_ms: Option<u64>,
+    lock_ttl_ms: Option<u64>,
+) -> Result<String> {
+    let owner = generate_owner();
+    let lock_timeout_ms = lock_timeout_ms.unwrap_or(DEFAULT_LOCK_TIMEOUT_MS);
+    let lock_timeout = Duration::from_millis(lock_timeout_ms);
+    let lock_ttl_ms = lock_ttl_ms.unwrap_or(DEFAULT_LOCK_TTL_MS);
+    let lock_table = migrations_table.lock_table();
+
+    let query = format!(
+        "CREATE {}:{} SET owner = $owner, locked_at = time::now();",
+        lock_table, LOCK_ID
+    );
+    let take_over_query = format!(
+        "UPDATE {}:{} SET owner = $owner, locked_at = time::now() WHERE locked_at < time::now() - {}ms;",
+        lock_table, LOCK_ID, lock_ttl_ms
+    );
+
+    let start = Instant::now();
+
+    loop {
+        let response = client.query(&query).bind(("owner", owner.as_str())).await?;
+
+        let error = match response.check() {
+            Ok(_) => return Ok(owner),
+            Err(error) => error,
+        };
+
+        let mut response = client
+            .query(&take_over_query)
+            .bind(("owner", owner.as_str()))
+            .await?;
+        let stale_locks: Vec<MigrationLock> = response.take(0)?;
+
+        if !stale_locks.is_empty() {
+            return Ok(owner);
+        }
+
+        let current_lock: Option<MigrationLock> =
+            client.select((lock_table.as_str(), LOCK_ID)).await?;
+
+        if start.elapsed() >= lock_timeout {
+            return match current_lock {
+                Some(current_lock) => Err(MigrationError::LockTimeout {
+                    owner: current_lock.owner,
+                    timeout_ms: lock_timeout_ms,
+                }
+                .into()),
+                None => Err(error.into()),
+            };
+        }
+
+        tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+    }
+}
+
+pub async fn release(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    owner: &str,
+) -> Result<()> {
+    let query = format!(
+        "DELETE {}:{} WHERE owner = $owner;",
+        migrations_table.lock_table(),
+        LOCK_ID
+    );
+
+    client.query(query).bind(("owner", owner)).await?.check()?;
+
+    Ok(())
+}
+
+/// Release the migrations lock whoever holds it, like a process that crashed before releasing it.
+pub async fn force_release(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<()> {
+    let query = format!("DELETE {}:{};", migrations_table.lock_table(), LOCK_ID);
+
+    client.query(query).await?.check()?;
+
+    Ok(())
+}
+
+fn generate_owner() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+
+    format!("{}-{}", std::process::id(), nanos)
+}
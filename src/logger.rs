@@ -0,0 +1,13 @@
+pub fn log(logger: Option<&(dyn Fn(&str) + Send + Sync)>, message: &str) {
+    if let Some(logger) = logger {
+        logger(message);
+    }
+}
+
+/// Log a warning, also emitted as a tracing event when the `tracing` feature is enabled.
+pub fn warn(logger: Option<&(dyn Fn(&str) + Send + Sync)>, message: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!("{}", message);
+
+    log(logger, &format!("Warning: {}", message));
+}
@@ -1,22 +1,63 @@
 use anyhow::{anyhow, Result};
-use apply::ApplyArgs;
+use apply::{ApplyArgs, ApplyOperation};
 use clap::Parser;
 use cli::{Action, Args, CreateAction, ScaffoldAction};
 use create::CreateOperation;
 use input::SurrealdbConfiguration;
+use io::{DirNames, FilesLocation};
+use retry::MigrationRetry;
 
+// Some items of the modules shared with the library are only used by the library
+#[allow(dead_code)]
 mod apply;
+mod audit;
+mod checksum;
 mod cli;
+mod clock;
+#[allow(dead_code)]
 mod config;
 mod constants;
 mod create;
+#[allow(dead_code)]
+mod create_migration;
+#[allow(dead_code)]
+mod definition_checksums;
 mod definitions;
+mod directives;
+#[allow(dead_code)]
+mod error;
+mod health_check;
+#[allow(dead_code)]
+mod history;
+mod idempotent_defines;
+#[allow(dead_code)]
 mod input;
+#[allow(dead_code)]
+mod io;
 mod list;
+#[allow(dead_code)]
+mod lock;
+mod logger;
+mod migration_meta;
+#[allow(dead_code)]
 mod models;
 mod remove;
+#[allow(dead_code)]
+mod retry;
 mod scaffold;
+mod schema_version;
+mod server_version;
+#[allow(dead_code)]
 mod surrealdb;
+mod tls;
+mod validate_schema_files;
+#[allow(dead_code)]
+mod validate_version_order;
+mod variables;
+#[allow(dead_code)]
+mod verify;
+#[allow(dead_code)]
+mod version;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -66,14 +107,64 @@ async fn main() -> Result<()> {
                 db,
                 username,
                 password,
+                ..Default::default()
             };
+            let client =
+                surrealdb::create_surrealdb_client(&db_configuration, Some(&print_log)).await?;
+
+            let operation = match up {
+                Some(name) => ApplyOperation::UpTo(name),
+                None => ApplyOperation::Up,
+            };
+
             let args = ApplyArgs {
-                up,
-                db_configuration: &db_configuration,
-                display_logs: true,
+                operation,
+                client: &client,
+                migrations_table: surrealdb::get_migrations_table(&db_configuration),
+                transactional: db_configuration.transactional,
+                location: FilesLocation {
+                    folder_path: config::retrieve_folder_path(),
+                    embedded_dir: None,
+                    source_files: None,
+                    folders: Vec::new(),
+                    dir_names: DirNames::default(),
+                    version_format: db_configuration.version_format,
+                },
+                logger: Some(&print_log),
                 dry_run,
+                force_schema: db_configuration.force_schema,
+                idempotent_defines: db_configuration.idempotent_defines,
+                schema_concurrency: db_configuration.schema_concurrency,
+                strict_schema: db_configuration.strict_schema,
+                continue_on_error: db_configuration.continue_on_error,
+                health_check: db_configuration.health_check.as_ref(),
+                min_server_version: db_configuration.min_server_version.as_deref(),
+                allow_checksum_mismatch: db_configuration.allow_checksum_mismatch,
+                migration_timeout: db_configuration.migration_timeout,
+                migration_retry: MigrationRetry::new(
+                    db_configuration.migration_retries,
+                    db_configuration.migration_retry_delay_ms,
+                    None,
+                ),
+                version_format: db_configuration.version_format,
+                validate_before_apply: db_configuration.validate_before_apply,
+                applied_by: db_configuration.applied_by.to_owned(),
+                applied_from_host: db_configuration.applied_from_host.to_owned(),
+                clock: None,
+                on_progress: None,
+                statement_progress: db_configuration.statement_progress,
+                lock_timeout_ms: db_configuration.lock_timeout_ms,
+                lock_ttl_ms: db_configuration.lock_ttl_ms,
+                concurrency_control: db_configuration.concurrency_control,
+                vars: None,
+                enabled_tags: &db_configuration.enabled_tags,
+                skip: &db_configuration.skip,
+                log_statements: db_configuration.log_statements,
+                redacted_vars: &db_configuration.redacted_vars,
             };
-            apply::main(args).await
+            apply::main(args).await?;
+
+            Ok(())
         }
         Action::List {
             url,
@@ -89,8 +180,13 @@ async fn main() -> Result<()> {
                 db,
                 username,
                 password,
+                ..Default::default()
             };
             list::main(&db_configuration, no_color).await
         }
     }
 }
+
+fn print_log(message: &str) {
+    println!("{}", message);
+}
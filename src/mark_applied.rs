@@ -0,0 +1,98 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{anyhow, Context, Result};
+
+use crate::{
+    audit, checksum,
+    clock::{self, Clock},
+    io::{self, FilesLocation},
+    lock,
+    surrealdb::{self, MigrationsTable},
+};
+
+pub struct MarkAppliedArgs<'a> {
+    pub client: &'a Surreal<Any>,
+    pub migrations_table: MigrationsTable<'a>,
+    pub name: &'a str,
+    pub location: &'a FilesLocation,
+    pub lock_timeout_ms: Option<u64>,
+    pub lock_ttl_ms: Option<u64>,
+    pub applied_by: Option<String>,
+    pub applied_from_host: Option<String>,
+    pub clock: Option<&'a Clock>,
+}
+
+pub async fn main(args: MarkAppliedArgs<'_>) -> Result<()> {
+    let client = args.client;
+    let migrations_table = args.migrations_table;
+
+    let owner = lock::acquire(
+        client,
+        args.migrations_table,
+        args.lock_timeout_ms,
+        args.lock_ttl_ms,
+    )
+    .await?;
+    let result = mark_applied(args).await;
+    let release_result = lock::release(client, migrations_table, &owner).await;
+
+    result?;
+    release_result?;
+
+    Ok(())
+}
+
+async fn mark_applied(args: MarkAppliedArgs<'_>) -> Result<()> {
+    let MarkAppliedArgs {
+        client,
+        migrations_table,
+        name,
+        location,
+        applied_by,
+        applied_from_host,
+        clock,
+        ..
+    } = args;
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    let migration_file = migrations_files
+        .iter()
+        .find(|migration_file| migration_file.name == name)
+        .context(format!(
+            "Cannot mark migration '{}' as applied: this migration does not exist",
+            name
+        ))?;
+
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let has_already_been_applied = migrations_applied
+        .iter()
+        .any(|migration_applied| migration_applied.script_name == name);
+
+    if has_already_been_applied {
+        return Err(anyhow!(
+            "Cannot mark migration '{}' as applied: this migration has already been applied",
+            name
+        ));
+    }
+
+    let checksum = checksum::compute_checksum(&migration_file.get_content()?);
+
+    let mut params = audit::get_audit_params(applied_by, applied_from_host);
+
+    let query = format!(
+        "CREATE {} SET {} = $migration_name, checksum = $migration_checksum{}, {} = {};",
+        migrations_table,
+        migrations_table.script_name,
+        audit::get_audit_fields_query(&params),
+        migrations_table.executed_at,
+        migrations_table.executed_at_value(&clock::get_now_expression(clock))
+    );
+
+    params.insert("migration_name", name.to_string());
+    params.insert("migration_checksum", checksum);
+
+    surrealdb::apply_query_with_params(client, &query, &params).await
+}
@@ -0,0 +1,187 @@
+use anyhow::{anyhow, Result};
+
+/// Prefixes of the SurrealQL line comments.
+const COMMENT_PREFIXES: [&str; 3] = ["--", "//", "#"];
+
+/// Keyword of the header declaring the namespace and database of a migration.
+const USE_HEADER: &str = "@use";
+
+/// Metadata of a migration, declared in the comment lines at the top of its file,
+/// like `-- tags: analytics, reporting` or `-- @use ns shared db config`.
+#[derive(Debug, Default, PartialEq)]
+pub struct MigrationMeta {
+    /// Tags of the migration, which is applied only if all of them are enabled.
+    pub tags: Vec<String>,
+    /// Namespace and database the migration is applied to, instead of the configured ones.
+    pub target: Option<MigrationTarget>,
+}
+
+/// A namespace and a database, like the ones a migration is applied to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationTarget {
+    pub ns: String,
+    pub db: String,
+}
+
+impl MigrationTarget {
+    /// Run a query in this namespace and database, then switch back to the default ones.
+    pub fn wrap_query(&self, query: &str, default_target: &MigrationTarget) -> String {
+        format!(
+            "USE NS {} DB {};\n{}\nUSE NS {} DB {};",
+            self.ns, self.db, query, default_target.ns, default_target.db
+        )
+    }
+}
+
+/// Check if the text of a comment is a `@use` header.
+fn is_use_header(text: &str) -> bool {
+    text.split_whitespace().next() == Some(USE_HEADER)
+}
+
+impl MigrationMeta {
+    /// Parse the header of a migration, made of the comment lines before its first statement.
+    pub fn parse(content: &str) -> Result<MigrationMeta> {
+        let mut meta = MigrationMeta::default();
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let Some(comment) = COMMENT_PREFIXES
+                .iter()
+                .find_map(|prefix| line.strip_prefix(prefix))
+            else {
+                break;
+            };
+
+            if is_use_header(comment.trim()) {
+                meta.target = Some(parse_target(comment.trim())?);
+                continue;
+            }
+
+            let Some((key, value)) = comment.split_once(':') else {
+                continue;
+            };
+
+            if key.trim().eq_ignore_ascii_case("tags") {
+                let tags = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string);
+
+                meta.tags.extend(tags);
+            }
+        }
+
+        Ok(meta)
+    }
+
+    /// Check if all the tags of the migration are enabled, which is always the case without tags.
+    pub fn is_enabled(&self, enabled_tags: &[String]) -> bool {
+        self.tags.iter().all(|tag| enabled_tags.contains(tag))
+    }
+}
+
+/// Parse a `@use ns <namespace> db <database>` header.
+fn parse_target(text: &str) -> Result<MigrationTarget> {
+    let parts = text.split_whitespace().collect::<Vec<_>>();
+
+    match parts[..] {
+        [USE_HEADER, ns_keyword, ns, db_keyword, db]
+            if ns_keyword.eq_ignore_ascii_case("ns") && db_keyword.eq_ignore_ascii_case("db") =>
+        {
+            Ok(MigrationTarget {
+                ns: ns.to_string(),
+                db: db.to_string(),
+            })
+        }
+        _ => Err(anyhow!(
+            "Invalid header '{}', expected '@use ns <namespace> db <database>'",
+            text
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_read_tags_of_header() {
+        let content = "-- Create the analytics tables
+-- tags: analytics, reporting
+
+CREATE event;
+-- tags: ignored";
+
+        let meta = MigrationMeta::parse(content).unwrap();
+
+        assert_eq!(meta.tags, vec!["analytics", "reporting"]);
+    }
+
+    #[test]
+    fn parse_should_return_no_tags_without_header() {
+        let meta = MigrationMeta::parse("CREATE event;").unwrap();
+
+        assert_eq!(meta, MigrationMeta::default());
+    }
+
+    #[test]
+    fn is_enabled_should_require_all_tags() {
+        let meta = MigrationMeta {
+            tags: vec!["analytics".to_string(), "reporting".to_string()],
+            target: None,
+        };
+
+        assert!(meta.is_enabled(&["analytics".to_string(), "reporting".to_string()]));
+        assert!(!meta.is_enabled(&["analytics".to_string()]));
+        assert!(MigrationMeta::default().is_enabled(&[]));
+    }
+
+    #[test]
+    fn parse_should_read_target_of_header() {
+        let content = "-- tags: config
+-- @use ns shared db config
+CREATE setting;";
+
+        let meta = MigrationMeta::parse(content).unwrap();
+
+        assert_eq!(
+            meta.target,
+            Some(MigrationTarget {
+                ns: "shared".to_string(),
+                db: "config".to_string(),
+            })
+        );
+        assert_eq!(meta.tags, vec!["config"]);
+    }
+
+    #[test]
+    fn parse_should_fail_on_invalid_target() {
+        let result = MigrationMeta::parse("-- @use db config\nCREATE setting;");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid header '@use db config', expected '@use ns <namespace> db <database>'"
+        );
+    }
+
+    #[test]
+    fn wrap_query_should_switch_back_to_default_target() {
+        let target = MigrationTarget {
+            ns: "shared".to_string(),
+            db: "config".to_string(),
+        };
+        let default_target = MigrationTarget {
+            ns: "test".to_string(),
+            db: "test".to_string(),
+        };
+
+        assert_eq!(
+            target.wrap_query("CREATE setting;", &default_target),
+            "USE NS shared DB config;\nCREATE setting;\nUSE NS test DB test;"
+        );
+    }
+}
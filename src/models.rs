@@ -1,7 +1,315 @@
-use serde::{Deserialize, Serialize};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::input::AuthLevel;
+
+/// A migration applied to the database, as recorded in the migrations table.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ScriptMigration {
     pub script_name: String,
+    /// Execution date of the migration, as an ISO-8601 string.
+    /// Dates stored as a number of milliseconds since the Unix epoch are converted.
+    #[serde(deserialize_with = "deserialize_executed_at")]
     pub executed_at: String,
+    /// SHA-256 checksum of the migration file content, when it was applied.
+    /// Migrations applied by older versions have no checksum.
+    #[serde(default)]
+    pub checksum: Option<String>,
+    /// Time taken to execute the migration, in milliseconds.
+    /// Migrations applied by older versions have no duration.
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Name of the user who applied the migration, if known.
+    #[serde(default)]
+    pub applied_by: Option<String>,
+    /// Name of the host the migration was applied from, if known.
+    #[serde(default)]
+    pub applied_from_host: Option<String>,
+}
+
+/// Read an execution date stored as a datetime, an ISO-8601 string or a number of milliseconds.
+fn deserialize_executed_at<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ExecutedAt {
+        Date(String),
+        EpochMillis(i64),
+    }
+
+    match ExecutedAt::deserialize(deserializer)? {
+        ExecutedAt::Date(date) => Ok(date),
+        ExecutedAt::EpochMillis(millis) => Utc
+            .timestamp_millis_opt(millis)
+            .single()
+            .map(|date| date.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            .ok_or_else(|| serde::de::Error::custom(format!("Invalid execution date {}", millis))),
+    }
+}
+
+/// An operation on a migration, as recorded in the migrations history.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationOperation {
+    /// The migration has been applied for the first time.
+    Applied,
+    /// The migration has been reverted using its down script.
+    RolledBack,
+    /// The migration has been applied again after being rolled back.
+    Reapplied,
+}
+
+/// An entry of the migrations history, which is never modified nor deleted once recorded.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MigrationEvent {
+    pub script_name: String,
+    pub operation: MigrationOperation,
+    /// Date of the operation, as an ISO-8601 string.
+    pub executed_at: String,
+}
+
+/// The report of an apply operation.
+#[derive(Debug)]
+pub struct ApplyReport {
+    /// Names of the migrations applied, in execution order.
+    pub applied_migrations: Vec<String>,
+    /// Number of schema files processed.
+    pub schema_files_count: usize,
+    /// Whether the schema/event definitions changed since the previous apply
+    /// or at least one migration was applied.
+    pub has_changes: bool,
+}
+
+/// A migration whose file content changed since it was applied.
+#[derive(Debug)]
+pub struct ChecksumMismatch {
+    /// Name of the migration.
+    pub script_name: String,
+    /// Checksum stored when the migration was applied.
+    pub expected_checksum: String,
+    /// Checksum of the current migration file, `None` if the file no longer exists.
+    pub actual_checksum: Option<String>,
+}
+
+/// The progress of a migration during an apply operation.
+#[derive(Debug, Clone)]
+pub struct MigrationProgress {
+    /// Name of the migration.
+    pub name: String,
+    /// Index of the migration among the migrations to apply, starting at 0.
+    pub index: usize,
+    /// Number of migrations to apply.
+    pub total: usize,
+    /// Whether the migration is about to be executed, is being executed or has been executed.
+    pub status: MigrationProgressStatus,
+}
+
+/// The status of a migration reported by [`MigrationProgress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationProgressStatus {
+    /// The migration is about to be executed.
+    Started,
+    /// A statement of the migration has been executed, successfully or not,
+    /// reported when `statement_progress` is enabled for migrations applied without a transaction.
+    StatementExecuted {
+        /// Index of the statement in the migration, starting at 0.
+        index: usize,
+        /// Number of statements of the migration.
+        total: usize,
+    },
+    /// The migration has been executed.
+    Completed,
+}
+
+/// The status of every migration, applied or pending.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MigrationStatus {
+    /// Every migration file, sorted by version.
+    pub migrations: Vec<MigrationStatusEntry>,
+    /// Migrations applied whose file no longer exists.
+    pub orphaned: Vec<ScriptMigration>,
+}
+
+/// The number of migrations applied and pending, without their records nor their content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MigrationCounts {
+    /// Number of migrations applied, including the ones whose file no longer exists.
+    pub applied: usize,
+    /// Number of migration files not applied yet.
+    pub pending: usize,
+    /// Number of migration files.
+    pub total: usize,
+}
+
+/// The status of a migration file.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MigrationStatusEntry {
+    /// Name of the migration.
+    pub name: String,
+    /// Whether the migration has been applied.
+    pub is_applied: bool,
+    /// Execution date of the migration as an ISO-8601 string, if applied.
+    pub executed_at: Option<String>,
+}
+
+/// The migrations as a graph, ordered by version, where each migration depends on the previous one.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MigrationGraph {
+    /// Every migration file and every migration applied whose file no longer exists, sorted by version.
+    pub nodes: Vec<MigrationNode>,
+}
+
+/// A migration of a [`MigrationGraph`].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MigrationNode {
+    /// Name of the migration.
+    pub name: String,
+    /// Version prefixing the name of the migration, as written in its name.
+    pub version: String,
+    /// Whether the migration has been applied.
+    pub is_applied: bool,
+    /// Execution date of the migration as an ISO-8601 string, if applied.
+    pub executed_at: Option<String>,
+    /// Checksum of the current migration file, `None` if the file no longer exists.
+    pub checksum: Option<String>,
+    /// Checksum stored when the migration was applied, if applied by a version storing it.
+    pub applied_checksum: Option<String>,
+    /// Name of the migration of the previous version, `None` for the first migration.
+    pub depends_on: Option<String>,
+}
+
+/// The differences between the definitions of the schema/event files and the ones of the database.
+#[derive(Debug)]
+pub struct SchemaDiff {
+    /// Definitions of the files that are missing from the database.
+    pub added: Vec<SchemaDefinition>,
+    /// Definitions of the database that are missing from the files.
+    pub removed: Vec<SchemaDefinition>,
+    /// Definitions that differ between the files and the database.
+    pub changed: Vec<SchemaDefinitionChange>,
+}
+
+impl SchemaDiff {
+    /// Whether the files and the database define the same schema.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The kind of a definition compared by [`SchemaDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SchemaDefinitionKind {
+    Table,
+    Field,
+    Index,
+    Event,
+}
+
+/// A table, field, index or event definition.
+#[derive(Debug)]
+pub struct SchemaDefinition {
+    /// Kind of the definition.
+    pub kind: SchemaDefinitionKind,
+    /// Name of the table the definition belongs to.
+    pub table: String,
+    /// Name of the definition, the same as the table name for a table definition.
+    pub name: String,
+    /// The `DEFINE` statement.
+    pub definition: String,
+}
+
+/// A definition that differs between the files and the database.
+#[derive(Debug)]
+pub struct SchemaDefinitionChange {
+    /// Kind of the definition.
+    pub kind: SchemaDefinitionKind,
+    /// Name of the table the definition belongs to.
+    pub table: String,
+    /// Name of the definition, the same as the table name for a table definition.
+    pub name: String,
+    /// The `DEFINE` statement of the files.
+    pub file_definition: String,
+    /// The `DEFINE` statement of the database.
+    pub db_definition: String,
+}
+
+/// The connection details effectively used, after applying the project config file and the default values.
+/// Secrets are redacted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedConfig {
+    /// Url of the SurrealDB instance, with its scheme.
+    pub url: String,
+    /// Namespace used.
+    pub ns: String,
+    /// Database used.
+    pub db: String,
+    /// Level of the credentials, when signing in with a username and a password.
+    pub auth_level: Option<AuthLevel>,
+    /// Username, when signing in with a username and a password.
+    pub username: Option<String>,
+    /// Redacted password, when signing in with a username and a password.
+    pub password: Option<String>,
+    /// Redacted token, when authenticating with a token.
+    pub token: Option<String>,
+    /// Name of the scope, when signing in as a record user.
+    pub scope: Option<String>,
+    /// Name of the table where applied migrations are recorded.
+    pub migrations_table: String,
+}
+
+/// The result of the checks run by `doctor()`, in order.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DiagnosticReport {
+    /// Every check, whether it passed or failed.
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticReport {
+    /// Whether every check passed.
+    pub fn is_healthy(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// The checks that failed.
+    pub fn failed_checks(&self) -> Vec<&DiagnosticCheck> {
+        self.checks.iter().filter(|check| !check.passed).collect()
+    }
+}
+
+/// A check run by `doctor()`.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DiagnosticCheck {
+    /// What is checked.
+    pub kind: DiagnosticCheckKind,
+    /// Whether the check passed.
+    pub passed: bool,
+    /// What is wrong, like the names of the migrations at fault, empty if the check passed.
+    pub details: Vec<String>,
+}
+
+/// What a [`DiagnosticCheck`] checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum DiagnosticCheckKind {
+    /// The SurrealDB instance can be connected to.
+    Connection,
+    /// The schemas and migrations folders exist.
+    Folders,
+    /// The migrations table exists, with the configured fields when it is schemafull.
+    MigrationsTable,
+    /// Every migration applied still has its file.
+    OrphanedMigrations,
+    /// No applied migration has been modified since.
+    ChecksumMismatches,
+    /// No migration is pending while later migrations have been applied.
+    VersionOrder,
 }
@@ -0,0 +1,78 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{anyhow, Result};
+
+use crate::{
+    io::{self, FilesLocation, SurqlFile},
+    surrealdb::{self, MigrationsTable},
+};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<Vec<String>> {
+    let pending_migrations = list_pending_migrations(client, migrations_table, location).await?;
+
+    let pending_migrations = pending_migrations
+        .into_iter()
+        .map(|migration_file| migration_file.name)
+        .collect();
+
+    Ok(pending_migrations)
+}
+
+pub async fn main_with_content(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<Vec<(String, String)>> {
+    let pending_migrations = list_pending_migrations(client, migrations_table, location).await?;
+
+    pending_migrations
+        .into_iter()
+        .map(|migration_file| {
+            let content = migration_file.get_content()?;
+            Ok((migration_file.name, content))
+        })
+        .collect()
+}
+
+async fn list_pending_migrations(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<Vec<SurqlFile>> {
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    let migrations_applied_without_file = migrations_applied
+        .iter()
+        .filter(|migration_applied| {
+            !migrations_files
+                .iter()
+                .any(|migration_file| migration_file.name == migration_applied.script_name)
+        })
+        .map(|migration_applied| migration_applied.script_name.to_string())
+        .collect::<Vec<_>>();
+
+    if !migrations_applied_without_file.is_empty() {
+        return Err(anyhow!(
+            "The following migrations have been applied but their file is missing: {}",
+            migrations_applied_without_file.join(", ")
+        ));
+    }
+
+    let pending_migrations = migrations_files
+        .into_iter()
+        .filter(|migration_file| {
+            !migrations_applied
+                .iter()
+                .any(|migration_applied| migration_applied.script_name == migration_file.name)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(pending_migrations)
+}
@@ -0,0 +1,83 @@
+use anyhow::{anyhow, Context, Result};
+
+use crate::{
+    apply::{self, ApplyArgs, ApplyOperation},
+    down::{self, DownArgs, DownOperation},
+    input::ConcurrencyControl,
+    io, lock, schema_version, surrealdb,
+};
+
+/// Revert the last migration applied, and apply it again with the given arguments,
+/// whose operation is replaced by the application up to this migration.
+pub async fn main(apply_args: ApplyArgs<'_>) -> Result<()> {
+    let client = apply_args.client;
+    let migrations_table = apply_args.migrations_table;
+
+    // The same lock is held while reverting and applying again,
+    // so that no other run can apply migrations in between
+    match apply_args.concurrency_control {
+        ConcurrencyControl::Lock => {
+            let owner = lock::acquire(
+                client,
+                migrations_table,
+                apply_args.lock_timeout_ms,
+                apply_args.lock_ttl_ms,
+            )
+            .await?;
+            let result = redo(apply_args).await;
+            let release_result = lock::release(client, migrations_table, &owner).await;
+
+            result?;
+            release_result?;
+
+            Ok(())
+        }
+        ConcurrencyControl::VersionCheck => {
+            let schema_version = schema_version::get(client, migrations_table).await?;
+            redo(apply_args).await?;
+            schema_version::increment(client, migrations_table, schema_version).await?;
+
+            Ok(())
+        }
+    }
+}
+
+async fn redo(apply_args: ApplyArgs<'_>) -> Result<()> {
+    let migrations_applied = surrealdb::list_script_migration_ordered_by_execution_date(
+        apply_args.client,
+        apply_args.migrations_table,
+    )
+    .await?;
+
+    let last_migration_applied = migrations_applied
+        .last()
+        .context("Cannot redo migration: no migration has been applied yet")?;
+    let name = &last_migration_applied.script_name;
+
+    if io::get_down_migration_content(&apply_args.location, name)?.is_none() {
+        return Err(anyhow!(
+            "Cannot redo migration '{}': it has no down script to revert it first",
+            name
+        ));
+    }
+
+    let down_args = DownArgs {
+        operation: DownOperation::Last,
+        client: apply_args.client,
+        migrations_table: apply_args.migrations_table,
+        location: apply_args.location.to_owned(),
+        dry_run: false,
+        clock: apply_args.clock,
+        lock_timeout_ms: apply_args.lock_timeout_ms,
+        lock_ttl_ms: apply_args.lock_ttl_ms,
+    };
+    down::revert(down_args).await?;
+
+    let apply_args = ApplyArgs {
+        operation: ApplyOperation::UpTo(name.to_string()),
+        ..apply_args
+    };
+    apply::apply(apply_args).await?;
+
+    Ok(())
+}
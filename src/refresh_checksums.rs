@@ -0,0 +1,68 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+
+use crate::{
+    checksum,
+    io::{self, FilesLocation},
+    lock,
+    surrealdb::{self, MigrationsTable, TransactionAction},
+};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+    lock_timeout_ms: Option<u64>,
+    lock_ttl_ms: Option<u64>,
+) -> Result<()> {
+    let owner = lock::acquire(client, migrations_table, lock_timeout_ms, lock_ttl_ms).await?;
+    let result = refresh_checksums(client, migrations_table, location).await;
+    let release_result = lock::release(client, migrations_table, &owner).await;
+
+    result?;
+    release_result?;
+
+    Ok(())
+}
+
+async fn refresh_checksums(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<()> {
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    let mut queries = Vec::new();
+
+    for migration_applied in migrations_applied {
+        let migration_file = migrations_files
+            .iter()
+            .find(|migration_file| migration_file.name == migration_applied.script_name);
+
+        // The checksum of a migration whose file has been removed cannot be computed
+        let Some(migration_file) = migration_file else {
+            continue;
+        };
+
+        let checksum = checksum::compute_checksum(&migration_file.get_content()?);
+
+        if migration_applied.checksum.as_ref() == Some(&checksum) {
+            continue;
+        }
+
+        queries.push(format!(
+            "UPDATE {} SET checksum = '{}' WHERE {} = '{}';",
+            migrations_table, checksum, migrations_table.script_name, migration_applied.script_name
+        ));
+    }
+
+    if queries.is_empty() {
+        return Ok(());
+    }
+
+    surrealdb::apply_in_transaction(client, &queries.join("\n"), TransactionAction::Commit).await
+}
@@ -2,7 +2,10 @@ use anyhow::{anyhow, Context, Result};
 use fs_extra::dir::{DirEntryAttr, DirEntryValue};
 use std::{collections::HashSet, path::Path};
 
-use crate::{config, constants::MIGRATIONS_DIR_NAME};
+use crate::{
+    config,
+    constants::{DOWN_MIGRATION_SUFFIX, MIGRATIONS_DIR_NAME, UP_MIGRATION_SUFFIX},
+};
 
 pub fn main() -> Result<()> {
     let folder_path = config::retrieve_folder_path();
@@ -23,7 +26,16 @@ pub fn main() -> Result<()> {
         return Err(anyhow!("No migration files left"));
     }
 
-    let mut sorted_migrations_files = migrations_files.items.iter().collect::<Vec<_>>();
+    let mut sorted_migrations_files = migrations_files
+        .items
+        .iter()
+        .filter(
+            |migration_file| match migration_file.get(&DirEntryAttr::Name) {
+                Some(DirEntryValue::String(name)) => !name.ends_with(DOWN_MIGRATION_SUFFIX),
+                _ => true,
+            },
+        )
+        .collect::<Vec<_>>();
     sorted_migrations_files.sort_by(|a, b| {
         let a = a.get(&DirEntryAttr::Name);
         let b = b.get(&DirEntryAttr::Name);
@@ -55,7 +67,11 @@ pub fn main() -> Result<()> {
         _ => Err(anyhow!("Cannot get name to migration files")),
     }?;
 
-    let last_migration_display_name = last_migration_filename
+    let last_migration_name = last_migration_filename
+        .strip_suffix(UP_MIGRATION_SUFFIX)
+        .unwrap_or(last_migration_filename);
+
+    let last_migration_display_name = last_migration_name
         .split("_")
         .skip(2)
         .map(|s| s.to_string())
@@ -66,10 +82,20 @@ pub fn main() -> Result<()> {
     let migration_file = migrations_path.join(last_migration_fullname);
     std::fs::remove_file(migration_file)?;
 
+    // Remove down migration file if exists
+    let down_migration_file = migrations_path.join(format!(
+        "{}{}.surql",
+        last_migration_name, DOWN_MIGRATION_SUFFIX
+    ));
+
+    if down_migration_file.exists() {
+        std::fs::remove_file(down_migration_file)?;
+    }
+
     // Remove definition file if exists
     let migration_definition_file_path = Path::new(&migrations_path)
         .join("definitions")
-        .join(format!("{}.json", last_migration_filename));
+        .join(format!("{}.json", last_migration_name));
 
     if migration_definition_file_path.exists() {
         std::fs::remove_file(migration_definition_file_path)?;
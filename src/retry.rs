@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use crate::error::MigrationError;
+
+/// A function telling whether an error is transient, so that the failing migration can be retried.
+pub type TransientErrorClassifier = dyn Fn(&anyhow::Error) -> bool + Send + Sync;
+
+/// Messages of the SurrealDB errors treated as transient, matched case-insensitively in the error chain.
+pub const TRANSIENT_ERROR_SIGNATURES: [&str; 5] = [
+    "failed to commit transaction due to a read or write conflict",
+    "this transaction can be retried",
+    "there was an error when starting a new datastore transaction",
+    "resource busy",
+    "deadlock",
+];
+
+/// How a migration failing with a transient error is retried.
+#[derive(Clone, Copy)]
+pub struct MigrationRetry<'a> {
+    /// Number of times a migration is retried.
+    pub retries: u32,
+    /// Delay before the first retry, doubled after each retry.
+    pub delay: Duration,
+    /// Classifier of the errors transient in addition to the known ones.
+    pub classifier: Option<&'a TransientErrorClassifier>,
+}
+
+impl MigrationRetry<'_> {
+    pub fn new(
+        retries: Option<u32>,
+        delay_ms: Option<u64>,
+        classifier: Option<&TransientErrorClassifier>,
+    ) -> MigrationRetry<'_> {
+        MigrationRetry {
+            retries: retries.unwrap_or(0),
+            delay: Duration::from_millis(delay_ms.unwrap_or(100)),
+            classifier,
+        }
+    }
+
+    /// Check if the migration can be retried after the given number of attempts failing with this error.
+    pub fn should_retry(&self, error: &anyhow::Error, attempts: u32) -> bool {
+        attempts <= self.retries && self.is_transient(error)
+    }
+
+    /// Get the delay before the next attempt, after the given number of attempts.
+    pub fn get_delay(&self, attempts: u32) -> Duration {
+        self.delay
+            .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+    }
+
+    /// Check if an error is transient, using the known signatures then the classifier.
+    /// Errors raised by the crate itself, like a partially applied migration, are never transient.
+    fn is_transient(&self, error: &anyhow::Error) -> bool {
+        if error.downcast_ref::<MigrationError>().is_some() {
+            return false;
+        }
+
+        is_known_transient_error(error)
+            || self.classifier.is_some_and(|classifier| classifier(error))
+    }
+}
+
+fn is_known_transient_error(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error).to_lowercase();
+
+    TRANSIENT_ERROR_SIGNATURES
+        .iter()
+        .any(|signature| message.contains(signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn should_retry_known_transient_errors() {
+        let retry = MigrationRetry::new(Some(2), None, None);
+
+        let error = anyhow!("Failed to commit transaction due to a read or write conflict. This transaction can be retried");
+        assert!(retry.should_retry(&error, 1));
+        assert!(retry.should_retry(&error, 2));
+        assert!(!retry.should_retry(&error, 3));
+
+        let error = anyhow!("Database record `post:failing` already exists");
+        assert!(!retry.should_retry(&error, 1));
+    }
+
+    #[test]
+    fn should_retry_errors_of_the_classifier() {
+        let classifier = |error: &anyhow::Error| {
+            error
+                .to_string()
+                .to_lowercase()
+                .contains("lock wait timeout")
+        };
+        let retry = MigrationRetry::new(Some(1), None, Some(&classifier));
+
+        assert!(retry.should_retry(&anyhow!("Lock wait timeout exceeded"), 1));
+        assert!(retry.should_retry(&anyhow!("Deadlock detected"), 1));
+        assert!(!retry.should_retry(&anyhow!("Parse error"), 1));
+    }
+
+    #[test]
+    fn should_not_retry_errors_of_the_crate() {
+        let retry = MigrationRetry::new(Some(1), None, Some(&|_: &anyhow::Error| true));
+        let error = MigrationError::Timeout {
+            name: "deadlock".to_string(),
+        };
+
+        assert!(!retry.should_retry(&error.into(), 1));
+    }
+
+    #[test]
+    fn get_delay_should_double_after_each_retry() {
+        let retry = MigrationRetry::new(Some(3), Some(50), None);
+
+        assert_eq!(retry.get_delay(1), Duration::from_millis(50));
+        assert_eq!(retry.get_delay(3), Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,16 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::surrealdb;
+
+pub async fn main(client: &Surreal<Any>, path: &Path) -> Result<()> {
+    let query = fs_extra::file::read_to_string(path).context(format!(
+        "Cannot read content of the script file '{}'",
+        path.display()
+    ))?;
+
+    surrealdb::apply_query(client, &query).await?;
+
+    Ok(())
+}
@@ -43,7 +43,7 @@ pub fn concat_path(folder_path: &Option<String>, dir_name: &str) -> PathBuf {
     }
 }
 
-fn fails_if_folder_already_exists(dir_path: &PathBuf, dir_name: &str) -> Result<()> {
+fn fails_if_folder_already_exists(dir_path: &Path, dir_name: &str) -> Result<()> {
     match dir_path.exists() {
         true => Err(anyhow!("'{}' folder already exists.", dir_name)),
         false => Ok(()),
@@ -111,7 +111,7 @@ pub fn extract<S: AsRef<Path>>(dir: &Dir<'_>, path: S) -> std::io::Result<()> {
 
 fn ensures_folder_exists(dir_path: &PathBuf) -> Result<()> {
     if !dir_path.exists() {
-        fs_extra::dir::create_all(&dir_path, false)?;
+        fs_extra::dir::create_all(dir_path, false)?;
     }
 
     Ok(())
@@ -121,7 +121,7 @@ fn rename_migrations_files_to_match_current_date(migrations_dir_path: &PathBuf)
     let now = chrono::Local::now();
     let regex = regex::Regex::new(r"^YYYYMMDD_HHMM(\d{2})_")?;
 
-    let migrations_dir = std::fs::read_dir(&migrations_dir_path)?;
+    let migrations_dir = std::fs::read_dir(migrations_dir_path)?;
 
     let migration_filenames_to_rename = migrations_dir
         .filter_map(|entry| match entry {
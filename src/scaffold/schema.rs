@@ -79,7 +79,7 @@ fn scaffold_from_schema(
 
     let schema = convert_ast_to_surrealdb_schema(ast, preserve_casing)?;
 
-    if schema.tables.len() <= 0 {
+    if schema.tables.is_empty() {
         return Err(anyhow!("No table found in schema file."));
     }
 
@@ -205,59 +205,57 @@ fn convert_ast_to_surrealdb_schema(
 
                     // Detect record type from foreign key (if any)
                     for constraint in &constraints {
-                        match constraint {
-                            sqlparser::ast::TableConstraint::ForeignKey {
-                                columns,
-                                foreign_table,
-                                referred_columns,
-                                ..
-                            } => {
-                                if columns.len() != 1 {
-                                    continue;
-                                }
-
-                                if referred_columns.len() != 1 {
-                                    continue;
-                                }
+                        if let sqlparser::ast::TableConstraint::ForeignKey {
+                            columns,
+                            foreign_table,
+                            referred_columns,
+                            ..
+                        } = constraint
+                        {
+                            if columns.len() != 1 {
+                                continue;
+                            }
 
-                                let column_identifier = columns.first().unwrap().value.to_string();
-                                let column_identifier = match preserve_casing {
-                                    true => column_identifier,
-                                    false => column_identifier.to_case(Case::Snake),
-                                };
+                            if referred_columns.len() != 1 {
+                                continue;
+                            }
 
-                                if field_name != column_identifier {
-                                    continue;
-                                }
+                            let column_identifier = columns.first().unwrap().value.to_string();
+                            let column_identifier = match preserve_casing {
+                                true => column_identifier,
+                                false => column_identifier.to_case(Case::Snake),
+                            };
 
-                                let referred_column =
-                                    referred_columns.first().unwrap().value.to_string();
+                            if field_name != column_identifier {
+                                continue;
+                            }
 
-                                if referred_column.to_lowercase() != "id" {
-                                    continue;
-                                }
+                            let referred_column =
+                                referred_columns.first().unwrap().value.to_string();
 
-                                let foreign_table = foreign_table.0.first();
-                                if foreign_table.is_none() {
-                                    continue;
-                                }
+                            if referred_column.to_lowercase() != "id" {
+                                continue;
+                            }
 
-                                let foreign_table = foreign_table.unwrap().value.to_string();
-                                let foreign_table = match preserve_casing {
-                                    true => foreign_table,
-                                    false => foreign_table.to_case(Case::Snake),
-                                };
-
-                                field_type = match field_type {
-                                    Some(SurrealdbFieldType::Record(tables)) => {
-                                        Some(SurrealdbFieldType::Record(
-                                            tables.into_iter().chain(vec![foreign_table]).collect(),
-                                        ))
-                                    }
-                                    _ => Some(SurrealdbFieldType::Record(vec![foreign_table])),
-                                };
+                            let foreign_table = foreign_table.0.first();
+                            if foreign_table.is_none() {
+                                continue;
                             }
-                            _ => {}
+
+                            let foreign_table = foreign_table.unwrap().value.to_string();
+                            let foreign_table = match preserve_casing {
+                                true => foreign_table,
+                                false => foreign_table.to_case(Case::Snake),
+                            };
+
+                            field_type = match field_type {
+                                Some(SurrealdbFieldType::Record(tables)) => {
+                                    Some(SurrealdbFieldType::Record(
+                                        tables.into_iter().chain(vec![foreign_table]).collect(),
+                                    ))
+                                }
+                                _ => Some(SurrealdbFieldType::Record(vec![foreign_table])),
+                            };
                         }
                     }
 
@@ -265,11 +263,8 @@ fn convert_ast_to_surrealdb_schema(
 
                     // Detect unique constraints
                     for column_option in &column.options {
-                        match column_option.option {
-                            sqlparser::ast::ColumnOption::NotNull => {
-                                is_not_null = true;
-                            }
-                            _ => {}
+                        if column_option.option == sqlparser::ast::ColumnOption::NotNull {
+                            is_not_null = true;
                         }
                     }
 
@@ -286,24 +281,23 @@ fn convert_ast_to_surrealdb_schema(
                         let option_name = &column_option.name;
                         let index_name = match option_name {
                             Some(name) => name.value.to_string(),
-                            None => format!("{}_{}_index", table_name, field_name.to_string()),
+                            None => format!("{}_{}_index", table_name, field_name),
                         };
 
-                        match column_option.option {
-                            sqlparser::ast::ColumnOption::Unique { is_primary } => {
-                                if !is_primary {
-                                    let line_definition: SurrealdbSchemaLineDefinition =
-                                        SurrealdbSchemaLineDefinition::Index(
-                                            SurrealdbSchemaIndexDefinition {
-                                                name: index_name,
-                                                field_names: vec![field_name.to_string()],
-                                                unique: true,
-                                            },
-                                        );
-                                    line_definitions.push(line_definition);
-                                }
+                        if let sqlparser::ast::ColumnOption::Unique { is_primary } =
+                            column_option.option
+                        {
+                            if !is_primary {
+                                let line_definition: SurrealdbSchemaLineDefinition =
+                                    SurrealdbSchemaLineDefinition::Index(
+                                        SurrealdbSchemaIndexDefinition {
+                                            name: index_name,
+                                            field_names: vec![field_name.to_string()],
+                                            unique: true,
+                                        },
+                                    );
+                                line_definitions.push(line_definition);
                             }
-                            _ => {}
                         }
                     }
                 }
@@ -354,9 +348,7 @@ fn convert_ast_to_surrealdb_schema(
                         unique,
                     });
 
-                let line_definitions = tables
-                    .entry(table_name)
-                    .or_insert(SurrealdbSchemaDefinition::new());
+                let line_definitions = tables.entry(table_name).or_default();
                 line_definitions.push(line_definition);
             }
             _ => {}
@@ -401,13 +393,13 @@ fn detect_field_type(column: &sqlparser::ast::ColumnDef) -> Option<SurrealdbFiel
         sqlparser::ast::DataType::Time { .. } => Some(SurrealdbFieldType::DateTime),
         sqlparser::ast::DataType::Datetime(_) => Some(SurrealdbFieldType::DateTime),
         sqlparser::ast::DataType::Timestamp { .. } => Some(SurrealdbFieldType::DateTime),
-        sqlparser::ast::DataType::Interval { .. } => Some(SurrealdbFieldType::Duration),
+        sqlparser::ast::DataType::Interval => Some(SurrealdbFieldType::Duration),
         sqlparser::ast::DataType::JSON => Some(SurrealdbFieldType::Object),
         sqlparser::ast::DataType::Array(_) => Some(SurrealdbFieldType::Array),
         sqlparser::ast::DataType::Custom(sqlparser::ast::ObjectName(identifiers), _) => {
             if let Some(first_identifier) = identifiers.first() {
                 // 💡 MSSQL type for boolean
-                if first_identifier.value.to_string() == "BIT" {
+                if first_identifier.value == "BIT" {
                     Some(SurrealdbFieldType::Boolean)
                 } else {
                     None
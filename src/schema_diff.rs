@@ -0,0 +1,181 @@
+use ::surrealdb::{
+    engine::any::Any,
+    sql::{self, statements::DefineStatement, Statement},
+    Surreal,
+};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    io::{self, FilesLocation},
+    models::{SchemaDefinition, SchemaDefinitionChange, SchemaDefinitionKind, SchemaDiff},
+    surrealdb::MigrationsTable,
+};
+
+/// Definitions indexed by kind, table name and definition name.
+pub type Definitions = BTreeMap<(SchemaDefinitionKind, String, String), String>;
+
+#[derive(Deserialize)]
+struct DatabaseInfo {
+    tb: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct TableInfo {
+    fd: HashMap<String, String>,
+    ix: HashMap<String, String>,
+    ev: HashMap<String, String>,
+}
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<SchemaDiff> {
+    let file_definitions = extract_file_definitions(location)?;
+    let mut db_definitions = list_db_definitions(client, migrations_table).await?;
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+
+    for ((kind, table, name), file_definition) in file_definitions {
+        match db_definitions.remove(&(kind, table.to_string(), name.to_string())) {
+            None => added.push(SchemaDefinition {
+                kind,
+                table,
+                name,
+                definition: file_definition,
+            }),
+            Some(db_definition) if db_definition != file_definition => {
+                changed.push(SchemaDefinitionChange {
+                    kind,
+                    table,
+                    name,
+                    file_definition,
+                    db_definition,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    let removed = db_definitions
+        .into_iter()
+        .map(|((kind, table, name), definition)| SchemaDefinition {
+            kind,
+            table,
+            name,
+            definition,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(SchemaDiff {
+        added,
+        removed,
+        changed,
+    })
+}
+
+/// Parse the schema/event files, so that their definitions are formatted like the ones of the database.
+fn extract_file_definitions(location: &FilesLocation) -> Result<Definitions> {
+    let mut definitions = Definitions::new();
+
+    for dir_name in [&location.dir_names.schemas, &location.dir_names.events] {
+        if !io::definition_folder_exists(location, dir_name)? {
+            continue;
+        }
+
+        for file in io::extract_definition_files(location, dir_name)? {
+            let query = sql::parse(&file.get_content()?).context(format!(
+                "Cannot parse the definition file '{}'",
+                file.path.display()
+            ))?;
+
+            for statement in query.0 .0 {
+                let Statement::Define(statement) = statement else {
+                    continue;
+                };
+
+                let (kind, table, name) = match &statement {
+                    DefineStatement::Table(table) => (
+                        SchemaDefinitionKind::Table,
+                        table.name.to_string(),
+                        table.name.to_string(),
+                    ),
+                    DefineStatement::Field(field) => (
+                        SchemaDefinitionKind::Field,
+                        field.what.to_string(),
+                        field.name.to_string(),
+                    ),
+                    DefineStatement::Index(index) => (
+                        SchemaDefinitionKind::Index,
+                        index.what.to_string(),
+                        index.name.to_string(),
+                    ),
+                    DefineStatement::Event(event) => (
+                        SchemaDefinitionKind::Event,
+                        event.what.to_string(),
+                        event.name.to_string(),
+                    ),
+                    _ => continue,
+                };
+
+                definitions.insert((kind, table, name), statement.to_string());
+            }
+        }
+    }
+
+    Ok(definitions)
+}
+
+pub async fn list_db_definitions(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<Definitions> {
+    // Tables used internally by the migrations tool, which are never defined in the files
+    let internal_tables = [
+        migrations_table.lock_table(),
+        migrations_table.definition_checksums_table(),
+        migrations_table.schema_version_table(),
+        migrations_table.history_table(),
+    ];
+
+    let mut response = client.query("INFO FOR DB;").await?;
+    let database_info: Option<DatabaseInfo> = response.take(0)?;
+    let database_info = database_info.context("Cannot get the definitions of the database")?;
+
+    let mut definitions = Definitions::new();
+
+    for (table, table_definition) in database_info.tb {
+        if internal_tables.contains(&table) {
+            continue;
+        }
+
+        let mut response = client.query(format!("INFO FOR TABLE {};", table)).await?;
+        let table_info: Option<TableInfo> = response.take(0)?;
+        let table_info = table_info.context(format!(
+            "Cannot get the definitions of the table '{}'",
+            table
+        ))?;
+
+        let table_definitions = [
+            (SchemaDefinitionKind::Field, table_info.fd),
+            (SchemaDefinitionKind::Index, table_info.ix),
+            (SchemaDefinitionKind::Event, table_info.ev),
+        ];
+
+        for (kind, kind_definitions) in table_definitions {
+            for (name, definition) in kind_definitions {
+                definitions.insert((kind, table.to_string(), name), definition);
+            }
+        }
+
+        definitions.insert(
+            (SchemaDefinitionKind::Table, table.to_string(), table),
+            table_definition,
+        );
+    }
+
+    Ok(definitions)
+}
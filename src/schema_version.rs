@@ -0,0 +1,70 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::{error::MigrationError, surrealdb::MigrationsTable};
+
+const SCHEMA_VERSION_ID: &str = "current";
+
+#[derive(Deserialize)]
+struct SchemaVersion {
+    version: u64,
+}
+
+/// Get the current schema version, which is `0` until migrations are applied with a version check.
+pub async fn get(client: &Surreal<Any>, migrations_table: MigrationsTable<'_>) -> Result<u64> {
+    let schema_version: Option<SchemaVersion> = client
+        .select((
+            migrations_table.schema_version_table().as_str(),
+            SCHEMA_VERSION_ID,
+        ))
+        .await?;
+
+    Ok(schema_version
+        .map(|schema_version| schema_version.version)
+        .unwrap_or_default())
+}
+
+/// Increment the schema version, only if it is still the one read before applying migrations.
+/// Returns the new schema version.
+pub async fn increment(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    expected_version: u64,
+) -> Result<u64> {
+    let new_version = expected_version + 1;
+    let schema_version_table = migrations_table.schema_version_table();
+
+    let query = match expected_version {
+        // Creating the record fails if another process has created it in the meantime
+        0 => format!(
+            "CREATE {}:{} SET version = {}, updated_at = time::now();",
+            schema_version_table, SCHEMA_VERSION_ID, new_version
+        ),
+        _ => format!(
+            "UPDATE {}:{} SET version = {}, updated_at = time::now() WHERE version = {};",
+            schema_version_table, SCHEMA_VERSION_ID, new_version, expected_version
+        ),
+    };
+
+    let mut response = client.query(query).await?;
+    let updated: Result<Vec<SchemaVersion>, _> = response.take(0);
+
+    if updated.as_ref().is_ok_and(|updated| !updated.is_empty()) {
+        return Ok(new_version);
+    }
+
+    let actual_version = get(client, migrations_table).await?;
+
+    if actual_version != expected_version {
+        return Err(MigrationError::ConcurrentModification {
+            expected_version,
+            actual_version,
+        }
+        .into());
+    }
+
+    updated?;
+
+    Err(anyhow!("Cannot update the schema version"))
+}
@@ -0,0 +1,29 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{Context, Result};
+
+use crate::{
+    io::{self, FilesLocation},
+    surrealdb,
+};
+
+/// Run the seed files in name order. Seeds are not recorded, so they are run again every time.
+pub async fn main(client: &Surreal<Any>, location: &FilesLocation) -> Result<()> {
+    let seeds_dir_name = &location.dir_names.seeds;
+
+    if !io::definition_folder_exists(location, seeds_dir_name)? {
+        return Ok(());
+    }
+
+    let mut seed_files = io::extract_definition_files(location, seeds_dir_name)?;
+    seed_files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    for seed_file in seed_files {
+        let query = seed_file.get_content()?;
+
+        surrealdb::apply_query(client, &query)
+            .await
+            .context(format!("Cannot run seed file '{}'", seed_file.name))?;
+    }
+
+    Ok(())
+}
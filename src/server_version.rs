@@ -0,0 +1,45 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{Context, Result};
+use semver::{BuildMetadata, Version};
+
+use crate::error::MigrationError;
+
+/// Get the version of the SurrealDB server, like `1.0.0-beta.9+20230402`.
+pub async fn get(client: &Surreal<Any>) -> Result<Version> {
+    let version = client
+        .version()
+        .await
+        .context("Cannot get the version of the SurrealDB server")?;
+
+    Ok(version)
+}
+
+/// Fail if the version of the SurrealDB server is older than the minimum version.
+/// Build metadata is ignored, while pre-releases are older than their release.
+pub async fn check_min_version(client: &Surreal<Any>, min_version: &str) -> Result<()> {
+    let parsed_min_version = Version::parse(min_version).context(format!(
+        "Invalid minimum SurrealDB server version '{}'",
+        min_version
+    ))?;
+
+    let version = get(client).await?;
+
+    let version_without_build = Version {
+        build: BuildMetadata::EMPTY,
+        ..version.clone()
+    };
+    let min_version_without_build = Version {
+        build: BuildMetadata::EMPTY,
+        ..parsed_min_version
+    };
+
+    if version_without_build < min_version_without_build {
+        return Err(MigrationError::ServerVersionTooOld {
+            version: version.to_string(),
+            min_version: min_version.to_string(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,361 @@
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use std::{io::ErrorKind, path::PathBuf};
+
+use crate::io::{DirNames, SourceFiles};
+
+/// A source the schema, event, migration and seed files are read from, instead of the local disk.
+///
+/// Each folder (`schemas`, `events`, `migrations` and `seeds`, or their configured names)
+/// is listed then its `.surql` files are read, once per operation.
+///
+/// ## Examples
+///
+/// ```rust
+/// use futures::future::{BoxFuture, FutureExt};
+/// use std::collections::HashMap;
+/// use surrealdb_migrations::MigrationSource;
+///
+/// /// Migration files kept in memory, by folder name then file name.
+/// struct InMemorySource(HashMap<String, HashMap<String, String>>);
+///
+/// impl MigrationSource for InMemorySource {
+///     fn list_files<'a>(&'a self, dir_name: &'a str) -> BoxFuture<'a, anyhow::Result<Vec<String>>> {
+///         let file_names = match self.0.get(dir_name) {
+///             Some(files) => files.keys().cloned().collect(),
+///             None => Vec::new(),
+///         };
+///         async move { Ok(file_names) }.boxed()
+///     }
+///
+///     fn read_file<'a>(
+///         &'a self,
+///         dir_name: &'a str,
+///         file_name: &'a str,
+///     ) -> BoxFuture<'a, anyhow::Result<String>> {
+///         let content = self.0.get(dir_name).and_then(|files| files.get(file_name)).cloned();
+///         async move { content.ok_or_else(|| anyhow::anyhow!("File '{}' not found", file_name)) }
+///             .boxed()
+///     }
+/// }
+/// ```
+pub trait MigrationSource: Send + Sync {
+    /// List the names of the files of a folder, with their extension.
+    /// A folder that does not exist has no files.
+    fn list_files<'a>(&'a self, dir_name: &'a str) -> BoxFuture<'a, Result<Vec<String>>>;
+
+    /// Read the content of a file of a folder.
+    fn read_file<'a>(
+        &'a self,
+        dir_name: &'a str,
+        file_name: &'a str,
+    ) -> BoxFuture<'a, Result<String>>;
+}
+
+/// A migration source reading the files from a root folder of the local disk.
+pub struct FolderSource {
+    path: PathBuf,
+}
+
+impl FolderSource {
+    /// Create a migration source reading the files from the sub-folders of a root folder.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The root folder containing the `schemas`, `events` and `migrations` folders
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FolderSource { path: path.into() }
+    }
+}
+
+impl MigrationSource for FolderSource {
+    fn list_files<'a>(&'a self, dir_name: &'a str) -> BoxFuture<'a, Result<Vec<String>>> {
+        Box::pin(async move {
+            let dir_path = self.path.join(dir_name);
+
+            let mut entries = match tokio::fs::read_dir(&dir_path).await {
+                Ok(entries) => entries,
+                Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(error) => {
+                    return Err(error).context(format!(
+                        "Cannot list the files of the folder '{}'",
+                        dir_path.display()
+                    ))
+                }
+            };
+
+            let mut file_names = Vec::new();
+
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_type().await?.is_file() {
+                    file_names.push(entry.file_name().to_string_lossy().to_string());
+                }
+            }
+
+            Ok(file_names)
+        })
+    }
+
+    fn read_file<'a>(
+        &'a self,
+        dir_name: &'a str,
+        file_name: &'a str,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let file_path = self.path.join(dir_name).join(file_name);
+
+            tokio::fs::read_to_string(&file_path).await.context(format!(
+                "Cannot read content of the file '{}'",
+                file_path.display()
+            ))
+        })
+    }
+}
+
+/// A migration source reading the files from an HTTP server, e.g. an artifact store.
+///
+/// The files of a folder are listed by the `<base_url>/<folder>/index.json` file,
+/// containing the array of their names, a folder without index having no files.
+/// Each file is then read from `<base_url>/<folder>/<file name>`.
+///
+/// Requires the `http-source` feature.
+#[cfg(feature = "http-source")]
+pub struct HttpSource {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "http-source")]
+impl HttpSource {
+    /// Create a migration source reading the files from an HTTP server.
+    ///
+    /// ## Arguments
+    ///
+    /// * `base_url` - The url containing the `schemas`, `events` and `migrations` folders
+    pub fn new(base_url: impl Into<String>) -> Self {
+        HttpSource {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[cfg(feature = "http-source")]
+impl MigrationSource for HttpSource {
+    fn list_files<'a>(&'a self, dir_name: &'a str) -> BoxFuture<'a, Result<Vec<String>>> {
+        Box::pin(async move {
+            let url = format!("{}/{}/index.json", self.base_url, dir_name);
+
+            let response = self
+                .client
+                .get(&url)
+                .send()
+                .await
+                .context(format!("Cannot fetch '{}'", url))?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(Vec::new());
+            }
+
+            let file_names = response
+                .error_for_status()
+                .context(format!("Cannot fetch '{}'", url))?
+                .json::<Vec<String>>()
+                .await
+                .context(format!("Cannot parse '{}' as an array of file names", url))?;
+
+            Ok(file_names)
+        })
+    }
+
+    fn read_file<'a>(
+        &'a self,
+        dir_name: &'a str,
+        file_name: &'a str,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let url = format!("{}/{}/{}", self.base_url, dir_name, file_name);
+
+            let content = self
+                .client
+                .get(&url)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .context(format!("Cannot fetch '{}'", url))?
+                .text()
+                .await
+                .context(format!("Cannot read content of '{}'", url))?;
+
+            Ok(content)
+        })
+    }
+}
+
+/// The `.surql` files of an archive, by folder name then file name.
+#[cfg(feature = "archive-source")]
+type ArchiveFiles = std::collections::HashMap<String, std::collections::HashMap<String, String>>;
+
+/// A migration source reading the files from a gzip-compressed tar archive, like `migrations.tar.gz`,
+/// without extracting it to the disk.
+///
+/// The archive has the same layout as the migrations root folder, each `.surql` file being directly inside
+/// its folder, like `migrations/20230317_153201_AddAdminUser.surql`. An archive containing `.surql` files
+/// elsewhere, or none at all, is rejected. The archive is read once, the first time files are listed.
+///
+/// Requires the `archive-source` feature.
+#[cfg(feature = "archive-source")]
+pub struct ArchiveSource {
+    path: PathBuf,
+    files: tokio::sync::OnceCell<ArchiveFiles>,
+}
+
+#[cfg(feature = "archive-source")]
+impl ArchiveSource {
+    /// Create a migration source reading the files from a gzip-compressed tar archive.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the archive containing the `schemas`, `events` and `migrations` folders
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        ArchiveSource {
+            path: path.into(),
+            files: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    async fn get_files(&self) -> Result<&ArchiveFiles> {
+        self.files
+            .get_or_try_init(|| async {
+                let bytes = tokio::fs::read(&self.path)
+                    .await
+                    .context(format!("Cannot read the archive '{}'", self.path.display()))?;
+
+                extract_archive_files(&bytes)
+                    .context(format!("Invalid archive '{}'", self.path.display()))
+            })
+            .await
+    }
+}
+
+#[cfg(feature = "archive-source")]
+impl MigrationSource for ArchiveSource {
+    fn list_files<'a>(&'a self, dir_name: &'a str) -> BoxFuture<'a, Result<Vec<String>>> {
+        Box::pin(async move {
+            let file_names = match self.get_files().await?.get(dir_name) {
+                Some(files) => files.keys().cloned().collect(),
+                None => Vec::new(),
+            };
+
+            Ok(file_names)
+        })
+    }
+
+    fn read_file<'a>(
+        &'a self,
+        dir_name: &'a str,
+        file_name: &'a str,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            self.get_files()
+                .await?
+                .get(dir_name)
+                .and_then(|files| files.get(file_name))
+                .cloned()
+                .with_context(|| {
+                    format!(
+                        "File '{}/{}' not found in the archive '{}'",
+                        dir_name,
+                        file_name,
+                        self.path.display()
+                    )
+                })
+        })
+    }
+}
+
+/// Decompress a tar.gz archive and read its `.surql` files, checking that each one is directly inside a folder.
+#[cfg(feature = "archive-source")]
+fn extract_archive_files(bytes: &[u8]) -> Result<ArchiveFiles> {
+    use anyhow::anyhow;
+    use std::{io::Read, path::Component};
+
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+    let mut files = ArchiveFiles::new();
+
+    for entry in archive.entries().context("Cannot decompress the archive")? {
+        let mut entry = entry.context("Cannot read an entry of the archive")?;
+
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.to_path_buf();
+
+        if path.extension().and_then(|extension| extension.to_str()) != Some("surql") {
+            continue;
+        }
+
+        let components = path
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect::<Vec<_>>();
+
+        let [Component::Normal(dir_name), Component::Normal(file_name)] = components[..] else {
+            return Err(anyhow!(
+                "The file '{}' is not directly inside a folder, like 'migrations/<name>.surql'",
+                path.display()
+            ));
+        };
+
+        let mut content = String::new();
+        entry.read_to_string(&mut content).context(format!(
+            "Cannot read content of the file '{}'",
+            path.display()
+        ))?;
+
+        files
+            .entry(dir_name.to_string_lossy().to_string())
+            .or_default()
+            .insert(file_name.to_string_lossy().to_string(), content);
+    }
+
+    if files.is_empty() {
+        return Err(anyhow!("The archive does not contain any .surql file"));
+    }
+
+    Ok(files)
+}
+
+/// Read the `.surql` files of every folder of a migration source.
+pub async fn fetch_files(
+    source: &dyn MigrationSource,
+    dir_names: &DirNames,
+) -> Result<SourceFiles> {
+    let mut source_files = SourceFiles::new();
+
+    for dir_name in [
+        &dir_names.schemas,
+        &dir_names.events,
+        &dir_names.migrations,
+        &dir_names.seeds,
+    ] {
+        let file_names = source.list_files(dir_name).await?;
+
+        let mut files = Vec::new();
+
+        for file_name in file_names
+            .into_iter()
+            .filter(|file_name| file_name.ends_with(".surql"))
+        {
+            let content = source.read_file(dir_name, &file_name).await?;
+            files.push((file_name, content));
+        }
+
+        if !files.is_empty() {
+            source_files.insert(dir_name.to_string(), files);
+        }
+    }
+
+    Ok(source_files)
+}
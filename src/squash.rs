@@ -0,0 +1,94 @@
+use anyhow::{anyhow, Context, Result};
+use std::path::PathBuf;
+
+use crate::{
+    constants::DOWN_MIGRATION_SUFFIX,
+    error::MigrationError,
+    input::VersionFormat,
+    io::{self, FilesLocation},
+    version,
+};
+
+/// Concatenate the migrations up to the one given into a single migration file,
+/// and their down scripts into its down script if asked.
+/// Returns the path of the squashed migration file.
+pub fn main(
+    location: &FilesLocation,
+    up_to: &str,
+    with_down_script: bool,
+    version_format: VersionFormat,
+) -> Result<PathBuf> {
+    if location.embedded_dir.is_some() {
+        return Err(anyhow!("Cannot squash migrations in an embedded directory"));
+    }
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    let position = migrations_files
+        .iter()
+        .position(|migration_file| migration_file.name == up_to)
+        .context(format!(
+            "Cannot squash migrations up to '{}': this migration does not exist",
+            up_to
+        ))?;
+
+    let migrations_to_squash = &migrations_files[..=position];
+
+    let mut contents = Vec::new();
+    for migration_file in migrations_to_squash {
+        contents.push(format!(
+            "# {}\n{}",
+            migration_file.name,
+            migration_file.get_content()?.trim_end()
+        ));
+    }
+
+    // Down scripts revert the migrations in the reverse order
+    let mut down_contents = Vec::new();
+    if with_down_script {
+        let mut migrations_without_down_script = Vec::new();
+
+        for migration_file in migrations_to_squash.iter().rev() {
+            match io::get_down_migration_content(location, &migration_file.name)? {
+                Some(content) => {
+                    down_contents.push(format!("# {}\n{}", migration_file.name, content.trim_end()))
+                }
+                None => migrations_without_down_script.push(migration_file.name.to_string()),
+            }
+        }
+
+        if !migrations_without_down_script.is_empty() {
+            return Err(MigrationError::MissingDownScript {
+                migrations: migrations_without_down_script,
+            }
+            .into());
+        }
+    }
+
+    let migrations_path = io::concat_path(&location.folder_path, &location.dir_names.migrations);
+    let file_stem = format!(
+        "{}_Squashed",
+        version::get_version_prefix(up_to, version_format)?
+    );
+
+    let file_path = migrations_path.join(format!("{}.surql", file_stem));
+    let down_file_path =
+        migrations_path.join(format!("{}{}.surql", file_stem, DOWN_MIGRATION_SUFFIX));
+
+    let mut files = vec![(&file_path, contents)];
+    if with_down_script {
+        files.push((&down_file_path, down_contents));
+    }
+
+    for (file_path, _) in &files {
+        if file_path.exists() {
+            return Err(anyhow!("File {} already exists", file_path.display()));
+        }
+    }
+
+    for (file_path, contents) in files {
+        fs_extra::file::write_all(file_path, &format!("{}\n", contents.join("\n\n")))?;
+    }
+
+    Ok(file_path)
+}
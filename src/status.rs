@@ -0,0 +1,50 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+
+use crate::{
+    io::{self, FilesLocation},
+    models::{MigrationStatus, MigrationStatusEntry},
+    surrealdb::{self, MigrationsTable},
+};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<MigrationStatus> {
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    let migrations = migrations_files
+        .into_iter()
+        .map(|migration_file| {
+            let migration_applied = migrations_applied
+                .iter()
+                .find(|migration_applied| migration_applied.script_name == migration_file.name);
+
+            MigrationStatusEntry {
+                name: migration_file.name,
+                is_applied: migration_applied.is_some(),
+                executed_at: migration_applied
+                    .map(|migration_applied| migration_applied.executed_at.to_string()),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let orphaned = migrations_applied
+        .into_iter()
+        .filter(|migration_applied| {
+            !migrations
+                .iter()
+                .any(|migration| migration.name == migration_applied.script_name)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(MigrationStatus {
+        migrations,
+        orphaned,
+    })
+}
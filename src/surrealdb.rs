@@ -1,49 +1,364 @@
 use anyhow::{anyhow, Context, Result};
+use rustls::ClientConfig;
+use std::{collections::BTreeMap, fmt, time::Duration};
 use surrealdb::{
-    engine::remote::ws::{Client, Ws},
-    opt::auth::Root,
-    Surreal,
+    engine::any::{connect, Any},
+    opt::auth::{Database, Namespace, Root, Scope},
+    sql, Surreal,
 };
 
-use crate::{config, input::SurrealdbConfiguration, models::ScriptMigration};
+use crate::{
+    config::ProjectConfig,
+    constants::{
+        DEFINITION_CHECKSUMS_TABLE_SUFFIX, EXECUTED_AT_FIELD_NAME, IN_MEMORY_URL,
+        MIGRATIONS_HISTORY_TABLE_SUFFIX, MIGRATIONS_LOCK_TABLE_SUFFIX, MIGRATIONS_TABLE_NAME,
+        REDACTED_SECRET, REMOTE_URL_SCHEMES, SCHEMA_VERSION_TABLE_SUFFIX, SCRIPT_NAME_FIELD_NAME,
+    },
+    error::MigrationError,
+    input::{AuthLevel, ExecutedAtFormat, ListOrder, SurrealdbConfiguration},
+    logger,
+    migration_meta::MigrationTarget,
+    models::{ResolvedConfig, ScriptMigration},
+    tls,
+};
 
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "connect", skip_all))]
 pub async fn create_surrealdb_client(
     db_configuration: &SurrealdbConfiguration,
-) -> Result<Surreal<Client>> {
+    logger: Option<&(dyn Fn(&str) + Send + Sync)>,
+) -> Result<Surreal<Any>> {
     let SurrealdbConfiguration {
-        url,
         username,
         password,
-        ns,
-        db,
+        auth_level,
+        token,
+        scope,
+        scope_params,
+        connect_retries,
+        connect_retry_delay_ms,
+        connect_timeout,
+        tls_ca_cert,
+        tls_insecure,
+        create_ns_db_if_missing,
+        ..
     } = db_configuration;
 
-    let db_config = config::retrieve_db_config();
+    let db_config = ProjectConfig::load();
+    let ResolvedConfig { url, ns, db, .. } = resolve_config(db_configuration, &db_config);
+
+    validate_url_scheme(&url)?;
+
+    let tls_config = match tls_ca_cert.is_some() || *tls_insecure {
+        true => Some(tls::create_tls_config(
+            tls_ca_cert.as_deref(),
+            *tls_insecure,
+            logger,
+        )?),
+        false => None,
+    };
+
+    let client = create_surrealdb_connection(
+        &url,
+        tls_config,
+        *connect_retries,
+        *connect_retry_delay_ms,
+        *connect_timeout,
+    )
+    .await?;
+
+    #[cfg(feature = "tracing")]
+    tracing::info!(url = %url, "Connected to the SurrealDB instance");
+
+    // An embedded in-memory database does not support authentication
+    if !is_in_memory_url(&url) {
+        match (token, scope) {
+            (Some(token), _) => {
+                if username.is_some() || password.is_some() || scope.is_some() {
+                    logger::warn(
+                        logger,
+                        "both a token and credentials are set, the token is used to authenticate",
+                    );
+                }
+
+                client.authenticate(token.to_owned()).await?;
+            }
+            (None, Some(scope)) => {
+                sign_in_scope(scope, scope_params.as_ref(), &ns, &db, &client).await?;
+            }
+            (None, None) => {
+                let auth_level = auth_level.unwrap_or(AuthLevel::Root);
+                sign_in(
+                    username.clone(),
+                    password.clone(),
+                    auth_level,
+                    &ns,
+                    &db,
+                    &db_config,
+                    &client,
+                )
+                .await?;
+            }
+        }
+    }
 
-    let client = create_surrealdb_connection(url.clone(), &db_config).await?;
-    sign_in(username.clone(), password.clone(), &db_config, &client).await?;
-    set_namespace_and_database(ns.clone(), db.clone(), &db_config, &client).await?;
+    if *create_ns_db_if_missing {
+        define_namespace_and_database(&ns, &db, &client).await?;
+    }
+
+    client.use_ns(ns).use_db(db).await?;
 
     Ok(client)
 }
 
-async fn create_surrealdb_connection(
-    url: Option<String>,
-    db_config: &config::DbConfig,
-) -> Result<Surreal<Client>, surrealdb::Error> {
-    let url = url
+/// Resolve the connection details from the configuration, then the project config file, then the default values.
+pub fn resolve_config(
+    db_configuration: &SurrealdbConfiguration,
+    db_config: &ProjectConfig,
+) -> ResolvedConfig {
+    let url = db_configuration
+        .url
+        .to_owned()
         .or(db_config.url.to_owned())
         .unwrap_or("localhost:8000".to_owned());
+    let url = format_url(url);
+
+    let ns = db_configuration
+        .ns
+        .to_owned()
+        .or(db_config.ns.to_owned())
+        .unwrap_or("test".to_owned());
+    let db = db_configuration
+        .db
+        .to_owned()
+        .or(db_config.db.to_owned())
+        .unwrap_or("test".to_owned());
+
+    let redacted = Some(REDACTED_SECRET.to_string());
+
+    let (auth_level, username, password, token, scope) =
+        match (&db_configuration.token, &db_configuration.scope) {
+            _ if is_in_memory_url(&url) => (None, None, None, None, None),
+            (Some(_), _) => (None, None, None, redacted, None),
+            (None, Some(scope)) => (None, None, None, None, Some(scope.to_owned())),
+            (None, None) => {
+                let (username, _) = resolve_credentials(
+                    db_configuration.username.to_owned(),
+                    db_configuration.password.to_owned(),
+                    db_config,
+                );
+                let auth_level = db_configuration.auth_level.unwrap_or(AuthLevel::Root);
+
+                (Some(auth_level), Some(username), redacted, None, None)
+            }
+        };
 
-    Surreal::new::<Ws>(url.to_owned()).await
+    ResolvedConfig {
+        url,
+        ns,
+        db,
+        auth_level,
+        username,
+        password,
+        token,
+        scope,
+        migrations_table: get_migrations_table(db_configuration).to_string(),
+    }
+}
+
+/// Define the namespace and the database, which has no effect if they already exist.
+/// Their names are escaped, as they cannot be bound to the query.
+async fn define_namespace_and_database(ns: &str, db: &str, client: &Surreal<Any>) -> Result<()> {
+    let query = format!(
+        "DEFINE NAMESPACE {ns};\nUSE NS {ns};\nDEFINE DATABASE {db};",
+        ns = sql::Ident::from(ns),
+        db = sql::Ident::from(db)
+    );
+
+    apply_query(client, &query).await.context(format!(
+        "Cannot create namespace '{}' and database '{}'",
+        ns, db
+    ))
+}
+
+async fn create_surrealdb_connection(
+    url: &str,
+    tls_config: Option<ClientConfig>,
+    connect_retries: Option<u32>,
+    connect_retry_delay_ms: Option<u64>,
+    connect_timeout: Option<Duration>,
+) -> Result<Surreal<Any>> {
+    let connect_retries = connect_retries.unwrap_or(0);
+    let connect_retry_delay = Duration::from_millis(connect_retry_delay_ms.unwrap_or(500));
+
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        let connection = connect_once(url, tls_config.to_owned(), connect_timeout).await;
+
+        match connection {
+            Ok(client) => return Ok(client),
+            Err(error) if attempts > connect_retries => {
+                if attempts == 1 {
+                    return Err(error);
+                }
+
+                return Err(error.context(format!(
+                    "Cannot connect to the SurrealDB instance after {} attempts",
+                    attempts
+                )));
+            }
+            Err(_) => {
+                let delay = get_connect_retry_delay(connect_retry_delay, attempts);
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+async fn connect_once(
+    url: &str,
+    tls_config: Option<ClientConfig>,
+    connect_timeout: Option<Duration>,
+) -> Result<Surreal<Any>> {
+    let connection = async {
+        match tls_config {
+            Some(tls_config) => connect((url.to_owned(), tls_config)).await,
+            None => connect(url.to_owned()).await,
+        }
+    };
+
+    let client = match connect_timeout {
+        Some(timeout) => tokio::time::timeout(timeout, connection)
+            .await
+            .map_err(|_| MigrationError::ConnectionTimeout { timeout })??,
+        None => connection.await?,
+    };
+
+    Ok(client)
+}
+
+fn get_connect_retry_delay(connect_retry_delay: Duration, attempts: u32) -> Duration {
+    connect_retry_delay.saturating_mul(2u32.saturating_pow(attempts - 1))
+}
+
+/// Check that the scheme of the url selects an engine available in this build:
+/// WebSocket (`ws`/`wss`), HTTP (`http`/`https`), or in-memory (`mem`) with the `in-memory` feature.
+fn validate_url_scheme(url: &str) -> Result<()> {
+    if is_in_memory_url(url) {
+        return match cfg!(feature = "in-memory") {
+            true => Ok(()),
+            false => Err(anyhow!(
+                "Cannot connect to '{}': the `in-memory` feature is not enabled",
+                url
+            )),
+        };
+    }
+
+    let scheme = url
+        .split_once("://")
+        .map(|(scheme, _)| scheme)
+        .unwrap_or_default();
+
+    match REMOTE_URL_SCHEMES.contains(&scheme) {
+        true => Ok(()),
+        false => Err(anyhow!(
+            "Unsupported scheme '{}' in url '{}', expected one of: {}",
+            scheme,
+            url,
+            REMOTE_URL_SCHEMES.join(", ")
+        )),
+    }
+}
+
+pub fn is_in_memory_url(url: &str) -> bool {
+    url.starts_with(IN_MEMORY_URL)
+}
+
+pub fn format_url(url: String) -> String {
+    match url.contains("://") {
+        true => url,
+        false => format!("ws://{}", url),
+    }
 }
 
 async fn sign_in(
     username: Option<String>,
     password: Option<String>,
-    db_config: &config::DbConfig,
-    client: &Surreal<Client>,
+    auth_level: AuthLevel,
+    ns: &str,
+    db: &str,
+    db_config: &ProjectConfig,
+    client: &Surreal<Any>,
 ) -> Result<(), surrealdb::Error> {
+    let (username, password) = resolve_credentials(username, password, db_config);
+
+    match auth_level {
+        AuthLevel::Root => {
+            client
+                .signin(Root {
+                    username: &username,
+                    password: &password,
+                })
+                .await?;
+        }
+        AuthLevel::Namespace => {
+            client
+                .signin(Namespace {
+                    namespace: ns,
+                    username: &username,
+                    password: &password,
+                })
+                .await?;
+        }
+        AuthLevel::Database => {
+            client
+                .signin(Database {
+                    namespace: ns,
+                    database: db,
+                    username: &username,
+                    password: &password,
+                })
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sign in as a record user of the scope, the parameters being the ones expected by its `SIGNIN` clause.
+async fn sign_in_scope(
+    scope: &str,
+    scope_params: Option<&serde_json::Value>,
+    ns: &str,
+    db: &str,
+    client: &Surreal<Any>,
+) -> Result<()> {
+    let params = match scope_params {
+        None => serde_json::Value::Object(serde_json::Map::new()),
+        Some(scope_params) if scope_params.is_object() => scope_params.to_owned(),
+        Some(_) => return Err(anyhow!("The scope parameters must be a JSON object")),
+    };
+
+    client
+        .signin(Scope {
+            namespace: ns,
+            database: db,
+            scope,
+            params,
+        })
+        .await
+        .context(format!("Cannot sign in to the scope '{}'", scope))?;
+
+    Ok(())
+}
+
+fn resolve_credentials(
+    username: Option<String>,
+    password: Option<String>,
+    db_config: &ProjectConfig,
+) -> (String, String) {
     let username = username
         .or(db_config.username.to_owned())
         .unwrap_or("root".to_owned());
@@ -51,47 +366,208 @@ async fn sign_in(
         .or(db_config.password.to_owned())
         .unwrap_or("root".to_owned());
 
-    client
-        .signin(Root {
-            username: &username,
-            password: &password,
-        })
-        .await
+    (username, password)
 }
 
-async fn set_namespace_and_database(
-    ns: Option<String>,
-    db: Option<String>,
-    db_config: &config::DbConfig,
-    client: &Surreal<Client>,
-) -> Result<(), surrealdb::Error> {
-    let ns = ns.or(db_config.ns.to_owned()).unwrap_or("test".to_owned());
-    let db = db.or(db_config.db.to_owned()).unwrap_or("test".to_owned());
+/// The table storing the migrations applied, with the names of the fields identifying them.
+/// It is displayed as the name of the table, to be used in queries.
+#[derive(Clone, Copy)]
+pub struct MigrationsTable<'a> {
+    pub name: &'a str,
+    pub script_name: &'a str,
+    pub executed_at: &'a str,
+    pub executed_at_format: ExecutedAtFormat,
+}
+
+impl MigrationsTable<'_> {
+    /// Get the fields to select to read a `ScriptMigration`, renaming the custom fields.
+    pub fn select_fields(&self) -> String {
+        let mut fields = vec!["*".to_string()];
 
-    client.use_ns(ns.to_owned()).use_db(db.to_owned()).await
+        if self.script_name != SCRIPT_NAME_FIELD_NAME {
+            fields.push(format!("{} AS script_name", self.script_name));
+        }
+        if self.executed_at != EXECUTED_AT_FIELD_NAME {
+            fields.push(format!("{} AS executed_at", self.executed_at));
+        }
+
+        fields.join(", ")
+    }
+
+    /// Get the expression of the execution date to store, in the configured representation,
+    /// from a SurrealQL expression of the date.
+    pub fn executed_at_value(&self, date_expression: &str) -> String {
+        match self.executed_at_format {
+            ExecutedAtFormat::Datetime => date_expression.to_string(),
+            ExecutedAtFormat::EpochMillis => {
+                format!("<int> (time::nano({}) / 1000000)", date_expression)
+            }
+            ExecutedAtFormat::IsoString => format!(
+                "time::format({}, '%Y-%m-%dT%H:%M:%S%.6fZ')",
+                date_expression
+            ),
+        }
+    }
+
+    /// Get the name of the table storing the migrations lock.
+    /// The tables used internally are named after the migrations table,
+    /// so that projects sharing a database with different migrations tables do not share them.
+    pub fn lock_table(&self) -> String {
+        format!("{}{}", self.name, MIGRATIONS_LOCK_TABLE_SUFFIX)
+    }
+
+    /// Get the name of the table storing the checksums of the schema/event files applied.
+    pub fn definition_checksums_table(&self) -> String {
+        format!("{}{}", self.name, DEFINITION_CHECKSUMS_TABLE_SUFFIX)
+    }
+
+    /// Get the name of the table storing the schema version, when applying with a version check.
+    pub fn schema_version_table(&self) -> String {
+        format!("{}{}", self.name, SCHEMA_VERSION_TABLE_SUFFIX)
+    }
+
+    /// Get the name of the table storing the history of the migrations applied and rolled back.
+    pub fn history_table(&self) -> String {
+        format!("{}{}", self.name, MIGRATIONS_HISTORY_TABLE_SUFFIX)
+    }
+}
+
+impl fmt::Display for MigrationsTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+pub fn get_migrations_table(db_configuration: &SurrealdbConfiguration) -> MigrationsTable<'_> {
+    MigrationsTable {
+        name: db_configuration
+            .migrations_table
+            .as_deref()
+            .unwrap_or(MIGRATIONS_TABLE_NAME),
+        script_name: db_configuration
+            .script_name_field
+            .as_deref()
+            .unwrap_or(SCRIPT_NAME_FIELD_NAME),
+        executed_at: db_configuration
+            .executed_at_field
+            .as_deref()
+            .unwrap_or(EXECUTED_AT_FIELD_NAME),
+        executed_at_format: db_configuration.executed_at_format,
+    }
 }
 
 pub async fn list_script_migration_ordered_by_execution_date(
-    client: &Surreal<Client>,
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+) -> Result<Vec<ScriptMigration>> {
+    list_script_migration(client, migrations_table, ListOrder::ExecutionDateAsc).await
+}
+
+/// List the script migrations, sorted by the database.
+/// Migrations applied at the same date are sorted by version.
+pub async fn list_script_migration(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    order: ListOrder,
 ) -> Result<Vec<ScriptMigration>> {
-    let mut result = list_script_migration(client).await?;
-    result.sort_by_key(|m| m.executed_at.clone());
+    // NUMERIC compares the numbers in the names by value, so that `9_AddUser` comes before `10_AddPost`
+    let order_by = match order {
+        ListOrder::ExecutionDateAsc => format!(
+            "{} ASC, {} NUMERIC ASC",
+            migrations_table.executed_at, migrations_table.script_name
+        ),
+        ListOrder::ExecutionDateDesc => format!(
+            "{} DESC, {} NUMERIC DESC",
+            migrations_table.executed_at, migrations_table.script_name
+        ),
+        ListOrder::VersionAsc => format!("{} NUMERIC ASC", migrations_table.script_name),
+        ListOrder::VersionDesc => format!("{} NUMERIC DESC", migrations_table.script_name),
+    };
+
+    let query = format!(
+        "SELECT {} FROM {} ORDER BY {};",
+        migrations_table.select_fields(),
+        migrations_table,
+        order_by
+    );
+
+    let mut response = client.query(query).await?;
+    let result = response.take(0)?;
 
     Ok(result)
 }
 
-async fn list_script_migration(client: &Surreal<Client>) -> Result<Vec<ScriptMigration>> {
-    let result = client.select("script_migration").await?;
-    Ok(result)
+/// Get the namespace and the database currently used by the client.
+pub async fn get_current_ns_db(client: &Surreal<Any>) -> Result<(String, String)> {
+    let mut response = client
+        .query("RETURN session::ns();\nRETURN session::db();")
+        .await?;
+
+    let ns: Option<String> = response.take(0)?;
+    let db: Option<String> = response.take(1)?;
+
+    let ns = ns.context("No namespace is used by the client")?;
+    let db = db.context("No database is used by the client")?;
+
+    Ok((ns, db))
+}
+
+/// Get the namespace and the database currently used by the client, to switch back to after a migration with a `@use` header.
+pub async fn get_current_target(client: &Surreal<Any>) -> Result<MigrationTarget> {
+    let (ns, db) = get_current_ns_db(client).await?;
+    Ok(MigrationTarget { ns, db })
+}
+
+/// Values bound to the parameters of a query, instead of being interpolated in it.
+pub type QueryParams = BTreeMap<&'static str, String>;
+
+/// Get the statements defining the parameters of a query, for a script where they cannot be bound.
+pub fn get_params_definition_query(params: &QueryParams) -> Result<String> {
+    let statements = params
+        .iter()
+        .map(|(name, value)| {
+            Ok(format!(
+                "LET ${} = {};",
+                name,
+                serde_json::to_string(value)?
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(statements.join("\n"))
+}
+
+pub async fn apply_query(client: &Surreal<Any>, query: &String) -> Result<()> {
+    apply_query_with_params(client, query, &QueryParams::new()).await
+}
+
+pub async fn apply_query_with_params(
+    client: &Surreal<Any>,
+    query: &String,
+    params: &QueryParams,
+) -> Result<()> {
+    let response = client.query(query).bind(params).await?;
+    response.check()?;
+
+    Ok(())
 }
 
 pub async fn apply_in_transaction(
-    client: &Surreal<Client>,
+    client: &Surreal<Any>,
     inner_query: &String,
     action: TransactionAction,
+) -> Result<()> {
+    apply_in_transaction_with_params(client, inner_query, action, &QueryParams::new()).await
+}
+
+pub async fn apply_in_transaction_with_params(
+    client: &Surreal<Any>,
+    inner_query: &String,
+    action: TransactionAction,
+    params: &QueryParams,
 ) -> Result<()> {
     let query = format_transaction(inner_query.to_owned(), &action);
-    let response = client.query(query).await?;
+    let response = client.query(query).bind(params).await?;
 
     match action {
         TransactionAction::Rollback => {
@@ -151,6 +627,63 @@ CANCEL TRANSACTION;",
 mod tests {
     use super::*;
 
+    #[test]
+    fn format_url_should_use_websocket_by_default() {
+        let result = format_url("localhost:8000".to_owned());
+        assert_eq!(result, "ws://localhost:8000");
+    }
+
+    #[test]
+    fn format_url_should_keep_explicit_scheme() {
+        let result = format_url("wss://cloud.surrealdb.com".to_owned());
+        assert_eq!(result, "wss://cloud.surrealdb.com");
+    }
+
+    #[test]
+    fn validate_url_scheme_should_accept_websocket_and_http() {
+        for url in [
+            "ws://localhost:8000",
+            "wss://cloud.surrealdb.com",
+            "http://localhost:8000",
+            "https://cloud.surrealdb.com",
+        ] {
+            assert!(validate_url_scheme(url).is_ok());
+        }
+    }
+
+    #[test]
+    fn validate_url_scheme_should_reject_unknown_scheme() {
+        let result = validate_url_scheme("ftp://localhost:8000");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Unsupported scheme 'ftp' in url 'ftp://localhost:8000', expected one of: ws, wss, http, https"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "in-memory"))]
+    fn validate_url_scheme_should_reject_in_memory_without_feature() {
+        let result = validate_url_scheme("mem://");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Cannot connect to 'mem://': the `in-memory` feature is not enabled"
+        );
+    }
+
+    #[test]
+    fn connect_retry_delay_should_double_after_each_attempt() {
+        let connect_retry_delay = Duration::from_millis(500);
+
+        assert_eq!(
+            get_connect_retry_delay(connect_retry_delay, 1),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            get_connect_retry_delay(connect_retry_delay, 3),
+            Duration::from_millis(2000)
+        );
+    }
+
     #[test]
     fn within_transaction_should_return_string() {
         let inner_query = "DEFINE TABLE post SCHEMALESS;";
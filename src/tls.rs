@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier},
+    Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName,
+};
+use std::{fs::File, io::BufReader, path::Path, sync::Arc, time::SystemTime};
+
+use crate::logger;
+
+/// Create the TLS configuration trusting the default root certificates,
+/// and the CA certificate if any.
+/// Certificates are not verified at all if insecure.
+pub fn create_tls_config(
+    ca_cert_path: Option<&Path>,
+    insecure: bool,
+    logger: Option<&(dyn Fn(&str) + Send + Sync)>,
+) -> Result<ClientConfig> {
+    let mut root_store = RootCertStore::empty();
+    root_store.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(
+        |trust_anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                trust_anchor.subject,
+                trust_anchor.spki,
+                trust_anchor.name_constraints,
+            )
+        },
+    ));
+
+    if let Some(ca_cert_path) = ca_cert_path {
+        for certificate in read_certificates(ca_cert_path)? {
+            root_store.add(&certificate).context(format!(
+                "Invalid CA certificate in '{}'",
+                ca_cert_path.display()
+            ))?;
+        }
+    }
+
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+
+    if insecure {
+        logger::warn(
+            logger,
+            "TLS certificates are not verified, do not use it outside of local tests",
+        );
+
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(NoCertificateVerification));
+    }
+
+    Ok(config)
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<Certificate>> {
+    let file = File::open(path).context(format!(
+        "Cannot read the CA certificate file '{}'",
+        path.display()
+    ))?;
+
+    let certificates = rustls_pemfile::certs(&mut BufReader::new(file)).context(format!(
+        "Cannot parse the CA certificate file '{}'",
+        path.display()
+    ))?;
+
+    Ok(certificates.into_iter().map(Certificate).collect())
+}
+
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_tls_config_should_fail_if_ca_cert_file_does_not_exist() {
+        let result = create_tls_config(Some(Path::new("certs/missing.pem")), false, None);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Cannot read the CA certificate file 'certs/missing.pem'"
+        );
+    }
+}
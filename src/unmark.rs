@@ -0,0 +1,32 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::{anyhow, Result};
+
+use crate::surrealdb::{self, MigrationsTable, QueryParams};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    name: &str,
+) -> Result<()> {
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let has_been_applied = migrations_applied
+        .iter()
+        .any(|migration_applied| migration_applied.script_name == name);
+
+    if !has_been_applied {
+        return Err(anyhow!(
+            "Cannot unmark migration '{}': this migration has not been applied",
+            name
+        ));
+    }
+
+    let query = format!(
+        "DELETE {} WHERE {} = $migration_name;",
+        migrations_table, migrations_table.script_name
+    );
+    let params = QueryParams::from([("migration_name", name.to_string())]);
+    surrealdb::apply_query_with_params(client, &query, &params).await
+}
@@ -0,0 +1,123 @@
+use ::surrealdb::sql;
+use anyhow::{anyhow, Result};
+
+use crate::{
+    directives,
+    input::VersionFormat,
+    io::{self, FilesLocation, SurqlFile},
+    validate_schema_files, validate_version_order, version,
+};
+
+/// Run every validation of the migration files not needing the database, failing on the first one failing:
+/// the names of the migrations, their versions, the schema files if strictly validated, then the syntax of the files.
+pub fn main(
+    location: &FilesLocation,
+    version_format: VersionFormat,
+    strict_schema: bool,
+    skip: &[String],
+) -> Result<()> {
+    let migrations_files = io::extract_migrations_files(location)?
+        .into_iter()
+        .filter(|migration_file| !skip.contains(&migration_file.name))
+        .collect::<Vec<_>>();
+
+    validate_names(&migrations_files, version_format)?;
+    validate_version_order::validate_no_duplicate_versions(&migrations_files, version_format)?;
+    validate_sort_order(&migrations_files, version_format)?;
+
+    let schemas_files = extract_definition_files(location, &location.dir_names.schemas)?;
+    let events_files = extract_definition_files(location, &location.dir_names.events)?;
+
+    if strict_schema {
+        validate_schema_files::main(&schemas_files)?;
+    }
+
+    let mut files_content = Vec::new();
+
+    for file in schemas_files
+        .iter()
+        .chain(events_files.iter())
+        .chain(migrations_files.iter())
+    {
+        files_content.push((file.path.display().to_string(), file.get_content()?));
+    }
+
+    for migration_file in &migrations_files {
+        if let Some(content) = io::get_down_migration_content(location, &migration_file.name)? {
+            files_content.push((format!("{} (down script)", migration_file.name), content));
+        }
+    }
+
+    validate_syntax(&files_content)
+}
+
+fn extract_definition_files(location: &FilesLocation, dir_name: &str) -> Result<Vec<SurqlFile>> {
+    match io::definition_folder_exists(location, dir_name)? {
+        true => io::extract_definition_files(location, dir_name),
+        false => Ok(Vec::new()),
+    }
+}
+
+/// Fail if some migrations do not match the version format.
+fn validate_names(migrations_files: &[SurqlFile], version_format: VersionFormat) -> Result<()> {
+    let errors = migrations_files
+        .iter()
+        .filter_map(|migration_file| {
+            version::parse_version(&migration_file.name, version_format).err()
+        })
+        .map(|error| error.to_string())
+        .collect::<Vec<_>>();
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(anyhow!(errors.join("\n"))),
+    }
+}
+
+/// Fail if the migrations, applied in name order, are not in version order,
+/// like `10_AddPost` named before `9_AddUser` with sequential versions.
+fn validate_sort_order(
+    migrations_files: &[SurqlFile],
+    version_format: VersionFormat,
+) -> Result<()> {
+    let mut misplaced_migrations = Vec::new();
+    let mut last_version = None;
+
+    for migration_file in migrations_files {
+        let version = version::parse_version(&migration_file.name, version_format)?;
+
+        match last_version {
+            Some(last_version) if version < last_version => {
+                misplaced_migrations.push(migration_file.name.to_string())
+            }
+            _ => last_version = Some(version),
+        }
+    }
+
+    match misplaced_migrations.is_empty() {
+        true => Ok(()),
+        false => Err(anyhow!(
+            "The following migrations are sorted by name after migrations of a later version: {}",
+            misplaced_migrations.join(", ")
+        )),
+    }
+}
+
+/// Fail if some files cannot be parsed, listing all of them.
+/// Files with variables or version directives are not parsed, as their content depends on the apply operation.
+fn validate_syntax(files_content: &[(String, String)]) -> Result<()> {
+    let errors = files_content
+        .iter()
+        .filter(|(_, content)| !content.contains("${") && !directives::has_directives(content))
+        .filter_map(|(file, content)| {
+            sql::parse(content)
+                .err()
+                .map(|error| format!("Cannot parse '{}': {}", file, error))
+        })
+        .collect::<Vec<_>>();
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(anyhow!(errors.join("\n"))),
+    }
+}
@@ -0,0 +1,31 @@
+use ::surrealdb::sql::{self, statements::DefineStatement, Statement};
+use anyhow::{Context, Result};
+
+use crate::{error::MigrationError, io::SurqlFile};
+
+/// Check that each schema file defines exactly one table.
+pub fn main(schemas_files: &[SurqlFile]) -> Result<()> {
+    for file in schemas_files {
+        let query = sql::parse(&file.get_content()?).context(format!(
+            "Cannot parse the schema file '{}'",
+            file.path.display()
+        ))?;
+
+        let tables_count = query
+            .0
+             .0
+            .iter()
+            .filter(|statement| matches!(statement, Statement::Define(DefineStatement::Table(_))))
+            .count();
+
+        if tables_count != 1 {
+            return Err(MigrationError::InvalidSchemaFile {
+                file: file.path.display().to_string(),
+                tables_count,
+            }
+            .into());
+        }
+    }
+
+    Ok(())
+}
@@ -1,177 +1,138 @@
-use std::{
-    collections::{HashMap, HashSet},
-    path::{Path, PathBuf},
-};
-
-use anyhow::{anyhow, Context, Result};
-use fs_extra::dir::{DirEntryAttr, DirEntryValue, LsResult};
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+use std::collections::BTreeMap;
 
 use crate::{
-    config, constants::MIGRATIONS_DIR_NAME, input::SurrealdbConfiguration, models::ScriptMigration,
-    surrealdb,
+    error::MigrationError,
+    input::VersionFormat,
+    io::{self, FilesLocation, SurqlFile},
+    migration_meta::MigrationMeta,
+    models::ScriptMigration,
+    surrealdb::{self, MigrationsTable},
+    version,
 };
 
-pub async fn main(db_configuration: &SurrealdbConfiguration) -> Result<()> {
-    let client = surrealdb::create_surrealdb_client(db_configuration).await?;
-
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+    version_format: VersionFormat,
+    enabled_tags: &[String],
+    skip: &[String],
+) -> Result<()> {
     let migrations_applied =
-        surrealdb::list_script_migration_ordered_by_execution_date(&client).await?;
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
 
-    let mut config = HashSet::new();
-    config.insert(DirEntryAttr::Name);
-    config.insert(DirEntryAttr::Path);
-    config.insert(DirEntryAttr::IsFile);
+    let migrations_files = io::extract_migrations_files(location)?
+        .into_iter()
+        .filter(|migration_file| !skip.contains(&migration_file.name))
+        .collect::<Vec<_>>();
 
-    let folder_path = config::retrieve_folder_path();
-    let migrations_dir_path = concat_path(&folder_path, MIGRATIONS_DIR_NAME);
+    validate(
+        &migrations_applied,
+        &migrations_files,
+        version_format,
+        enabled_tags,
+    )
+}
 
-    let migrations_files = fs_extra::dir::ls(migrations_dir_path, &config)?;
+/// Fail if some migrations have not been applied while later migrations have been,
+/// the migrations applied being ordered by execution date.
+pub fn validate(
+    migrations_applied: &[ScriptMigration],
+    migrations_files: &[SurqlFile],
+    version_format: VersionFormat,
+    enabled_tags: &[String],
+) -> Result<()> {
+    let mut migrations_not_applied = Vec::new();
+
+    for migration_file in migrations_files {
+        let version = version::parse_version(&migration_file.name, version_format)?;
+
+        // Migrations with disabled tags are expected to be skipped
+        let is_enabled =
+            MigrationMeta::parse(&migration_file.get_content()?)?.is_enabled(enabled_tags);
+
+        if is_enabled && !is_migration_file_already_applied(migration_file, migrations_applied) {
+            migrations_not_applied.push((migration_file, version));
+        }
+    }
 
-    let migrations_not_applied = get_sorted_migrations_files(&migrations_files)
-        .into_iter()
-        .filter(|migration_file| {
-            is_migration_file_already_applied(migration_file, &migrations_applied).unwrap_or(false)
-        })
-        .collect::<Vec<_>>();
+    let last_version_applied = match migrations_applied.last() {
+        Some(last_migration_applied) => Some(version::parse_version(
+            &last_migration_applied.script_name,
+            version_format,
+        )?),
+        None => None,
+    };
+
+    let migrations_not_applied_before_last_applied = match last_version_applied {
+        Some(last_version_applied) => migrations_not_applied
+            .into_iter()
+            .filter(|(_, version)| *version < last_version_applied)
+            .map(|(migration_file, _)| migration_file)
+            .collect::<Vec<_>>(),
+        None => Vec::new(),
+    };
 
-    let last_migration_applied = migrations_applied.last();
-
-    let migrations_not_applied_before_last_applied =
-        if let Some(last_migration_applied) = last_migration_applied {
-            migrations_not_applied
-                .iter()
-                .filter(|migration_file| {
-                    is_migration_file_before_last_applied(migration_file, &last_migration_applied)
-                        .unwrap_or(false)
-                })
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        };
-
-    if migrations_not_applied_before_last_applied.len() > 0 {
+    if !migrations_not_applied_before_last_applied.is_empty() {
         let migration_names = migrations_not_applied_before_last_applied
             .iter()
-            .map(|migration_file| get_migration_file_name(migration_file).unwrap_or("".to_string()))
+            .map(|migration_file| migration_file.name.to_string())
             .collect::<Vec<_>>();
 
-        Err(anyhow!(
-            "The following migrations have not been applied: {}",
-            migration_names.join(", ")
-        ))
+        Err(MigrationError::VersionOrderViolation {
+            migrations: migration_names,
+        }
+        .into())
     } else {
         Ok(())
     }
 }
 
-fn concat_path(folder_path: &Option<String>, dir_name: &str) -> PathBuf {
-    match folder_path.to_owned() {
-        Some(folder_path) => Path::new(&folder_path).join(dir_name),
-        None => Path::new(dir_name).to_path_buf(),
-    }
-}
-
-fn get_sorted_migrations_files(
-    migrations_files: &LsResult,
-) -> Vec<&HashMap<DirEntryAttr, DirEntryValue>> {
-    let mut sorted_migrations_files = migrations_files.items.iter().collect::<Vec<_>>();
-    sorted_migrations_files.sort_by(|a, b| {
-        let a = a.get(&DirEntryAttr::Name);
-        let b = b.get(&DirEntryAttr::Name);
-
-        let a = match a {
-            Some(DirEntryValue::String(a)) => Some(a),
-            _ => None,
-        };
-
-        let b = match b {
-            Some(DirEntryValue::String(b)) => Some(b),
-            _ => None,
-        };
-
-        a.cmp(&b)
-    });
-
-    sorted_migrations_files
-}
-
 fn is_migration_file_already_applied(
-    migration_file: &&std::collections::HashMap<DirEntryAttr, DirEntryValue>,
-    migrations_applied: &Vec<ScriptMigration>,
-) -> Result<bool> {
-    let is_file = migration_file
-        .get(&DirEntryAttr::IsFile)
-        .context("Cannot detect if the migration file is a file or a folder")?;
-    let is_file = match is_file {
-        DirEntryValue::Boolean(is_file) => Some(is_file),
-        _ => None,
-    };
-    let is_file = is_file.context("Cannot detect if the migration file is a file or a folder")?;
-
-    if !is_file {
-        return Ok(false);
-    }
-
-    let name = migration_file
-        .get(&DirEntryAttr::Name)
-        .context("Cannot get name of the migration file")?;
-    let name = match name {
-        DirEntryValue::String(name) => Some(name),
-        _ => None,
-    };
-    let name = name.context("Cannot get name of the migration file")?;
-
-    let has_already_been_applied = migrations_applied
+    migration_file: &SurqlFile,
+    migrations_applied: &[ScriptMigration],
+) -> bool {
+    migrations_applied
         .iter()
-        .any(|migration_applied| &migration_applied.script_name == name);
-
-    if has_already_been_applied {
-        return Ok(false);
-    }
-
-    return Ok(true);
+        .any(|migration_applied| migration_applied.script_name == migration_file.name)
 }
 
-fn is_migration_file_before_last_applied(
-    migration_file: &&HashMap<DirEntryAttr, DirEntryValue>,
-    last_migration_applied: &ScriptMigration,
-) -> Result<bool> {
-    let is_file = migration_file
-        .get(&DirEntryAttr::IsFile)
-        .context("Cannot detect if the migration file is a file or a folder")?;
-    let is_file = match is_file {
-        DirEntryValue::Boolean(is_file) => Some(is_file),
-        _ => None,
-    };
-    let is_file = is_file.context("Cannot detect if the migration file is a file or a folder")?;
-
-    if !is_file {
-        return Ok(false);
+/// Fail if several migration files share the same version, listing their file names.
+/// Migrations whose name does not match the version format are not checked.
+pub fn validate_no_duplicate_versions(
+    migrations_files: &[SurqlFile],
+    version_format: VersionFormat,
+) -> Result<()> {
+    let mut files_by_version: BTreeMap<u64, Vec<&SurqlFile>> = BTreeMap::new();
+
+    for migration_file in migrations_files {
+        if let Ok(version) = version::parse_version(&migration_file.name, version_format) {
+            files_by_version
+                .entry(version)
+                .or_default()
+                .push(migration_file);
+        }
     }
 
-    let name = migration_file
-        .get(&DirEntryAttr::Name)
-        .context("Cannot get name of the migration file")?;
-    let name = match name {
-        DirEntryValue::String(name) => Some(name),
-        _ => None,
-    };
-    let name = name.context("Cannot get name of the migration file")?;
-
-    Ok(name < &last_migration_applied.script_name)
-}
+    let duplicated_files = files_by_version
+        .into_values()
+        .filter(|files| files.len() > 1)
+        .flatten()
+        .map(|migration_file| match migration_file.path.file_name() {
+            Some(file_name) => file_name.to_string_lossy().to_string(),
+            None => migration_file.name.to_string(),
+        })
+        .collect::<Vec<_>>();
 
-fn get_migration_file_name(
-    migration_file: &&HashMap<DirEntryAttr, DirEntryValue>,
-) -> Result<String> {
-    let name = migration_file
-        .get(&DirEntryAttr::Name)
-        .context("Cannot get name of the migration file")?;
-    let name = match name {
-        DirEntryValue::String(name) => Some(name),
-        _ => None,
-    };
-    let name = name.context("Cannot get name of the migration file")?;
+    if !duplicated_files.is_empty() {
+        return Err(MigrationError::DuplicateVersions {
+            migrations: duplicated_files,
+        }
+        .into());
+    }
 
-    Ok(name.to_string())
+    Ok(())
 }
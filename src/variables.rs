@@ -0,0 +1,64 @@
+use anyhow::{anyhow, Result};
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+/// Replace `${NAME}` placeholders with the value of the variable `NAME`.
+/// The `$` prefix alone is kept for SurrealQL parameters (like `$before`).
+pub fn substitute_variables(content: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let regex = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}")?;
+
+    let mut missing_variables = Vec::new();
+
+    for captures in regex.captures_iter(content) {
+        let name = captures[1].to_string();
+
+        if !vars.contains_key(&name) && !missing_variables.contains(&name) {
+            missing_variables.push(name);
+        }
+    }
+
+    if !missing_variables.is_empty() {
+        return Err(anyhow!(
+            "The following variables are missing: {}",
+            missing_variables.join(", ")
+        ));
+    }
+
+    let result = regex.replace_all(content, |captures: &Captures| {
+        vars[&captures[1]].to_string()
+    });
+
+    Ok(result.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitute_variables_should_replace_placeholders() {
+        let vars = HashMap::from([("ADMIN_EMAIL".to_string(), "admin@example.com".to_string())]);
+
+        let result = substitute_variables(
+            "CREATE user SET email = '${ADMIN_EMAIL}', created_at = $now;",
+            &vars,
+        );
+
+        assert_eq!(
+            result.unwrap(),
+            "CREATE user SET email = 'admin@example.com', created_at = $now;"
+        );
+    }
+
+    #[test]
+    fn substitute_variables_should_fail_on_missing_variables() {
+        let vars = HashMap::new();
+
+        let result = substitute_variables("${A} ${B} ${A}", &vars);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "The following variables are missing: A, B"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use ::surrealdb::{engine::any::Any, Surreal};
+use anyhow::Result;
+
+use crate::{
+    checksum,
+    io::{self, FilesLocation, SurqlFile},
+    models::{ChecksumMismatch, ScriptMigration},
+    surrealdb::{self, MigrationsTable},
+};
+
+pub async fn main(
+    client: &Surreal<Any>,
+    migrations_table: MigrationsTable<'_>,
+    location: &FilesLocation,
+) -> Result<Vec<ChecksumMismatch>> {
+    let migrations_applied =
+        surrealdb::list_script_migration_ordered_by_execution_date(client, migrations_table)
+            .await?;
+
+    let migrations_files = io::extract_migrations_files(location)?;
+
+    find_checksum_mismatches(&migrations_applied, &migrations_files)
+}
+
+/// Compare the checksum stored for each applied migration with the checksum of its current file.
+/// Migrations applied without checksum (by an older version) are ignored.
+pub fn find_checksum_mismatches(
+    migrations_applied: &[ScriptMigration],
+    migrations_files: &[SurqlFile],
+) -> Result<Vec<ChecksumMismatch>> {
+    let mut checksum_mismatches = Vec::new();
+
+    for migration_applied in migrations_applied {
+        let expected_checksum = match &migration_applied.checksum {
+            Some(checksum) => checksum,
+            None => continue,
+        };
+
+        let migration_file = migrations_files
+            .iter()
+            .find(|migration_file| migration_file.name == migration_applied.script_name);
+
+        let actual_checksum = match migration_file {
+            Some(migration_file) => {
+                Some(checksum::compute_checksum(&migration_file.get_content()?))
+            }
+            None => None,
+        };
+
+        if actual_checksum.as_ref() != Some(expected_checksum) {
+            checksum_mismatches.push(ChecksumMismatch {
+                script_name: migration_applied.script_name.to_string(),
+                expected_checksum: expected_checksum.to_string(),
+                actual_checksum,
+            });
+        }
+    }
+
+    Ok(checksum_mismatches)
+}
@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+
+use crate::input::VersionFormat;
+
+/// Parse the version prefixing the name of a migration, as a number ordered like the migrations.
+pub fn parse_version(name: &str, version_format: VersionFormat) -> Result<u64> {
+    let version = match version_format {
+        VersionFormat::Timestamp => parse_timestamp_version(name),
+        VersionFormat::Sequential => parse_sequential_version(name),
+    };
+
+    version.ok_or_else(|| {
+        let expected_format = match version_format {
+            VersionFormat::Timestamp => "YYYYMMDD_HHMMSS_Name",
+            VersionFormat::Sequential => "NNNN_Name",
+        };
+
+        anyhow!(
+            "Migration '{}' does not match the version format '{}'",
+            name,
+            expected_format
+        )
+    })
+}
+
+/// Get the version prefixing the name of a migration, as written in its name.
+pub fn get_version_prefix(name: &str, version_format: VersionFormat) -> Result<String> {
+    parse_version(name, version_format)?;
+
+    let parts_count = match version_format {
+        VersionFormat::Timestamp => 2,
+        VersionFormat::Sequential => 1,
+    };
+
+    let prefix = name
+        .split('_')
+        .take(parts_count)
+        .collect::<Vec<_>>()
+        .join("_");
+
+    Ok(prefix)
+}
+
+fn parse_timestamp_version(name: &str) -> Option<u64> {
+    let mut parts = name.splitn(3, '_');
+    let (date, time, migration_name) = (parts.next()?, parts.next()?, parts.next()?);
+
+    if date.len() != 8 || time.len() != 6 || migration_name.is_empty() {
+        return None;
+    }
+
+    let timestamp = format!("{}{}", date, time);
+    chrono::NaiveDateTime::parse_from_str(&timestamp, "%Y%m%d%H%M%S").ok()?;
+
+    timestamp.parse().ok()
+}
+
+fn parse_sequential_version(name: &str) -> Option<u64> {
+    let (version, migration_name) = name.split_once('_')?;
+
+    if version.is_empty() || !version.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    if migration_name.is_empty() {
+        return None;
+    }
+
+    version.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamp_and_sequential_versions() {
+        let timestamp_version =
+            parse_version("20230317_153201_AddAdminUser", VersionFormat::Timestamp).unwrap();
+        let sequential_version =
+            parse_version("0012_AddAdminUser", VersionFormat::Sequential).unwrap();
+
+        assert_eq!(timestamp_version, 20230317153201);
+        assert_eq!(sequential_version, 12);
+    }
+
+    #[test]
+    fn cannot_parse_version_not_matching_format() {
+        let error = parse_version("0012_AddAdminUser", VersionFormat::Timestamp).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Migration '0012_AddAdminUser' does not match the version format 'YYYYMMDD_HHMMSS_Name'"
+        );
+    }
+}
@@ -20,6 +20,36 @@ fn remove_last_migration() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn remove_last_up_and_down_migration_files() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let last_migration_name = get_last_migration_name()?;
+    rename_to_up_migration_file(&last_migration_name)?;
+    add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+    let mut cmd = create_cmd()?;
+
+    cmd.arg("remove");
+
+    cmd.assert()
+        .success()
+        .stdout("Migration 'CommentPost' successfully removed\n");
+
+    assert!(!is_file_exists(&format!(
+        "tests-files/migrations/{}.up.surql",
+        last_migration_name
+    ))?);
+    assert!(!is_file_exists(&format!(
+        "tests-files/migrations/{}.down.surql",
+        last_migration_name
+    ))?);
+
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn cannot_remove_if_no_migration_file_left() -> Result<()> {
@@ -1,6 +1,9 @@
 use anyhow::{anyhow, Result};
 use assert_cmd::Command;
-use std::{fs, io, path::PathBuf};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 pub fn scaffold_empty_template() -> Result<()> {
     scaffold_template("empty")
@@ -41,33 +44,62 @@ pub fn create_cmd() -> Result<Command> {
 
 pub fn get_first_migration_name() -> Result<String> {
     let first_migration_file = get_first_migration_file()?;
+    get_migration_name(&first_migration_file)
+}
+
+pub fn get_last_migration_name() -> Result<String> {
+    let migration_files = get_sorted_migration_files()?;
+
+    let last_migration_file = migration_files
+        .last()
+        .ok_or_else(|| anyhow!("No migration files found"))?;
+
+    get_migration_name(last_migration_file)
+}
 
-    let first_migration_name = first_migration_file
+pub fn get_migration_names() -> Result<Vec<String>> {
+    get_sorted_migration_files()?
+        .iter()
+        .map(|migration_file| get_migration_name(migration_file))
+        .collect()
+}
+
+fn get_migration_name(migration_file: &Path) -> Result<String> {
+    let migration_name = migration_file
         .file_stem()
         .ok_or_else(|| anyhow!("Could not get file stem"))?
         .to_str()
         .ok_or_else(|| anyhow!("Could not convert file stem to str"))?
         .to_owned();
 
-    Ok(first_migration_name)
+    Ok(migration_name)
 }
 
 pub fn get_first_migration_file() -> Result<PathBuf> {
+    let migration_files = get_sorted_migration_files()?;
+
+    let first_migration_file = migration_files
+        .first()
+        .ok_or_else(|| anyhow!("No migration files found"))?;
+
+    Ok(first_migration_file.to_path_buf())
+}
+
+fn get_sorted_migration_files() -> Result<Vec<PathBuf>> {
     let migrations_files_dir = std::path::Path::new("tests-files/migrations");
 
     let mut migration_files = fs::read_dir(migrations_files_dir)?
         .map(|entry| -> io::Result<PathBuf> { Ok(entry?.path()) })
-        .collect::<Result<Vec<PathBuf>, io::Error>>()?;
+        .collect::<Result<Vec<PathBuf>, io::Error>>()?
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
 
     migration_files.sort_by(|a, b| {
         a.file_name()
             .unwrap_or_default()
-            .cmp(&b.file_name().unwrap_or_default())
+            .cmp(b.file_name().unwrap_or_default())
     });
 
-    let first_migration_file = migration_files
-        .first()
-        .ok_or_else(|| anyhow!("No migration files found"))?;
-
-    Ok(first_migration_file.to_path_buf())
+    Ok(migration_files)
 }
@@ -42,3 +42,27 @@ DEFINE FIELD created_at ON comment TYPE datetime VALUE $before OR time::now();";
 
     Ok(())
 }
+
+pub fn add_down_migration_file(migration_name: &str, content: &str) -> Result<()> {
+    let migrations_files_dir = std::path::Path::new("tests-files/migrations");
+
+    if migrations_files_dir.exists() {
+        let down_migration_file =
+            migrations_files_dir.join(format!("{}.down.surql", migration_name));
+
+        std::fs::write(down_migration_file, content)?;
+    }
+
+    Ok(())
+}
+
+pub fn rename_to_up_migration_file(migration_name: &str) -> Result<()> {
+    let migrations_files_dir = std::path::Path::new("tests-files/migrations");
+
+    std::fs::rename(
+        migrations_files_dir.join(format!("{}.surql", migration_name)),
+        migrations_files_dir.join(format!("{}.up.surql", migration_name)),
+    )?;
+
+    Ok(())
+}
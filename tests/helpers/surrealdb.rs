@@ -126,7 +126,7 @@ pub async fn check_surrealdb_empty() -> Result<()> {
     let result: Option<SurrealdbTableDefinitions> = response.take("tb")?;
     let table_definitions = result.context("Failed to get table definitions")?;
 
-    if table_definitions.len() > 0 {
+    if !table_definitions.is_empty() {
         return Err(anyhow!("SurrealDB instance is not empty"));
     }
 
@@ -142,6 +142,7 @@ pub async fn create_surrealdb_client(
         password,
         ns,
         db,
+        ..
     } = db_configuration;
 
     let client = create_surrealdb_connection(url.clone()).await?;
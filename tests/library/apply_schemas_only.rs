@@ -0,0 +1,81 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn apply_schemas_only() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.apply_schemas_only().await?;
+
+            let schema_diff = runner.schema_diff().await?;
+            ensure!(schema_diff.is_empty());
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_schemas_only_without_migrations_folder() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            remove_folder("tests-files/migrations")?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.apply_schemas_only().await?;
+
+            let schema_diff = runner.schema_diff().await?;
+            ensure!(schema_diff.is_empty());
+
+            ensure!(!is_file_exists("tests-files/migrations")?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_schemas_only_twice_with_idempotent_defines() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .force_schema(true)
+                .idempotent_defines(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.apply_schemas_only().await?;
+            runner.apply_schemas_only().await?;
+
+            let schema_diff = runner.schema_diff().await?;
+            ensure!(schema_diff.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
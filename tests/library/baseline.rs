@@ -0,0 +1,91 @@
+use anyhow::{ensure, Result};
+use chrono::{TimeZone, Utc};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn baseline_marks_migrations_as_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+            let second_migration_name = &migration_names[1];
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.baseline(second_migration_name).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            let migrations_applied = migrations_applied
+                .into_iter()
+                .map(|migration| migration.script_name)
+                .collect::<Vec<_>>();
+            ensure!(migrations_applied == migration_names[..2]);
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations == migration_names[2..]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn baseline_with_clock() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let last_migration_name = get_last_migration_name()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration)
+                .with_clock(|| Utc.with_ymd_and_hms(2023, 3, 17, 15, 32, 1).unwrap());
+
+            runner.baseline(&last_migration_name).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.executed_at == "2023-03-17T15:32:01Z"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_baseline_to_unknown_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let result = SurrealdbMigrations::new(configuration)
+                .baseline("20230101_000000_Unknown")
+                .await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == "Cannot baseline to migration '20230101_000000_Unknown': this migration does not exist"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,178 @@
+use anyhow::{ensure, Result};
+use serde_json::json;
+use std::{path::PathBuf, time::Duration};
+use surrealdb_migrations::{
+    AuthLevel, ConcurrencyControl, ExecutedAtFormat, HealthCheck, SurrealdbConfiguration,
+    VersionFormat,
+};
+
+#[test]
+fn build_with_default_values() -> Result<()> {
+    let configuration = SurrealdbConfiguration::builder().build();
+
+    ensure!(configuration.url.is_none());
+    ensure!(configuration.ns.is_none());
+    ensure!(configuration.db.is_none());
+    ensure!(configuration.username.is_none());
+    ensure!(configuration.password.is_none());
+    ensure!(configuration.auth_level.is_none());
+    ensure!(configuration.token.is_none());
+    ensure!(configuration.scope.is_none());
+    ensure!(configuration.scope_params.is_none());
+    ensure!(configuration.migrations_table.is_none());
+    ensure!(configuration.script_name_field.is_none());
+    ensure!(configuration.executed_at_field.is_none());
+    ensure!(configuration.executed_at_format == ExecutedAtFormat::Datetime);
+    ensure!(configuration.transactional);
+    ensure!(!configuration.statement_progress);
+    ensure!(configuration.connect_timeout.is_none());
+    ensure!(configuration.tls_ca_cert.is_none());
+    ensure!(!configuration.tls_insecure);
+    ensure!(configuration.folders.is_empty());
+    ensure!(configuration.schema_dir.is_none());
+    ensure!(configuration.event_dir.is_none());
+    ensure!(configuration.migration_dir.is_none());
+    ensure!(configuration.lock_ttl_ms.is_none());
+    ensure!(configuration.concurrency_control == ConcurrencyControl::Lock);
+    ensure!(!configuration.force_schema);
+    ensure!(!configuration.idempotent_defines);
+    ensure!(configuration.schema_concurrency == 1);
+    ensure!(!configuration.strict_schema);
+    ensure!(!configuration.continue_on_error);
+    ensure!(!configuration.allow_checksum_mismatch);
+    ensure!(configuration.migration_timeout.is_none());
+    ensure!(configuration.migration_retries.is_none());
+    ensure!(configuration.migration_retry_delay_ms.is_none());
+    ensure!(!configuration.create_down_script);
+    ensure!(configuration.version_format == VersionFormat::Timestamp);
+    ensure!(!configuration.create_ns_db_if_missing);
+    ensure!(configuration.health_check.is_none());
+    ensure!(configuration.min_server_version.is_none());
+    ensure!(configuration.applied_by.is_none());
+    ensure!(configuration.applied_from_host.is_none());
+    ensure!(configuration.enabled_tags.is_empty());
+    ensure!(!configuration.run_seeds);
+    ensure!(!configuration.validate_before_apply);
+    ensure!(configuration.skip.is_empty());
+    ensure!(!configuration.log_statements);
+    ensure!(configuration.redacted_vars.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn build_with_all_values() -> Result<()> {
+    let configuration = SurrealdbConfiguration::builder()
+        .url("localhost:8001")
+        .ns("namespace")
+        .db(String::from("database"))
+        .username("admin")
+        .password("password")
+        .auth_level(AuthLevel::Database)
+        .token("token")
+        .scope("migrator")
+        .scope_params(serde_json::json!({ "email": "admin@example.com" }))
+        .migrations_table("_my_app_migrations")
+        .script_name_field("migration_name")
+        .executed_at_field("ran_at")
+        .executed_at_format(ExecutedAtFormat::EpochMillis)
+        .transactional(false)
+        .statement_progress(true)
+        .connect_retries(3)
+        .connect_retry_delay_ms(100)
+        .connect_timeout(Duration::from_secs(5))
+        .tls_ca_cert("certs/ca.pem")
+        .tls_insecure(true)
+        .folder("db")
+        .folders(["db/users", "db/billing"])
+        .schema_dir("tables")
+        .event_dir("triggers")
+        .migration_dir("changes")
+        .lock_timeout_ms(1000)
+        .lock_ttl_ms(60000)
+        .concurrency_control(ConcurrencyControl::VersionCheck)
+        .force_schema(true)
+        .idempotent_defines(true)
+        .schema_concurrency(4)
+        .strict_schema(true)
+        .continue_on_error(true)
+        .allow_checksum_mismatch(true)
+        .migration_timeout(Duration::from_secs(60))
+        .migration_retries(2)
+        .migration_retry_delay_ms(50)
+        .create_down_script(true)
+        .version_format(VersionFormat::Sequential)
+        .create_ns_db_if_missing(true)
+        .health_check("RETURN session::db()", json!(["database"]))
+        .min_server_version("1.0.0")
+        .applied_by("admin")
+        .applied_from_host("server-1")
+        .enabled_tags(["analytics", "reporting"])
+        .run_seeds(true)
+        .validate_before_apply(true)
+        .skip(["20230101_000000_AddTestData"])
+        .log_statements(true)
+        .redacted_vars(["ADMIN_PASSWORD"])
+        .build();
+
+    ensure!(configuration.url == Some("localhost:8001".to_string()));
+    ensure!(configuration.ns == Some("namespace".to_string()));
+    ensure!(configuration.db == Some("database".to_string()));
+    ensure!(configuration.username == Some("admin".to_string()));
+    ensure!(configuration.password == Some("password".to_string()));
+    ensure!(configuration.auth_level == Some(AuthLevel::Database));
+    ensure!(configuration.token == Some("token".to_string()));
+    ensure!(configuration.scope == Some("migrator".to_string()));
+    ensure!(
+        configuration.scope_params == Some(serde_json::json!({ "email": "admin@example.com" }))
+    );
+    ensure!(configuration.migrations_table == Some("_my_app_migrations".to_string()));
+    ensure!(configuration.script_name_field == Some("migration_name".to_string()));
+    ensure!(configuration.executed_at_field == Some("ran_at".to_string()));
+    ensure!(configuration.executed_at_format == ExecutedAtFormat::EpochMillis);
+    ensure!(!configuration.transactional);
+    ensure!(configuration.statement_progress);
+    ensure!(configuration.connect_retries == Some(3));
+    ensure!(configuration.connect_retry_delay_ms == Some(100));
+    ensure!(configuration.connect_timeout == Some(Duration::from_secs(5)));
+    ensure!(configuration.tls_ca_cert == Some("certs/ca.pem".into()));
+    ensure!(configuration.tls_insecure);
+    ensure!(configuration.folder == Some("db".into()));
+    ensure!(configuration.folders == vec![PathBuf::from("db/users"), PathBuf::from("db/billing")]);
+    ensure!(configuration.schema_dir == Some("tables".to_string()));
+    ensure!(configuration.event_dir == Some("triggers".to_string()));
+    ensure!(configuration.migration_dir == Some("changes".to_string()));
+    ensure!(configuration.lock_timeout_ms == Some(1000));
+    ensure!(configuration.lock_ttl_ms == Some(60000));
+    ensure!(configuration.concurrency_control == ConcurrencyControl::VersionCheck);
+    ensure!(configuration.force_schema);
+    ensure!(configuration.idempotent_defines);
+    ensure!(configuration.schema_concurrency == 4);
+    ensure!(configuration.strict_schema);
+    ensure!(configuration.continue_on_error);
+    ensure!(configuration.allow_checksum_mismatch);
+    ensure!(configuration.migration_timeout == Some(Duration::from_secs(60)));
+    ensure!(configuration.migration_retries == Some(2));
+    ensure!(configuration.migration_retry_delay_ms == Some(50));
+    ensure!(configuration.create_down_script);
+    ensure!(configuration.version_format == VersionFormat::Sequential);
+    ensure!(configuration.create_ns_db_if_missing);
+    ensure!(
+        configuration.health_check
+            == Some(HealthCheck {
+                query: "RETURN session::db()".to_string(),
+                expected: json!(["database"]),
+            })
+    );
+    ensure!(configuration.min_server_version == Some("1.0.0".to_string()));
+    ensure!(configuration.applied_by == Some("admin".to_string()));
+    ensure!(configuration.applied_from_host == Some("server-1".to_string()));
+    ensure!(configuration.enabled_tags == vec!["analytics", "reporting"]);
+    ensure!(configuration.run_seeds);
+    ensure!(configuration.validate_before_apply);
+    ensure!(configuration.skip == vec!["20230101_000000_AddTestData"]);
+    ensure!(configuration.log_statements);
+    ensure!(configuration.redacted_vars == vec!["ADMIN_PASSWORD"]);
+
+    Ok(())
+}
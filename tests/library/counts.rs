@@ -0,0 +1,128 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{MigrationCounts, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn counts_without_applied_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let counts = SurrealdbMigrations::new(configuration).counts().await?;
+
+            ensure!(
+                counts
+                    == MigrationCounts {
+                        applied: 0,
+                        pending: 3,
+                        total: 3,
+                    }
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn counts_with_applied_and_pending_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            apply_migrations_up_to(&first_migration_name)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let counts = SurrealdbMigrations::new(configuration).counts().await?;
+
+            ensure!(
+                counts
+                    == MigrationCounts {
+                        applied: 1,
+                        pending: 2,
+                        total: 3,
+                    }
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn counts_with_orphaned_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let first_migration_file = get_first_migration_file()?;
+            std::fs::remove_file(first_migration_file)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let counts = SurrealdbMigrations::new(configuration).counts().await?;
+
+            ensure!(
+                counts
+                    == MigrationCounts {
+                        applied: 3,
+                        pending: 0,
+                        total: 2,
+                    }
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn counts_with_migration_recorded_twice() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let first_migration_name = get_first_migration_name()?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query(
+                    "CREATE script_migration SET script_name = $name, executed_at = time::now();",
+                )
+                .bind(("name", &first_migration_name))
+                .await?
+                .check()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let counts = SurrealdbMigrations::new(configuration).counts().await?;
+
+            ensure!(
+                counts
+                    == MigrationCounts {
+                        applied: 4,
+                        pending: 0,
+                        total: 3,
+                    }
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
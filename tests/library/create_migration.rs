@@ -0,0 +1,97 @@
+use anyhow::{ensure, Context, Result};
+use chrono::Local;
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations, VersionFormat};
+
+use crate::helpers::*;
+
+#[test]
+#[serial]
+fn create_migration_file() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_empty_template()?;
+
+    let now = Local::now();
+
+    let configuration = SurrealdbConfiguration::default();
+    let path = SurrealdbMigrations::new(configuration).create_migration("AddAdminUser")?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .context("Cannot get name of the migration file")?;
+
+    ensure!(path.parent() == Some(std::path::Path::new("./tests-files/migrations")));
+    ensure!(file_name.starts_with(&now.format("%Y%m%d_%H%M").to_string()));
+    ensure!(file_name.ends_with("_AddAdminUser.surql"));
+    ensure!(std::fs::read_to_string(&path)?.is_empty());
+
+    let down_path = path.with_file_name(file_name.replace(".surql", ".down.surql"));
+    ensure!(!down_path.exists());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn create_migration_file_with_down_script() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_empty_template()?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .create_down_script(true)
+        .build();
+    let path = SurrealdbMigrations::new(configuration).create_migration("AddAdminUser")?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .context("Cannot get name of the migration file")?;
+
+    let down_path = path.with_file_name(file_name.replace(".surql", ".down.surql"));
+
+    ensure!(path.exists());
+    ensure!(down_path.exists());
+    ensure!(std::fs::read_to_string(&down_path)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn create_sequential_migration_files() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_empty_template()?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .version_format(VersionFormat::Sequential)
+        .build();
+    let runner = SurrealdbMigrations::new(configuration);
+
+    let first_path = runner.create_migration("AddAdminUser")?;
+    let second_path = runner.create_migration("AddPost")?;
+
+    ensure!(first_path.ends_with("0001_AddAdminUser.surql"));
+    ensure!(second_path.ends_with("0002_AddPost.surql"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn cannot_create_migration_with_invalid_name() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_empty_template()?;
+
+    let configuration = SurrealdbConfiguration::default();
+    let result = SurrealdbMigrations::new(configuration).create_migration("Add/AdminUser");
+
+    let error = result.unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Invalid migration name 'Add/AdminUser': the character '/' is not allowed in a file name"
+    );
+
+    Ok(())
+}
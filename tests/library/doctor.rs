@@ -0,0 +1,131 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{
+    DiagnosticCheckKind, DiagnosticReport, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+fn get_check_details(report: &DiagnosticReport, kind: DiagnosticCheckKind) -> Option<Vec<String>> {
+    report
+        .failed_checks()
+        .into_iter()
+        .find(|check| check.kind == kind)
+        .map(|check| check.details.clone())
+}
+
+#[tokio::test]
+#[serial]
+async fn doctor_after_applying_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let report = runner.doctor().await?;
+
+            ensure!(report.checks.len() == 6);
+            ensure!(report.is_healthy());
+            ensure!(report.failed_checks().is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn doctor_detects_missing_migrations_table() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let report = SurrealdbMigrations::new(configuration).doctor().await?;
+
+            ensure!(!report.is_healthy());
+            ensure!(report.failed_checks().len() == 1);
+            ensure!(
+                get_check_details(&report, DiagnosticCheckKind::MigrationsTable)
+                    == Some(vec![
+                        "The migrations table 'script_migration' does not exist".to_string()
+                    ])
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn doctor_detects_orphaned_and_edited_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let first_migration_file = get_first_migration_file()?;
+            std::fs::remove_file(first_migration_file)?;
+
+            let last_migration_name = get_last_migration_name()?;
+            let last_migration_file =
+                format!("tests-files/migrations/{}.surql", last_migration_name);
+            let content = std::fs::read_to_string(&last_migration_file)?;
+            std::fs::write(&last_migration_file, content + "\nCREATE post;")?;
+
+            let report = runner.doctor().await?;
+
+            ensure!(report.failed_checks().len() == 2);
+            ensure!(
+                get_check_details(&report, DiagnosticCheckKind::OrphanedMigrations)
+                    == Some(vec![first_migration_name])
+            );
+            ensure!(
+                get_check_details(&report, DiagnosticCheckKind::ChecksumMismatches)
+                    == Some(vec![last_migration_name])
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn doctor_detects_connection_failure() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .url("ftp://localhost:8000")
+        .build();
+    let report = SurrealdbMigrations::new(configuration).doctor().await?;
+
+    ensure!(
+        get_check_details(&report, DiagnosticCheckKind::Connection)
+            == Some(vec![
+                "Unsupported scheme 'ftp' in url 'ftp://localhost:8000', expected one of: ws, wss, http, https".to_string()
+            ])
+    );
+    ensure!(!report
+        .failed_checks()
+        .iter()
+        .any(|check| check.kind == DiagnosticCheckKind::Folders));
+    ensure!(report.failed_checks().len() == 5);
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{
+    ListOrder, MigrationError, SchemaDefinitionKind, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn revert_last_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let last_migration_name = get_last_migration_name()?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            runner.down().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 2);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != last_migration_name));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_revert_if_no_down_script() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let result = runner.down().await;
+
+            ensure!(result.is_err());
+
+            let last_migration_name = get_last_migration_name()?;
+            let error = result.unwrap_err();
+
+            ensure!(
+                error.to_string()
+                    == format!(
+                        "The following migrations have no down script: {}",
+                        last_migration_name
+                    )
+            );
+            ensure!(matches!(
+                error,
+                MigrationError::MissingDownScript { migrations } if migrations == vec![last_migration_name]
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_revert_if_no_migration_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let result = runner.down().await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == "Cannot revert migration: no migration has been applied yet"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn revert_last_migration_with_up_file() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let last_migration_name = get_last_migration_name()?;
+            rename_to_up_migration_file(&last_migration_name)?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .any(|migration| migration.script_name == last_migration_name));
+
+            runner.down().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn revert_migration_removes_events_it_introduced() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up_to(&migration_names[0]).await?;
+
+            std::fs::write(
+                "tests-files/events/archive_post.surql",
+                "DEFINE TABLE archive_post SCHEMALESS;
+DEFINE EVENT archive_post ON TABLE archive_post WHEN $before == NONE THEN (
+    UPDATE post SET status = 'ARCHIVED' WHERE id = $after.post_id
+);",
+            )?;
+
+            runner.up().await?;
+
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+            add_down_migration_file(&migration_names[1], "DELETE post;")?;
+
+            // The event was introduced by the first migration applied along with it
+            runner.down().await?;
+            ensure!(runner.schema_diff().await?.is_empty());
+
+            runner.down().await?;
+
+            let schema_diff = runner.schema_diff().await?;
+            ensure!(schema_diff.added.len() == 1);
+            ensure!(schema_diff.added[0].kind == SchemaDefinitionKind::Event);
+            ensure!(schema_diff.added[0].name == "archive_post");
+
+            // The event file is applied again with the next migrations
+            runner.up().await?;
+            ensure!(runner.schema_diff().await?.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn revert_migration_removes_events_with_quotes_in_names() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+            let quoted_migration_name = format!("{}'s", migration_names[1]);
+            std::fs::rename(
+                format!("tests-files/migrations/{}.surql", migration_names[1]),
+                format!("tests-files/migrations/{}.surql", quoted_migration_name),
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up_to(&migration_names[0]).await?;
+
+            std::fs::write(
+                "tests-files/events/post's_archive.surql",
+                "DEFINE TABLE archive_post SCHEMALESS;
+DEFINE EVENT archive_post ON TABLE archive_post WHEN $before == NONE THEN (
+    UPDATE post SET status = 'ARCHIVED' WHERE id = $after.post_id
+);",
+            )?;
+
+            runner.up().await?;
+            ensure!(runner.schema_diff().await?.is_empty());
+
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+            add_down_migration_file(&quoted_migration_name, "DELETE post;")?;
+
+            runner.down().await?;
+            runner.down().await?;
+
+            let schema_diff = runner.schema_diff().await?;
+            ensure!(schema_diff.added.len() == 1);
+            ensure!(schema_diff.added[0].kind == SchemaDefinitionKind::Event);
+            ensure!(schema_diff.added[0].name == "archive_post");
+
+            Ok(())
+        })
+    })
+    .await
+}
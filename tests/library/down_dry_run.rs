@@ -0,0 +1,63 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{
+    ListOrder, MigrationError, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn return_down_queries_without_reverting() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let last_migration_name = get_last_migration_name()?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            let queries = runner.down_dry_run().await?;
+
+            ensure!(queries == vec!["DELETE comment;".to_string()]);
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_return_down_queries_if_no_down_script() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let result = runner.down_dry_run().await;
+
+            let last_migration_name = get_last_migration_name()?;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::MissingDownScript { migrations }) if migrations == vec![last_migration_name]
+            ));
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,79 @@
+use anyhow::{ensure, Context, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn revert_to_first_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let migration_names = get_migration_names()?;
+            add_down_migration_file(&migration_names[1], "DELETE post;")?;
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+
+            let first_migration_name = get_first_migration_name()?;
+            runner.down_to(&first_migration_name).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 1);
+
+            let first_migration = migrations_applied
+                .first()
+                .context("Cannot get first migration")?;
+
+            ensure!(first_migration.script_name == first_migration_name);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_revert_if_any_down_script_is_missing() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let migration_names = get_migration_names()?;
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let result = runner.down_to(&first_migration_name).await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "The following migrations have no down script: {}",
+                        migration_names[1]
+                    )
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,73 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use std::path::Path;
+use surrealdb_migrations::{SchemaDefinitionKind, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn export_schema() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+            runner
+                .export_schema(Path::new("tests-files/export"))
+                .await?;
+
+            for table in ["comment", "post", "script_migration", "user"] {
+                ensure!(is_file_exists(&format!(
+                    "tests-files/export/schemas/{}.surql",
+                    table
+                ))?);
+            }
+
+            let configuration = SurrealdbConfiguration::builder()
+                .folder("tests-files/export")
+                .build();
+            let schema_diff = SurrealdbMigrations::new(configuration)
+                .schema_diff()
+                .await?;
+
+            ensure!(schema_diff.added.is_empty());
+            ensure!(schema_diff.changed.is_empty());
+            ensure!(schema_diff
+                .removed
+                .iter()
+                .all(|definition| definition.kind == SchemaDefinitionKind::Event));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_export_schema_if_file_already_exists() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let result = runner.export_schema(Path::new("tests-files")).await;
+            ensure!(result.is_err());
+            ensure!(result
+                .unwrap_err()
+                .to_string()
+                .ends_with("comment.surql already exists"));
+
+            Ok(())
+        })
+    })
+    .await
+}
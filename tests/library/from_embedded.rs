@@ -0,0 +1,60 @@
+use anyhow::{ensure, Result};
+use include_dir::{include_dir, Dir};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+static BLOG_TEMPLATE_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates/blog");
+
+#[tokio::test]
+#[serial]
+async fn apply_embedded_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::from_embedded(&BLOG_TEMPLATE_DIR, configuration);
+
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_pending_embedded_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let pending_migrations =
+                SurrealdbMigrations::from_embedded(&BLOG_TEMPLATE_DIR, configuration)
+                    .pending()
+                    .await?;
+
+            ensure!(
+                pending_migrations
+                    == vec![
+                        "YYYYMMDD_HHMM01_AddAdminUser",
+                        "YYYYMMDD_HHMM02_AddPost",
+                        "YYYYMMDD_HHMM03_CommentPost",
+                    ]
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,71 @@
+use anyhow::{ensure, Context, Result};
+use serial_test::serial;
+use surrealdb_migrations::SurrealdbConfiguration;
+
+const ENV_VARS: [&str; 5] = [
+    "SURREALDB_URL",
+    "SURREALDB_NS",
+    "SURREALDB_DB",
+    "SURREALDB_USER",
+    "SURREALDB_PASS",
+];
+
+fn clear_env_vars() {
+    for name in ENV_VARS {
+        std::env::remove_var(name);
+    }
+}
+
+#[test]
+#[serial]
+fn from_env_with_default_values() -> Result<()> {
+    clear_env_vars();
+
+    let configuration = SurrealdbConfiguration::from_env()?;
+
+    ensure!(configuration.url.is_none());
+    ensure!(configuration.ns.is_none());
+    ensure!(configuration.db.is_none());
+    ensure!(configuration.username.is_none());
+    ensure!(configuration.password.is_none());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn from_env_with_all_variables() -> Result<()> {
+    clear_env_vars();
+    std::env::set_var("SURREALDB_URL", "localhost:8001");
+    std::env::set_var("SURREALDB_NS", "namespace");
+    std::env::set_var("SURREALDB_DB", "database");
+    std::env::set_var("SURREALDB_USER", "admin");
+    std::env::set_var("SURREALDB_PASS", "password");
+
+    let configuration = SurrealdbConfiguration::from_env();
+    clear_env_vars();
+    let configuration = configuration?;
+
+    ensure!(configuration.url == Some("localhost:8001".to_string()));
+    ensure!(configuration.ns == Some("namespace".to_string()));
+    ensure!(configuration.db == Some("database".to_string()));
+    ensure!(configuration.username == Some("admin".to_string()));
+    ensure!(configuration.password == Some("password".to_string()));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn from_env_fails_with_invalid_url() -> Result<()> {
+    clear_env_vars();
+    std::env::set_var("SURREALDB_URL", "local host:8000");
+
+    let result = SurrealdbConfiguration::from_env();
+    clear_env_vars();
+
+    let error = result.err().context("from_env should fail")?;
+    ensure!(error.to_string() == "Invalid url 'local host:8000' in SURREALDB_URL");
+
+    Ok(())
+}
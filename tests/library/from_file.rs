@@ -0,0 +1,111 @@
+use anyhow::{ensure, Context, Result};
+use serial_test::serial;
+use surrealdb_migrations::{AuthLevel, SurrealdbConfiguration};
+
+use crate::helpers::*;
+
+const CONFIGURATION_FILE_PATH: &str = "tests-files/surrealdb.json";
+
+fn write_configuration_file(content: &str) -> Result<()> {
+    clear_tests_files()?;
+    std::fs::create_dir_all("tests-files")?;
+    std::fs::write(CONFIGURATION_FILE_PATH, content)?;
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn from_file_with_all_fields() -> Result<()> {
+    write_configuration_file(
+        r#"{
+    "url": "localhost:8001",
+    "ns": "namespace",
+    "db": "database",
+    "username": "admin",
+    "password": "password",
+    "auth_level": "database",
+    "token": "token",
+    "scope": "migrator",
+    "scope_params": { "email": "admin@example.com" }
+}"#,
+    )?;
+
+    let configuration = SurrealdbConfiguration::from_file(CONFIGURATION_FILE_PATH)?;
+
+    ensure!(configuration.url == Some("localhost:8001".to_string()));
+    ensure!(configuration.ns == Some("namespace".to_string()));
+    ensure!(configuration.db == Some("database".to_string()));
+    ensure!(configuration.username == Some("admin".to_string()));
+    ensure!(configuration.password == Some("password".to_string()));
+    ensure!(configuration.auth_level == Some(AuthLevel::Database));
+    ensure!(configuration.token == Some("token".to_string()));
+    ensure!(configuration.scope == Some("migrator".to_string()));
+    ensure!(
+        configuration.scope_params == Some(serde_json::json!({ "email": "admin@example.com" }))
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn from_file_with_missing_fields() -> Result<()> {
+    write_configuration_file(r#"{ "username": "admin", "password": "password" }"#)?;
+
+    let configuration = SurrealdbConfiguration::from_file(CONFIGURATION_FILE_PATH)?;
+
+    ensure!(configuration.url.is_none());
+    ensure!(configuration.ns.is_none());
+    ensure!(configuration.db.is_none());
+    ensure!(configuration.username == Some("admin".to_string()));
+    ensure!(configuration.password == Some("password".to_string()));
+    ensure!(configuration.auth_level.is_none());
+    ensure!(configuration.token.is_none());
+    ensure!(configuration.scope.is_none());
+    ensure!(configuration.transactional);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn from_file_fails_with_malformed_file() -> Result<()> {
+    write_configuration_file(r#"{ "username": "admin", "password": }"#)?;
+
+    let result = SurrealdbConfiguration::from_file(CONFIGURATION_FILE_PATH);
+
+    let error = result.err().context("from_file should fail")?;
+    ensure!(
+        format!("{:#}", error)
+            == "Cannot parse the configuration file 'tests-files/surrealdb.json': expected value at line 1 column 36"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn from_file_fails_with_unknown_field() -> Result<()> {
+    write_configuration_file(r#"{ "user": "admin" }"#)?;
+
+    let result = SurrealdbConfiguration::from_file(CONFIGURATION_FILE_PATH);
+
+    let error = result.err().context("from_file should fail")?;
+    ensure!(format!("{:#}", error).contains("unknown field `user`"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn from_file_fails_if_file_does_not_exist() -> Result<()> {
+    clear_tests_files()?;
+
+    let result = SurrealdbConfiguration::from_file(CONFIGURATION_FILE_PATH);
+
+    let error = result.err().context("from_file should fail")?;
+    ensure!(error.to_string() == "Cannot read the configuration file 'tests-files/surrealdb.json'");
+
+    Ok(())
+}
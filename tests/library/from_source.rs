@@ -0,0 +1,200 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use futures::future::{BoxFuture, FutureExt};
+use serial_test::serial;
+use std::collections::HashMap;
+use surrealdb_migrations::{
+    FolderSource, ListOrder, MigrationSource, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+const BLOG_TEMPLATE_PATH: &str = "templates/blog";
+
+/// Migration files kept in memory, by folder name then file name.
+struct InMemorySource(HashMap<String, HashMap<String, String>>);
+
+impl MigrationSource for InMemorySource {
+    fn list_files<'a>(&'a self, dir_name: &'a str) -> BoxFuture<'a, Result<Vec<String>>> {
+        let file_names = match self.0.get(dir_name) {
+            Some(files) => files.keys().cloned().collect(),
+            None => Vec::new(),
+        };
+        async move { Ok(file_names) }.boxed()
+    }
+
+    fn read_file<'a>(
+        &'a self,
+        dir_name: &'a str,
+        file_name: &'a str,
+    ) -> BoxFuture<'a, Result<String>> {
+        let content = self
+            .0
+            .get(dir_name)
+            .and_then(|files| files.get(file_name))
+            .cloned();
+        async move { content.ok_or_else(|| anyhow!("File '{}' not found", file_name)) }.boxed()
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_migrations_from_folder_source() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let source = FolderSource::new(BLOG_TEMPLATE_PATH);
+            let runner = SurrealdbMigrations::from_source(source, configuration);
+
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_migrations_from_custom_source() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+
+            let source = InMemorySource(HashMap::from([
+                (
+                    "schemas".to_string(),
+                    HashMap::from([(
+                        "item.surql".to_string(),
+                        "DEFINE TABLE item SCHEMALESS;".to_string(),
+                    )]),
+                ),
+                (
+                    "migrations".to_string(),
+                    HashMap::from([
+                        (
+                            "20230101_000002_AddSecondItem.surql".to_string(),
+                            "CREATE item:second;".to_string(),
+                        ),
+                        (
+                            "20230101_000001_AddFirstItem.surql".to_string(),
+                            "CREATE item:first;".to_string(),
+                        ),
+                    ]),
+                ),
+            ]));
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::from_source(source, configuration);
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(
+                pending_migrations
+                    == vec![
+                        "20230101_000001_AddFirstItem",
+                        "20230101_000002_AddSecondItem",
+                    ]
+            );
+
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[test]
+fn cannot_create_migration_in_source() -> Result<()> {
+    let configuration = SurrealdbConfiguration::default();
+    let source = FolderSource::new(BLOG_TEMPLATE_PATH);
+    let result =
+        SurrealdbMigrations::from_source(source, configuration).create_migration("AddUser");
+
+    let error = result.err().context("create_migration should fail")?;
+    ensure!(error.to_string() == "Cannot create migration 'AddUser' in a migration source");
+
+    Ok(())
+}
+
+#[cfg(feature = "archive-source")]
+const ARCHIVE_PATH: &str = "tests-files/migrations.tar.gz";
+
+/// Create a tar.gz archive containing the files of a folder, under a path prefix.
+#[cfg(feature = "archive-source")]
+fn create_archive(folder: &str, prefix: &str) -> Result<()> {
+    std::fs::create_dir_all("tests-files")?;
+
+    let archive_file = std::fs::File::create(ARCHIVE_PATH)?;
+    let encoder = flate2::write::GzEncoder::new(archive_file, flate2::Compression::default());
+
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_dir_all(prefix, folder)?;
+    builder.into_inner()?.finish()?;
+
+    Ok(())
+}
+
+#[cfg(feature = "archive-source")]
+#[tokio::test]
+#[serial]
+async fn apply_migrations_from_archive_source() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            create_archive(BLOG_TEMPLATE_PATH, ".")?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let source = surrealdb_migrations::ArchiveSource::new(ARCHIVE_PATH);
+            let runner = SurrealdbMigrations::from_source(source, configuration);
+
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[cfg(feature = "archive-source")]
+#[tokio::test]
+#[serial]
+async fn cannot_apply_migrations_from_archive_with_unexpected_structure() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            create_archive(BLOG_TEMPLATE_PATH, "bundle")?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let source = surrealdb_migrations::ArchiveSource::new(ARCHIVE_PATH);
+            let result = SurrealdbMigrations::from_source(source, configuration)
+                .up()
+                .await;
+
+            let error = result.err().ok_or_else(|| anyhow!("up should fail"))?;
+            ensure!(format!("{:#}", error).starts_with(&format!(
+                "Invalid archive '{}': The file 'bundle/",
+                ARCHIVE_PATH
+            )));
+
+            Ok(())
+        })
+    })
+    .await
+}
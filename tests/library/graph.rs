@@ -0,0 +1,117 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn graph_with_applied_and_pending_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up_to(&first_migration_name).await?;
+
+            let graph = runner.graph().await?;
+
+            let names = graph
+                .nodes
+                .iter()
+                .map(|node| node.name.to_string())
+                .collect::<Vec<_>>();
+            ensure!(names == get_migration_names()?);
+
+            let first_node = &graph.nodes[0];
+            ensure!(first_migration_name.starts_with(&format!("{}_", first_node.version)));
+            ensure!(first_node.is_applied);
+            ensure!(first_node.executed_at.is_some());
+            ensure!(first_node.checksum.is_some());
+            ensure!(first_node.applied_checksum == first_node.checksum);
+            ensure!(first_node.depends_on.is_none());
+
+            let second_node = &graph.nodes[1];
+            ensure!(!second_node.is_applied);
+            ensure!(second_node.executed_at.is_none());
+            ensure!(second_node.checksum.is_some());
+            ensure!(second_node.applied_checksum.is_none());
+            ensure!(second_node.depends_on == Some(first_migration_name));
+
+            ensure!(graph.nodes[2].depends_on == Some(second_node.name.to_string()));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn graph_with_orphaned_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migration_names = get_migration_names()?;
+            let first_migration_file = get_first_migration_file()?;
+            std::fs::remove_file(first_migration_file)?;
+
+            let graph = runner.graph().await?;
+
+            let names = graph
+                .nodes
+                .iter()
+                .map(|node| node.name.to_string())
+                .collect::<Vec<_>>();
+            ensure!(names == migration_names);
+            ensure!(graph.nodes.iter().all(|node| node.is_applied));
+
+            let orphaned_node = &graph.nodes[0];
+            ensure!(orphaned_node.checksum.is_none());
+            ensure!(orphaned_node.applied_checksum.is_some());
+            ensure!(graph.nodes[1].depends_on == Some(orphaned_node.name.to_string()));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+#[serial]
+async fn graph_serialized_to_json() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let graph = runner.graph().await?;
+
+            let json = serde_json::to_value(&graph)?;
+
+            ensure!(json["nodes"][0]["name"] == get_first_migration_name()?.as_str());
+            ensure!(json["nodes"][0]["is_applied"] == true);
+            ensure!(json["nodes"][0]["checksum"].is_string());
+            ensure!(json["nodes"][0]["depends_on"].is_null());
+            ensure!(json["nodes"][1]["depends_on"] == get_first_migration_name()?.as_str());
+
+            Ok(())
+        })
+    })
+    .await
+}
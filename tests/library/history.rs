@@ -0,0 +1,101 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{MigrationOperation, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn record_applied_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let history = runner.history().await?;
+
+            ensure!(history.len() == 3);
+            ensure!(history
+                .iter()
+                .all(|event| event.operation == MigrationOperation::Applied));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn record_rolled_back_and_reapplied_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let last_migration_name = get_last_migration_name()?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            runner.down().await?;
+            runner.up().await?;
+
+            let history = runner.history().await?;
+
+            ensure!(history.len() == 5);
+            ensure!(history[3].script_name == last_migration_name);
+            ensure!(history[3].operation == MigrationOperation::RolledBack);
+            ensure!(history[4].script_name == last_migration_name);
+            ensure!(history[4].operation == MigrationOperation::Reapplied);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn record_migration_with_quote_in_name() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            let migration_name = "99991231_235959_AddO'BrienUser";
+            std::fs::write(
+                format!("tests-files/migrations/{}.surql", migration_name),
+                "CREATE user:obrien;",
+            )?;
+            add_down_migration_file(migration_name, "DELETE user:obrien;")?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+            runner.down().await?;
+            runner.up().await?;
+
+            let history = runner.history().await?;
+
+            ensure!(history.len() == 3);
+            ensure!(history
+                .iter()
+                .all(|event| event.script_name == migration_name));
+            ensure!(history[2].operation == MigrationOperation::Reapplied);
+
+            runner.unmark(migration_name).await?;
+            ensure!(runner.pending().await? == vec![migration_name.to_string()]);
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,42 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn apply_in_memory() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let configuration = SurrealdbConfiguration::in_memory();
+    let runner = SurrealdbMigrations::new(configuration);
+
+    runner.up().await?;
+
+    let migrations_applied = runner.list(ListOrder::default()).await?;
+    ensure!(migrations_applied.len() == 3);
+
+    let pending_migrations = runner.pending().await?;
+    ensure!(pending_migrations.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn in_memory_databases_are_isolated() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let runner = SurrealdbMigrations::new(SurrealdbConfiguration::in_memory());
+    runner.up().await?;
+
+    let other_runner = SurrealdbMigrations::new(SurrealdbConfiguration::in_memory());
+    let migrations_applied = other_runner.list(ListOrder::default()).await?;
+
+    ensure!(migrations_applied.is_empty());
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use anyhow::{ensure, Context, Result};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Duration, Local, Utc};
+use futures::TryStreamExt;
 use serial_test::serial;
-use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+use surrealdb_migrations::{
+    ExecutedAtFormat, ListOrder, ScriptMigration, SurrealdbConfiguration, SurrealdbMigrations,
+    VersionFormat,
+};
 
 use crate::helpers::*;
 
@@ -15,7 +19,9 @@ async fn list_empty_migrations() -> Result<()> {
             apply_migrations()?;
 
             let configuration = SurrealdbConfiguration::default();
-            let migrations_applied = SurrealdbMigrations::new(configuration).list().await?;
+            let migrations_applied = SurrealdbMigrations::new(configuration)
+                .list(ListOrder::default())
+                .await?;
 
             ensure!(migrations_applied.len() == 0);
 
@@ -37,7 +43,9 @@ async fn list_blog_migrations() -> Result<()> {
             apply_migrations()?;
 
             let configuration = SurrealdbConfiguration::default();
-            let migrations_applied = SurrealdbMigrations::new(configuration).list().await?;
+            let migrations_applied = SurrealdbMigrations::new(configuration)
+                .list(ListOrder::default())
+                .await?;
 
             ensure!(migrations_applied.len() == 3);
 
@@ -47,7 +55,7 @@ async fn list_blog_migrations() -> Result<()> {
             let now_timestamp_range = (now_timestamp - 2)..(now_timestamp + 2);
 
             let first_migration = migrations_applied
-                .get(0)
+                .first()
                 .context("Cannot get first migration")?;
 
             ensure!(first_migration.script_name == format!("{}01_AddAdminUser", date_prefix));
@@ -84,3 +92,372 @@ async fn list_blog_migrations() -> Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_with_duration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.duration_ms.is_some()));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_with_applied_by_and_host() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .applied_by("admin")
+                .applied_from_host("server-1")
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied.iter().all(|migration| {
+                migration.applied_by == Some("admin".to_string())
+                    && migration.applied_from_host == Some("server-1".to_string())
+            }));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_as_stream() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let migrations_applied = runner.list_stream().try_collect::<Vec<_>>().await?;
+
+            let script_names = migrations_applied
+                .into_iter()
+                .map(|migration| migration.script_name)
+                .collect::<Vec<_>>();
+
+            ensure!(script_names == get_migration_names()?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn last_applied_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            ensure!(runner.last_applied().await?.is_none());
+
+            runner.up().await?;
+
+            let last_applied = runner
+                .last_applied()
+                .await?
+                .context("Cannot get last applied migration")?;
+
+            ensure!(last_applied.script_name == get_last_migration_name()?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn get_applied_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let last_migration_name = get_last_migration_name()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up_to(&first_migration_name).await?;
+
+            let migration_applied = runner
+                .get_applied(&first_migration_name)
+                .await?
+                .context("Cannot get applied migration")?;
+
+            ensure!(migration_applied.script_name == first_migration_name);
+            ensure!(runner.get_applied(&last_migration_name).await?.is_none());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_between_dates() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let now = Utc::now();
+
+            let migrations_applied = runner
+                .list_between(now - Duration::hours(1), now + Duration::hours(1))
+                .await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let migrations_applied = runner
+                .list_between(now - Duration::days(2), now - Duration::days(1))
+                .await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_with_executed_at_as_epoch_millis() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let schema_path = "tests-files/schemas/script_migration.surql";
+            let schema = std::fs::read_to_string(schema_path)?.replace(
+                "TYPE datetime VALUE $before OR $value OR time::now()",
+                "TYPE int VALUE $before OR $value",
+            );
+            std::fs::write(schema_path, schema)?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .executed_at_format(ExecutedAtFormat::EpochMillis)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let mut response = client
+                .query("SELECT VALUE executed_at FROM script_migration;")
+                .await?;
+            let executed_at_values: Vec<serde_json::Value> = response.take(0)?;
+            ensure!(executed_at_values.iter().all(|value| value.is_i64()));
+
+            let now = Utc::now();
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            for migration in migrations_applied {
+                let executed_at = DateTime::parse_from_rfc3339(&migration.executed_at)?;
+                ensure!(now.signed_duration_since(executed_at) < Duration::hours(1));
+            }
+
+            let migrations_applied = runner
+                .list_between(now - Duration::hours(1), now + Duration::hours(1))
+                .await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let migrations_applied = runner
+                .list_between(now - Duration::days(2), now - Duration::days(1))
+                .await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_with_executed_at_as_iso_string() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let schema_path = "tests-files/schemas/script_migration.surql";
+            let schema = std::fs::read_to_string(schema_path)?.replace(
+                "TYPE datetime VALUE $before OR $value OR time::now()",
+                "TYPE string VALUE $before OR $value",
+            );
+            std::fs::write(schema_path, schema)?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .executed_at_format(ExecutedAtFormat::IsoString)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let mut response = client
+                .query("SELECT VALUE executed_at FROM script_migration;")
+                .await?;
+            let executed_at_values: Vec<String> = response.take(0)?;
+            ensure!(executed_at_values
+                .iter()
+                .all(|value| value.len() == "2023-03-17T15:32:01.000000Z".len()));
+
+            let now = Utc::now();
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            for migration in migrations_applied {
+                let executed_at = DateTime::parse_from_rfc3339(&migration.executed_at)?;
+                ensure!(now.signed_duration_since(executed_at) < Duration::hours(1));
+            }
+
+            let migrations_applied = runner
+                .list_between(now - Duration::hours(1), now + Duration::hours(1))
+                .await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let migrations_applied = runner
+                .list_between(now - Duration::days(2), now - Duration::days(1))
+                .await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_newest_first() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::ExecutionDateDesc).await?;
+
+            let names = migrations_applied
+                .iter()
+                .map(|migration| migration.script_name.to_string())
+                .collect::<Vec<_>>();
+            let mut expected_names = get_migration_names()?;
+            expected_names.reverse();
+            ensure!(names == expected_names);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_in_version_order() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write("tests-files/migrations/9_AddUser.surql", "CREATE user;")?;
+            std::fs::write("tests-files/migrations/10_AddPost.surql", "CREATE post;")?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .version_format(VersionFormat::Sequential)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let get_names = |migrations: Vec<ScriptMigration>| {
+                migrations
+                    .into_iter()
+                    .map(|migration| migration.script_name)
+                    .collect::<Vec<_>>()
+            };
+
+            let names = get_names(runner.list(ListOrder::VersionAsc).await?);
+            ensure!(names == vec!["9_AddUser", "10_AddPost"]);
+
+            let names = get_names(runner.list(ListOrder::VersionDesc).await?);
+            ensure!(names == vec!["10_AddPost", "9_AddUser"]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_with_quotes_in_applied_by_and_host() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .applied_by("o'brien")
+                .applied_from_host("server-'1'")
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied.iter().all(|migration| {
+                migration.applied_by == Some("o'brien".to_string())
+                    && migration.applied_from_host == Some("server-'1'".to_string())
+            }));
+
+            Ok(())
+        })
+    })
+    .await
+}
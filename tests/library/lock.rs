@@ -0,0 +1,238 @@
+use anyhow::{ensure, Result};
+use serde::Deserialize;
+use serial_test::serial;
+use surrealdb_migrations::{
+    ListOrder, MigrationError, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+#[derive(Deserialize)]
+struct MigrationLock {
+    owner: String,
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_release_lock() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            SurrealdbMigrations::new(configuration).up().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let locks: Vec<MigrationLock> = client.select("script_migration_lock").await?;
+
+            ensure!(locks.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_lock_is_held() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query("CREATE script_migration_lock:lock SET owner = 'other', locked_at = time::now();")
+                .await?
+                .check()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .lock_timeout_ms(200)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+
+            ensure!(matches!(
+                result,
+                Err(MigrationError::LockTimeout { owner, timeout_ms: 200 }) if owner == "other"
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            let locks: Vec<MigrationLock> = client.select("script_migration_lock").await?;
+            ensure!(locks.len() == 1);
+            ensure!(locks[0].owner == "other");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_take_over_stale_lock() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query("CREATE script_migration_lock:lock SET owner = 'crashed', locked_at = time::now() - 1h;")
+                .await?
+                .check()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .lock_timeout_ms(200)
+                .lock_ttl_ms(60_000)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let locks: Vec<MigrationLock> = client.select("script_migration_lock").await?;
+            ensure!(locks.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn force_release_lock_held_by_another_process() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query("CREATE script_migration_lock:lock SET owner = 'other', locked_at = time::now();")
+                .await?
+                .check()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .lock_timeout_ms(200)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.force_release_lock().await?;
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_revert_if_lock_is_held() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .lock_timeout_ms(200)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let last_migration_name = get_last_migration_name()?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query("CREATE script_migration_lock:lock SET owner = 'other', locked_at = time::now();")
+                .await?
+                .check()?;
+
+            let result = runner.down().await;
+
+            ensure!(matches!(
+                result,
+                Err(MigrationError::LockTimeout { owner, timeout_ms: 200 }) if owner == "other"
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn redo_should_release_lock() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let last_migration_name = get_last_migration_name()?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            runner.redo().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let locks: Vec<MigrationLock> = client.select("script_migration_lock").await?;
+
+            ensure!(locks.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn lock_is_named_after_migrations_table() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query("CREATE script_migration_lock:lock SET owner = 'other', locked_at = time::now();")
+                .await?
+                .check()?;
+
+            let configuration = SurrealdbConfiguration {
+                migrations_table: Some("_my_app_migrations".to_string()),
+                lock_timeout_ms: Some(200),
+                ..Default::default()
+            };
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let locks: Vec<MigrationLock> = client.select("_my_app_migrations_lock").await?;
+            ensure!(locks.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,92 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn mark_single_migration_as_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let migration_names = get_migration_names()?;
+            runner.mark_applied(&migration_names[1]).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 1);
+            ensure!(migrations_applied[0].script_name == migration_names[1]);
+            ensure!(migrations_applied[0].checksum.is_some());
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(
+                pending_migrations
+                    == vec![
+                        migration_names[0].to_string(),
+                        migration_names[2].to_string()
+                    ]
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_mark_missing_migration_as_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let result = SurrealdbMigrations::new(configuration)
+                .mark_applied("20230101_000000_MissingMigration")
+                .await;
+
+            ensure!(
+                result.unwrap_err().to_string()
+                    == "Cannot mark migration '20230101_000000_MissingMigration' as applied: this migration does not exist"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_mark_migration_already_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let first_migration_name = get_first_migration_name()?;
+            let result = SurrealdbMigrations::new(configuration)
+                .mark_applied(&first_migration_name)
+                .await;
+
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "Cannot mark migration '{}' as applied: this migration has already been applied",
+                        first_migration_name
+                    )
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,160 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn migrate_to_migration_ahead() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.migrate_to(&migration_names[1]).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 2);
+            ensure!(migrations_applied[1].script_name == migration_names[1]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn migrate_to_migration_behind() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+            add_down_migration_file(&migration_names[1], "DELETE post;")?;
+
+            runner.migrate_to(&migration_names[0]).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 1);
+            ensure!(migrations_applied[0].script_name == migration_names[0]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_migrate_to_missing_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            let result = runner.migrate_to("20200101_000000_Missing").await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == "Cannot migrate to migration '20200101_000000_Missing': this migration does not exist"
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn migrate_to_migration_with_older_pending_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner
+                .up_range(&migration_names[1], &migration_names[2])
+                .await?;
+
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+
+            runner.migrate_to(&migration_names[1]).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            let mut script_names = migrations_applied
+                .into_iter()
+                .map(|migration| migration.script_name)
+                .collect::<Vec<_>>();
+            script_names.sort();
+
+            ensure!(script_names == migration_names[..2]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_migrate_to_migration_if_applied_out_of_order() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner
+                .up_range(&migration_names[2], &migration_names[2])
+                .await?;
+            runner
+                .up_range(&migration_names[0], &migration_names[0])
+                .await?;
+
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+
+            let result = runner.migrate_to(&migration_names[1]).await;
+
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "Cannot migrate to migration '{}': migration '{}' has been applied after '{}', which comes after it",
+                        migration_names[1], migration_names[0], migration_names[2]
+                    )
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -1,4 +1,46 @@
+mod apply_schemas_only;
+mod baseline;
+mod builder;
+mod counts;
+mod create_migration;
+mod doctor;
+mod down;
+mod down_dry_run;
+mod down_to;
+mod export_schema;
+mod from_embedded;
+mod from_env;
+mod from_file;
+mod from_source;
+mod from_url;
+mod graph;
+mod history;
+#[cfg(feature = "in-memory")]
+mod in_memory;
 mod list;
+mod lock;
+mod mark_applied;
+mod migrate_to;
+mod pending;
+mod project_config;
+mod redo;
+mod refresh_checksums;
+mod reset;
+mod resolved_config;
+mod run_script;
+mod schema_diff;
+mod seed;
+mod squash;
+mod status;
+mod unmark;
 mod up;
+mod up_all;
+mod up_dry_run;
+mod up_range;
+mod up_script;
+mod up_step;
 mod up_to;
+mod up_until_deadline;
+mod validate_offline;
 mod validate_version_order;
+mod verify;
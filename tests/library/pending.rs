@@ -0,0 +1,143 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn list_all_migrations_if_none_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let pending_migrations = SurrealdbMigrations::new(configuration).pending().await?;
+
+            ensure!(pending_migrations == get_migration_names()?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_not_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            apply_migrations_up_to(&first_migration_name)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let pending_migrations = SurrealdbMigrations::new(configuration).pending().await?;
+
+            let migration_names = get_migration_names()?;
+            ensure!(pending_migrations == migration_names[1..]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn fails_if_applied_migration_file_is_missing() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let first_migration_file = get_first_migration_file()?;
+            std::fs::remove_file(first_migration_file)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let result = SurrealdbMigrations::new(configuration).pending().await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "The following migrations have been applied but their file is missing: {}",
+                        first_migration_name
+                    )
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn fails_if_migration_defined_by_several_files() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let first_migration_file = get_first_migration_file()?;
+            std::fs::copy(
+                &first_migration_file,
+                format!("tests-files/migrations/{}.up.surql", first_migration_name),
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let result = SurrealdbMigrations::new(configuration).pending().await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "Migration '{0}' is defined by both '{0}.surql' and '{0}.up.surql'",
+                        first_migration_name
+                    )
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn list_migrations_not_applied_with_content() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            apply_migrations_up_to(&first_migration_name)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let pending_migrations = SurrealdbMigrations::new(configuration)
+                .list_pending_with_content()
+                .await?;
+
+            let migration_names = get_migration_names()?;
+            let pending_migration_names = pending_migrations
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>();
+            ensure!(pending_migration_names == migration_names[1..]);
+
+            let (_, content) = &pending_migrations[0];
+            ensure!(content.contains("CREATE post"));
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,16 @@
+use anyhow::{ensure, Result};
+use surrealdb_migrations::ProjectConfig;
+
+#[test]
+fn load_project_config() -> Result<()> {
+    let project_config = ProjectConfig::load();
+
+    ensure!(project_config.path == Some("./tests-files".to_string()));
+    ensure!(project_config.url == Some("localhost:8000".to_string()));
+    ensure!(project_config.username == Some("root".to_string()));
+    ensure!(project_config.password == Some("root".to_string()));
+    ensure!(project_config.ns == Some("test".to_string()));
+    ensure!(project_config.db == Some("test".to_string()));
+
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use surrealdb_migrations::{
+    ListOrder, MigrationError, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn redo_last_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let last_migration_name = get_last_migration_name()?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            let migrations_applied_before = runner.list(ListOrder::default()).await?;
+
+            runner.redo().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+
+            let last_migration_applied = migrations_applied.last().unwrap();
+            let last_migration_applied_before = migrations_applied_before.last().unwrap();
+
+            ensure!(last_migration_applied.script_name == last_migration_name);
+            ensure!(
+                last_migration_applied.executed_at != last_migration_applied_before.executed_at
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_redo_if_no_down_script() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let result = runner.redo().await;
+
+            ensure!(result.is_err());
+
+            let last_migration_name = get_last_migration_name()?;
+
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "Cannot redo migration '{}': it has no down script to revert it first",
+                        last_migration_name
+                    )
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn redo_last_migration_with_vars() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            let migration_name = "99991231_235959_AddAdmin";
+            std::fs::write(
+                format!("tests-files/migrations/{}.surql", migration_name),
+                "CREATE user:admin SET email = '${ADMIN_EMAIL}';",
+            )?;
+            add_down_migration_file(migration_name, "DELETE user:admin;")?;
+
+            let vars =
+                HashMap::from([("ADMIN_EMAIL".to_string(), "admin@example.com".to_string())]);
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up_with_vars(vars.clone()).await?;
+            runner.redo_with_vars(vars).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 1);
+            ensure!(migrations_applied[0].script_name == migration_name);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn redo_last_migration_keeps_applied_by_and_host() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .applied_by("admin")
+                .applied_from_host("server-1")
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let last_migration_name = get_last_migration_name()?;
+            add_down_migration_file(&last_migration_name, "DELETE comment;")?;
+
+            runner.redo().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied.iter().all(|migration| {
+                migration.applied_by == Some("admin".to_string())
+                    && migration.applied_from_host == Some("server-1".to_string())
+            }));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn redo_last_migration_with_logged_statements() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            let migration_name = "99991231_235959_AddSettings";
+            std::fs::write(
+                format!("tests-files/migrations/{}.surql", migration_name),
+                "CREATE setting:admin SET email = '${ADMIN_EMAIL}', password = '${ADMIN_PASSWORD}';",
+            )?;
+            add_down_migration_file(migration_name, "DELETE setting:admin;")?;
+
+            let vars = HashMap::from([
+                ("ADMIN_EMAIL".to_string(), "admin@example.com".to_string()),
+                ("ADMIN_PASSWORD".to_string(), "s3cr3t".to_string()),
+            ]);
+
+            let messages = Arc::new(Mutex::new(Vec::new()));
+            let logger_messages = messages.clone();
+
+            let configuration = SurrealdbConfiguration::builder()
+                .log_statements(true)
+                .redacted_vars(["ADMIN_PASSWORD"])
+                .build();
+            let runner = SurrealdbMigrations::new(configuration).with_logger(move |message| {
+                logger_messages.lock().unwrap().push(message.to_string())
+            });
+
+            runner.up_with_vars(vars.clone()).await?;
+            messages.lock().unwrap().clear();
+
+            runner.redo_with_vars(vars).await?;
+
+            let messages = messages.lock().unwrap();
+
+            ensure!(messages.iter().any(|message| message
+                == "Executing statement:\nCREATE setting:admin SET email = 'admin@example.com', password = '***';"));
+            ensure!(!messages.iter().any(|message| message.contains("s3cr3t")));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn redo_should_retry_migration_failing_with_transient_error() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            let migration_name = "99991231_235959_AddPost";
+            std::fs::write(
+                format!("tests-files/migrations/{}.surql", migration_name),
+                "CREATE post:first;",
+            )?;
+            // The down script does not delete the post, so that the migration fails when re-applied
+            add_down_migration_file(migration_name, "UPDATE post:first SET reverted = true;")?;
+
+            let classified_errors = Arc::new(Mutex::new(0));
+            let classified_errors_clone = classified_errors.clone();
+
+            let configuration = SurrealdbConfiguration::builder()
+                .migration_retries(2)
+                .migration_retry_delay_ms(1)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration).with_transient_error_classifier(
+                move |_| {
+                    *classified_errors_clone.lock().unwrap() += 1;
+                    true
+                },
+            );
+
+            runner.up().await?;
+
+            let result = runner.redo().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::MigrationFailed { name, .. }) if name == migration_name
+            ));
+            ensure!(*classified_errors.lock().unwrap() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,45 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn refresh_checksums_of_edited_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let first_migration_file = get_first_migration_file()?;
+            let content = std::fs::read_to_string(&first_migration_file)?;
+            std::fs::write(&first_migration_file, content + "\n-- Fix a typo")?;
+
+            let checksum_mismatches = runner.verify().await?;
+            ensure!(checksum_mismatches.len() == 1);
+
+            let migrations_applied_before = runner.list(ListOrder::default()).await?;
+
+            runner.refresh_checksums().await?;
+
+            let checksum_mismatches = runner.verify().await?;
+            ensure!(checksum_mismatches.is_empty());
+
+            let migrations_applied_after = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied_after.len() == migrations_applied_before.len());
+            ensure!(migrations_applied_before
+                .iter()
+                .zip(migrations_applied_after.iter())
+                .all(|(before, after)| before.script_name == after.script_name
+                    && before.executed_at == after.executed_at));
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,72 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn revert_all_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let migration_names = get_migration_names()?;
+            add_down_migration_file(&migration_names[0], "DELETE user;")?;
+            add_down_migration_file(&migration_names[1], "DELETE post;")?;
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+
+            runner.reset().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_reset_if_any_down_script_is_missing() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            let migration_names = get_migration_names()?;
+            add_down_migration_file(&migration_names[2], "DELETE comment;")?;
+
+            let result = runner.reset().await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "The following migrations have no down script: {}, {}",
+                        migration_names[1], migration_names[0]
+                    )
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,76 @@
+use anyhow::{ensure, Result};
+use surrealdb_migrations::{AuthLevel, SurrealdbConfiguration, SurrealdbMigrations};
+
+#[test]
+fn resolve_config_from_project_config() -> Result<()> {
+    let configuration = SurrealdbConfiguration::default();
+    let resolved_config = SurrealdbMigrations::new(configuration).resolved_config();
+
+    ensure!(resolved_config.url == "ws://localhost:8000");
+    ensure!(resolved_config.ns == "test");
+    ensure!(resolved_config.db == "test");
+    ensure!(resolved_config.auth_level == Some(AuthLevel::Root));
+    ensure!(resolved_config.username == Some("root".to_string()));
+    ensure!(resolved_config.password == Some("********".to_string()));
+    ensure!(resolved_config.token.is_none());
+    ensure!(resolved_config.scope.is_none());
+    ensure!(resolved_config.migrations_table == "script_migration");
+
+    Ok(())
+}
+
+#[test]
+fn resolve_config_with_configured_values() -> Result<()> {
+    let configuration = SurrealdbConfiguration::builder()
+        .url("https://db.example.com")
+        .ns("production")
+        .db("app")
+        .username("admin")
+        .password("secret")
+        .auth_level(AuthLevel::Database)
+        .migrations_table("_app_migrations")
+        .build();
+    let resolved_config = SurrealdbMigrations::new(configuration).resolved_config();
+
+    ensure!(resolved_config.url == "https://db.example.com");
+    ensure!(resolved_config.ns == "production");
+    ensure!(resolved_config.db == "app");
+    ensure!(resolved_config.auth_level == Some(AuthLevel::Database));
+    ensure!(resolved_config.username == Some("admin".to_string()));
+    ensure!(resolved_config.password == Some("********".to_string()));
+    ensure!(resolved_config.migrations_table == "_app_migrations");
+
+    Ok(())
+}
+
+#[test]
+fn resolve_config_with_token() -> Result<()> {
+    let configuration = SurrealdbConfiguration::builder()
+        .token("eyJhbGciOiJIUzI1NiJ9")
+        .build();
+    let resolved_config = SurrealdbMigrations::new(configuration).resolved_config();
+
+    ensure!(resolved_config.auth_level.is_none());
+    ensure!(resolved_config.username.is_none());
+    ensure!(resolved_config.password.is_none());
+    ensure!(resolved_config.token == Some("********".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn resolve_config_with_scope() -> Result<()> {
+    let configuration = SurrealdbConfiguration::builder()
+        .scope("migrator")
+        .scope_params(serde_json::json!({ "email": "admin@example.com", "password": "secret" }))
+        .build();
+    let resolved_config = SurrealdbMigrations::new(configuration).resolved_config();
+
+    ensure!(resolved_config.auth_level.is_none());
+    ensure!(resolved_config.username.is_none());
+    ensure!(resolved_config.password.is_none());
+    ensure!(resolved_config.token.is_none());
+    ensure!(resolved_config.scope == Some("migrator".to_string()));
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anyhow::{ensure, Result};
+use serde::Deserialize;
+use serial_test::serial;
+use std::path::Path;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[derive(Deserialize)]
+struct Post {
+    title: String,
+}
+
+#[tokio::test]
+#[serial]
+async fn run_script_without_recording_it() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write(
+                "tests-files/cleanup.surql",
+                "CREATE post:maintenance SET title = 'Maintenance';",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner
+                .run_script(Path::new("tests-files/cleanup.surql"))
+                .await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let post: Option<Post> = client.select(("post", "maintenance")).await?;
+            ensure!(post.is_some_and(|post| post.title == "Maintenance"));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_run_script_if_file_does_not_exist() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let result = SurrealdbMigrations::new(configuration)
+                .run_script(Path::new("tests-files/missing.surql"))
+                .await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == "Cannot read content of the script file 'tests-files/missing.surql'"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_run_script_with_invalid_statement() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write("tests-files/invalid.surql", "CREATE;")?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let result = SurrealdbMigrations::new(configuration)
+                .run_script(Path::new("tests-files/invalid.surql"))
+                .await;
+
+            ensure!(result.is_err());
+
+            Ok(())
+        })
+    })
+    .await
+}
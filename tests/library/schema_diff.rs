@@ -0,0 +1,105 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SchemaDefinitionKind, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn schema_diff_empty_after_apply() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let diff = runner.schema_diff().await?;
+
+            ensure!(diff.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn schema_diff_with_new_schema_file() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            add_new_schema_file()?;
+
+            let diff = runner.schema_diff().await?;
+
+            let added = diff
+                .added
+                .iter()
+                .map(|definition| (definition.kind, definition.name.as_str()))
+                .collect::<Vec<_>>();
+
+            ensure!(
+                added
+                    == vec![
+                        (SchemaDefinitionKind::Table, "category"),
+                        (SchemaDefinitionKind::Field, "name"),
+                    ]
+            );
+            ensure!(diff.removed.is_empty());
+            ensure!(diff.changed.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn schema_diff_with_changes_outside_of_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query(
+                    "DEFINE FIELD content ON comment TYPE string; DEFINE TABLE archive SCHEMALESS;",
+                )
+                .await?
+                .check()?;
+
+            let diff = runner.schema_diff().await?;
+
+            ensure!(diff.added.is_empty());
+
+            ensure!(diff.removed.len() == 1);
+            ensure!(diff.removed[0].kind == SchemaDefinitionKind::Table);
+            ensure!(diff.removed[0].name == "archive");
+
+            ensure!(diff.changed.len() == 1);
+            ensure!(diff.changed[0].kind == SchemaDefinitionKind::Field);
+            ensure!(diff.changed[0].table == "comment");
+            ensure!(diff.changed[0].name == "content");
+            ensure!(diff.changed[0].db_definition == "DEFINE FIELD content ON comment TYPE string");
+
+            Ok(())
+        })
+    })
+    .await
+}
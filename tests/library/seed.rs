@@ -0,0 +1,98 @@
+use anyhow::{ensure, Result};
+use serde::Deserialize;
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[derive(Deserialize)]
+struct Counter {
+    count: u32,
+    last_count: Option<u32>,
+}
+
+#[tokio::test]
+#[serial]
+async fn seed_runs_seed_files_in_order_every_time() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::create_dir("tests-files/seeds")?;
+            std::fs::write(
+                "tests-files/seeds/01_increment.surql",
+                "UPDATE counter:seeds SET count += 1;",
+            )?;
+            std::fs::write(
+                "tests-files/seeds/02_copy.surql",
+                "UPDATE counter:seeds SET last_count = count;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            runner.seed().await?;
+            runner.seed().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let counter: Option<Counter> = client.select(("counter", "seeds")).await?;
+            ensure!(
+                counter.is_some_and(|counter| counter.count == 2 && counter.last_count == Some(2))
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn seed_without_seeds_folder() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.seed().await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_run_seeds() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::create_dir("tests-files/seeds")?;
+            std::fs::write(
+                "tests-files/seeds/01_increment.surql",
+                "UPDATE counter:seeds SET count += 1;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder().run_seeds(true).build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let counter: Option<Counter> = client.select(("counter", "seeds")).await?;
+            ensure!(counter.is_some_and(|counter| counter.count == 1));
+
+            Ok(())
+        })
+    })
+    .await
+}
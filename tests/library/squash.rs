@@ -0,0 +1,94 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{MigrationError, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[test]
+#[serial]
+fn squash_migrations() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let migration_names = get_migration_names()?;
+
+    let configuration = SurrealdbConfiguration::default();
+    let path = SurrealdbMigrations::new(configuration).squash(&migration_names[1])?;
+
+    let version = migration_names[1]
+        .split('_')
+        .take(2)
+        .collect::<Vec<_>>()
+        .join("_");
+
+    ensure!(path.ends_with(format!("{}_Squashed.surql", version)));
+
+    let first_content = std::fs::read_to_string(format!(
+        "tests-files/migrations/{}.surql",
+        migration_names[0]
+    ))?;
+    let second_content = std::fs::read_to_string(format!(
+        "tests-files/migrations/{}.surql",
+        migration_names[1]
+    ))?;
+
+    ensure!(
+        std::fs::read_to_string(&path)?
+            == format!(
+                "# {}\n{}\n\n# {}\n{}\n",
+                migration_names[0], first_content, migration_names[1], second_content
+            )
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn squash_migrations_with_down_scripts() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let migration_names = get_migration_names()?;
+    add_down_migration_file(&migration_names[0], "DELETE user:admin;")?;
+    add_down_migration_file(&migration_names[1], "DELETE post;")?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .create_down_script(true)
+        .build();
+    let path = SurrealdbMigrations::new(configuration).squash(&migration_names[1])?;
+
+    let down_path = path.with_extension("").with_extension("down.surql");
+
+    ensure!(
+        std::fs::read_to_string(down_path)?
+            == format!(
+                "# {}\nDELETE post;\n\n# {}\nDELETE user:admin;\n",
+                migration_names[1], migration_names[0]
+            )
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn cannot_squash_migrations_without_down_scripts() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let migration_names = get_migration_names()?;
+    add_down_migration_file(&migration_names[1], "DELETE post;")?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .create_down_script(true)
+        .build();
+    let result = SurrealdbMigrations::new(configuration).squash(&migration_names[1]);
+
+    ensure!(matches!(
+        result,
+        Err(MigrationError::MissingDownScript { migrations }) if migrations == vec![migration_names[0].to_string()]
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn status_with_applied_and_pending_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            apply_migrations_up_to(&first_migration_name)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let status = SurrealdbMigrations::new(configuration).status().await?;
+
+            let names = status
+                .migrations
+                .iter()
+                .map(|migration| migration.name.to_string())
+                .collect::<Vec<_>>();
+            ensure!(names == get_migration_names()?);
+
+            ensure!(status.migrations[0].is_applied);
+            ensure!(status.migrations[0].executed_at.is_some());
+            ensure!(!status.migrations[1].is_applied);
+            ensure!(status.migrations[1].executed_at.is_none());
+            ensure!(!status.migrations[2].is_applied);
+
+            ensure!(status.orphaned.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn status_with_orphaned_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let first_migration_file = get_first_migration_file()?;
+            std::fs::remove_file(first_migration_file)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let status = SurrealdbMigrations::new(configuration).status().await?;
+
+            ensure!(status.migrations.len() == 2);
+            ensure!(status
+                .migrations
+                .iter()
+                .all(|migration| migration.is_applied));
+
+            ensure!(status.orphaned.len() == 1);
+            ensure!(status.orphaned[0].script_name == first_migration_name);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+#[serial]
+async fn status_serialized_to_json() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            apply_migrations_up_to(&first_migration_name)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let status = SurrealdbMigrations::new(configuration).status().await?;
+
+            let json = serde_json::to_value(&status)?;
+
+            ensure!(json["migrations"][0]["name"] == first_migration_name.as_str());
+            ensure!(json["migrations"][0]["is_applied"] == true);
+            ensure!(json["migrations"][0]["executed_at"].is_string());
+            ensure!(json["migrations"][1]["is_applied"] == false);
+            ensure!(json["migrations"][1]["executed_at"].is_null());
+            ensure!(json["orphaned"] == serde_json::json!([]));
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,58 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn unmark_applied_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let last_migration_name = get_last_migration_name()?;
+            runner.unmark(&last_migration_name).await?;
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations == vec![last_migration_name]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_unmark_migration_not_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let first_migration_name = get_first_migration_name()?;
+            let result = SurrealdbMigrations::new(configuration)
+                .unmark(&first_migration_name)
+                .await;
+
+            ensure!(result.is_err());
+            ensure!(
+                result.unwrap_err().to_string()
+                    == format!(
+                        "Cannot unmark migration '{}': this migration has not been applied",
+                        first_migration_name
+                    )
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -1,6 +1,17 @@
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use chrono::{TimeZone, Utc};
+use serde_json::json;
 use serial_test::serial;
-use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use surrealdb_migrations::{
+    ConcurrencyControl, ListOrder, MigrationError, MigrationProgressStatus, SurrealdbConfiguration,
+    SurrealdbMigrations, VersionFormat,
+};
 
 use crate::helpers::*;
 
@@ -37,6 +48,7 @@ async fn cannot_apply_if_surreal_instance_not_running() -> Result<()> {
         error.to_string(),
         "There was an error processing a remote WS request"
     );
+    assert!(matches!(error, MigrationError::ConnectionFailed(_)));
 
     Ok(())
 }
@@ -107,13 +119,12 @@ async fn apply_with_db_configuration() -> Result<()> {
             scaffold_blog_template()?;
             empty_folder("tests-files/migrations")?;
 
-            let configuration = SurrealdbConfiguration {
-                url: None,
-                username: Some("admin".to_string()),
-                password: Some("admin".to_string()),
-                ns: Some("namespace".to_string()),
-                db: Some("database".to_string()),
-            };
+            let configuration = SurrealdbConfiguration::builder()
+                .username("admin")
+                .password("admin")
+                .ns("namespace")
+                .db("database")
+                .build();
             SurrealdbMigrations::new(configuration).up().await?;
 
             Ok(())
@@ -122,6 +133,31 @@ async fn apply_with_db_configuration() -> Result<()> {
     .await
 }
 
+#[tokio::test]
+#[serial]
+async fn apply_with_missing_namespace_and_database() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .ns("fresh_namespace")
+                .db("fresh_database")
+                .create_ns_db_if_missing(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
 #[tokio::test]
 #[serial]
 async fn apply_should_skip_events_if_no_events_folder() -> Result<()> {
@@ -132,14 +168,1799 @@ async fn apply_should_skip_events_if_no_events_folder() -> Result<()> {
             empty_folder("tests-files/migrations")?;
             remove_folder("tests-files/events")?;
 
+            let configuration = SurrealdbConfiguration::builder()
+                .username("admin")
+                .password("admin")
+                .ns("namespace")
+                .db("database")
+                .build();
+            SurrealdbMigrations::new(configuration).up().await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_surreal_instance_not_running_after_retries() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let configuration = SurrealdbConfiguration {
+        connect_retries: Some(2),
+        connect_retry_delay_ms: Some(10),
+        ..Default::default()
+    };
+    let result = SurrealdbMigrations::new(configuration).up().await;
+
+    let error = result.unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Cannot connect to the SurrealDB instance after 3 attempts"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_with_unsupported_url_scheme() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .url("ftp://localhost:8000")
+        .build();
+    let result = SurrealdbMigrations::new(configuration).up().await;
+
+    ensure!(matches!(
+        result,
+        Err(MigrationError::ConnectionFailed(error)) if error.to_string()
+            == "Unsupported scheme 'ftp' in url 'ftp://localhost:8000', expected one of: ws, wss, http, https"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_connection_timed_out() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    // Accepts connections but never answers the handshake
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .url(address.to_string())
+        .connect_timeout(Duration::from_millis(100))
+        .build();
+    let result = SurrealdbMigrations::new(configuration).up().await;
+
+    ensure!(matches!(
+        result,
+        Err(MigrationError::ConnectionTimeout { timeout }) if timeout == Duration::from_millis(100)
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_existing_client() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let client = surrealdb::engine::any::connect("ws://localhost:8000").await?;
+            client
+                .signin(surrealdb::opt::auth::Root {
+                    username: "root",
+                    password: "root",
+                })
+                .await?;
+            client.use_ns("test").use_db("test").await?;
+
+            let runner = SurrealdbMigrations::from_client(client);
+
+            runner.up().await?;
+            runner.validate_version_order().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_report() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let report = runner.up_with_report().await?;
+
+            ensure!(report.applied_migrations == get_migration_names()?);
+            ensure!(report.schema_files_count == 4);
+            ensure!(report.has_changes);
+
+            let report = runner.up_with_report().await?;
+
+            ensure!(report.applied_migrations.is_empty());
+            ensure!(report.schema_files_count == 4);
+            ensure!(!report.has_changes);
+
+            add_new_schema_file()?;
+
+            let report = runner.up_with_report().await?;
+
+            ensure!(report.applied_migrations.is_empty());
+            ensure!(report.schema_files_count == 5);
+            ensure!(report.has_changes);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_custom_migrations_table() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
             let configuration = SurrealdbConfiguration {
-                url: None,
-                username: Some("admin".to_string()),
-                password: Some("admin".to_string()),
-                ns: Some("namespace".to_string()),
-                db: Some("database".to_string()),
+                migrations_table: Some("_my_app_migrations".to_string()),
+                ..Default::default()
             };
-            SurrealdbMigrations::new(configuration).up().await?;
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let default_runner = SurrealdbMigrations::new(SurrealdbConfiguration::default());
+            let default_migrations_applied = default_runner.list(ListOrder::default()).await?;
+            ensure!(default_migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_custom_migrations_fields() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration {
+                migrations_table: Some("legacy_migration".to_string()),
+                script_name_field: Some("migration_name".to_string()),
+                executed_at_field: Some("ran_at".to_string()),
+                ..Default::default()
+            };
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            let names = migrations_applied
+                .iter()
+                .map(|migration| migration.script_name.to_string())
+                .collect::<Vec<_>>();
+            ensure!(names == get_migration_names()?);
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let mut response = client
+                .query("SELECT migration_name, ran_at FROM legacy_migration WHERE ran_at != NONE;")
+                .await?;
+            let records: Vec<serde_json::Value> = response.take(0)?;
+            ensure!(records.len() == 3);
+
+            ensure!(runner.pending().await?.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_without_transaction() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration {
+                transactional: false,
+                ..Default::default()
+            };
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_migration_with_semicolons_inside_statements() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddGreetFunction.surql",
+                "DEFINE FUNCTION fn::greet($name: string) {
+    LET $greeting = \"Hello; \";
+    RETURN $greeting + $name;
+};
+
+CREATE greeting:first SET message = fn::greet(\"world\");",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 4);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_not_record_failing_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_FailingMigration.surql",
+                "CREATE post:failing;\nCREATE post:failing;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::MigrationFailed { name, .. }) if name == "99991231_235959_FailingMigration"
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != "99991231_235959_FailingMigration"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_retry_migration_failing_with_transient_error() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_FailingMigration.surql",
+                "CREATE post:failing;\nCREATE post:failing;",
+            )?;
+
+            let classified_errors = Arc::new(Mutex::new(0));
+            let classified_errors_clone = classified_errors.clone();
+
+            let configuration = SurrealdbConfiguration::builder()
+                .migration_retries(2)
+                .migration_retry_delay_ms(1)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration).with_transient_error_classifier(
+                move |_| {
+                    *classified_errors_clone.lock().unwrap() += 1;
+                    true
+                },
+            );
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::MigrationFailed { name, .. }) if name == "99991231_235959_FailingMigration"
+            ));
+            ensure!(*classified_errors.lock().unwrap() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_not_retry_migration_failing_with_non_transient_error() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_FailingMigration.surql",
+                "CREATE post:failing;\nCREATE post:failing;",
+            )?;
+
+            let logs = Arc::new(Mutex::new(Vec::new()));
+            let logs_clone = logs.clone();
+
+            let configuration = SurrealdbConfiguration::builder()
+                .migration_retries(2)
+                .migration_retry_delay_ms(1)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration)
+                .with_logger(move |message| logs_clone.lock().unwrap().push(message.to_string()));
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::MigrationFailed { name, .. }) if name == "99991231_235959_FailingMigration"
+            ));
+            ensure!(!logs
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|message| message.contains("retrying")));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_report_partially_applied_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_PartialMigration.surql",
+                "CREATE item:1;\nCREATE item:2;\nCREATE item:3;\nCREATE item:1;\nCREATE item:5;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .transactional(false)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::PartiallyApplied { name, statement_index: 3, applied_statements, .. })
+                    if name == "99991231_235959_PartialMigration"
+                        && applied_statements == ["CREATE item:1", "CREATE item:2", "CREATE item:3", "CREATE item:5"]
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_skip_migrations_with_disabled_tags() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddAnalytics.surql",
+                "-- tags: analytics\nCREATE item:analytics;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != "99991231_235959_AddAnalytics"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_migrations_with_enabled_tags() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235958_AddAnalytics.surql",
+                "-- tags: analytics\nCREATE item:analytics;",
+            )?;
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddReporting.surql",
+                "-- tags: analytics, reporting\nCREATE item:reporting;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .enabled_tags(["analytics"])
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 4);
+            ensure!(migrations_applied
+                .iter()
+                .any(|migration| migration.script_name == "99991231_235958_AddAnalytics"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_skip_migrations_listed_in_skip() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/00000101_000000_AddTestData.surql",
+                "CREATE item:test_data;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .skip(["00000101_000000_AddTestData"])
+                .validate_before_apply(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != "00000101_000000_AddTestData"));
+
+            runner.validate_version_order().await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_next_migrations_when_continuing_on_error() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/00000101_000000_FailingMigration.surql",
+                "CREATE post:failing;\nCREATE post:failing;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .continue_on_error(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::MigrationsFailed(errors)) if matches!(
+                    errors.as_slice(),
+                    [MigrationError::MigrationFailed { name, .. }] if name == "00000101_000000_FailingMigration"
+                )
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != "00000101_000000_FailingMigration"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_migration_timed_out() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_SlowMigration.surql",
+                "SLEEP 2s;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .transactional(false)
+                .migration_timeout(Duration::from_millis(100))
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::Timeout { name }) if name == "99991231_235959_SlowMigration"
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != "99991231_235959_SlowMigration"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_migration_timed_out_in_transaction() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_SlowMigration.surql",
+                "SLEEP 500ms;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .migration_timeout(Duration::from_millis(100))
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::Timeout { name }) if name == "99991231_235959_SlowMigration"
+            ));
+
+            // The migration is completed by the database after the timeout
+            tokio::time::sleep(Duration::from_secs(1)).await;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != "99991231_235959_SlowMigration"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_next_migrations_when_continuing_on_timeout() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/00000101_000000_SlowMigration.surql",
+                "SLEEP 1s;",
+            )?;
+
+            // The next migrations may wait for the database to complete the slow one
+            let configuration = SurrealdbConfiguration::builder()
+                .continue_on_error(true)
+                .migration_timeout(Duration::from_millis(700))
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::MigrationsFailed(errors))
+                    if matches!(
+                        errors.as_slice(),
+                        [MigrationError::Timeout { name }] if name == "00000101_000000_SlowMigration"
+                    )
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.script_name != "00000101_000000_SlowMigration"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_migrations_share_the_same_version() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_FirstMigration.surql",
+                "CREATE post:first;",
+            )?;
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_SecondMigration.surql",
+                "CREATE post:second;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::DuplicateVersions { migrations }) if migrations == vec![
+                    "99991231_235959_FirstMigration.surql",
+                    "99991231_235959_SecondMigration.surql",
+                ]
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_applied_migration_modified() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up_to(&first_migration_name).await?;
+
+            let first_migration_file = get_first_migration_file()?;
+            let content = std::fs::read_to_string(&first_migration_file)?;
+            std::fs::write(&first_migration_file, content + "\nCREATE post;")?;
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::ChecksumMismatch(checksum_mismatches))
+                    if checksum_mismatches.len() == 1
+                        && checksum_mismatches[0].script_name == first_migration_name
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 1);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_allowed_checksum_mismatch() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .allow_checksum_mismatch(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up_to(&first_migration_name).await?;
+
+            let first_migration_file = get_first_migration_file()?;
+            let content = std::fs::read_to_string(&first_migration_file)?;
+            std::fs::write(&first_migration_file, content + "\nCREATE post;")?;
+
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_health_check() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .health_check("RETURN session::db()", json!(["test"]))
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_health_check_fails() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .health_check("RETURN session::db()", json!(["production"]))
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::HealthCheckFailed { actual, .. }) if actual == json!(["test"])
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_min_server_version() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .min_server_version("1.0.0-beta.1")
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_server_is_older_than_min_server_version() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .min_server_version("99.0.0")
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let server_version = runner.server_version().await?;
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::ServerVersionTooOld { version, min_version })
+                    if version == server_version && min_version == "99.0.0"
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_migration_with_version_directives() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddFlags.surql",
+                "CREATE flag:always;
+-- @if version >= 1.0.0-beta.1
+CREATE flag:current;
+-- @else
+CREATE flag:legacy;
+-- @endif
+-- @if version >= 99
+CREATE flag:future;
+-- @endif",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 4);
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let mut response = client.query("SELECT VALUE meta::id(id) FROM flag;").await?;
+            let mut flag_ids: Vec<String> = response.take(0)?;
+            flag_ids.sort();
+            ensure!(flag_ids == vec!["always", "current"]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_schema_concurrency() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .schema_concurrency(4)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let schema_diff = runner.schema_diff().await?;
+            ensure!(schema_diff.added.is_empty());
+            ensure!(schema_diff.changed.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_strict_schema() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .strict_schema(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_schema_file_defines_several_tables() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let schema_file = "tests-files/schemas/post.surql";
+            let content = std::fs::read_to_string(schema_file)?;
+            std::fs::write(schema_file, content + "\nDEFINE TABLE draft SCHEMALESS;")?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .strict_schema(true)
+                .build();
+            let result = SurrealdbMigrations::new(configuration).up().await;
+
+            ensure!(matches!(
+                result,
+                Err(MigrationError::InvalidSchemaFile { file, tables_count: 2 })
+                    if file.ends_with("post.surql")
+            ));
+
+            check_surrealdb_empty().await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_version_check() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .concurrency_control(ConcurrencyControl::VersionCheck)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_schema_version_changed_concurrently() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            // Simulate another process applying migrations while these ones are applied
+            let first_migration_file = get_first_migration_file()?;
+            let content = std::fs::read_to_string(&first_migration_file)?;
+            std::fs::write(
+                &first_migration_file,
+                content + "\nUPDATE script_migration_schema_version:current SET version = 5;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .concurrency_control(ConcurrencyControl::VersionCheck)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            let result = runner.up().await;
+
+            ensure!(matches!(
+                result,
+                Err(MigrationError::ConcurrentModification {
+                    expected_version: 0,
+                    actual_version: 5
+                })
+            ));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_custom_folder() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::create_dir("tests-files/db")?;
+            for dir_name in ["schemas", "events", "migrations"] {
+                std::fs::rename(
+                    format!("tests-files/{}", dir_name),
+                    format!("tests-files/db/{}", dir_name),
+                )?;
+            }
+
+            let configuration = SurrealdbConfiguration::builder()
+                .folder("tests-files/db")
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_custom_dir_names() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            for (dir_name, custom_dir_name) in [
+                ("schemas", "tables"),
+                ("events", "triggers"),
+                ("migrations", "changes"),
+            ] {
+                std::fs::rename(
+                    format!("tests-files/{}", dir_name),
+                    format!("tests-files/{}", custom_dir_name),
+                )?;
+            }
+
+            let configuration = SurrealdbConfiguration::builder()
+                .folder("tests-files")
+                .schema_dir("tables")
+                .event_dir("triggers")
+                .migration_dir("changes")
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            ensure!(Path::new("tests-files/changes/definitions/_initial.json").exists());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_custom_folder_does_not_exist() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .folder("tests-files/missing")
+        .build();
+    let result = SurrealdbMigrations::new(configuration).up().await;
+
+    let error = result.unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "The migrations root folder 'tests-files/missing' does not exist"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_merged_folders() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let migration_names = get_migration_names()?;
+
+            std::fs::create_dir_all("tests-files/users/migrations")?;
+            std::fs::create_dir_all("tests-files/blog/migrations")?;
+            for (index, migration_name) in migration_names.iter().enumerate() {
+                let folder = if index == 0 { "users" } else { "blog" };
+                std::fs::rename(
+                    format!("tests-files/migrations/{}.surql", migration_name),
+                    format!("tests-files/{}/migrations/{}.surql", folder, migration_name),
+                )?;
+            }
+
+            let configuration = SurrealdbConfiguration::builder()
+                .folder("tests-files")
+                .folders(["tests-files/blog", "tests-files/users"])
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            let script_names = migrations_applied
+                .into_iter()
+                .map(|migration| migration.script_name)
+                .collect::<Vec<_>>();
+
+            ensure!(script_names == migration_names);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_version_defined_in_several_folders() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let version = first_migration_name
+                .splitn(3, '_')
+                .take(2)
+                .collect::<Vec<_>>()
+                .join("_");
+
+            for folder in ["users", "blog"] {
+                std::fs::create_dir_all(format!("tests-files/{}/migrations", folder))?;
+                std::fs::copy(
+                    format!("tests-files/migrations/{}.surql", first_migration_name),
+                    format!(
+                        "tests-files/{}/migrations/{}.surql",
+                        folder, first_migration_name
+                    ),
+                )?;
+            }
+
+            let configuration = SurrealdbConfiguration::builder()
+                .folder("tests-files")
+                .folders(["tests-files/users", "tests-files/blog"])
+                .build();
+            let result = SurrealdbMigrations::new(configuration).up().await;
+
+            let error = result.unwrap_err();
+
+            assert_eq!(
+                error.to_string(),
+                format!(
+                    "Migration version '{}' is defined in both 'tests-files/users' and 'tests-files/blog' folders",
+                    version
+                )
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_sequential_version_defined_in_several_folders() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            for (folder, migration_name) in [("users", "0001_AddUser"), ("blog", "0001_AddPost")] {
+                std::fs::create_dir_all(format!("tests-files/{}/migrations", folder))?;
+                std::fs::write(
+                    format!("tests-files/{}/migrations/{}.surql", folder, migration_name),
+                    "",
+                )?;
+            }
+
+            let configuration = SurrealdbConfiguration::builder()
+                .folder("tests-files")
+                .folders(["tests-files/users", "tests-files/blog"])
+                .version_format(VersionFormat::Sequential)
+                .build();
+            let result = SurrealdbMigrations::new(configuration).up().await;
+
+            let error = result.unwrap_err();
+
+            assert_eq!(
+                error.to_string(),
+                "Migration version '0001' is defined in both 'tests-files/users' and 'tests-files/blog' folders"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_namespace_and_database_to_escape() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .ns("my-app")
+                .db("test-db")
+                .create_ns_db_if_missing(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_progress() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let progresses = Mutex::new(Vec::new());
+
+            let configuration = SurrealdbConfiguration::default();
+            SurrealdbMigrations::new(configuration)
+                .up_with_progress(|progress| progresses.lock().unwrap().push(progress))
+                .await?;
+
+            let progresses = progresses.into_inner().unwrap();
+            let migration_names = get_migration_names()?;
+
+            ensure!(progresses.len() == 6);
+
+            for (index, name) in migration_names.iter().enumerate() {
+                let started = &progresses[index * 2];
+                let completed = &progresses[index * 2 + 1];
+
+                ensure!(&started.name == name);
+                ensure!(started.index == index);
+                ensure!(started.total == 3);
+                ensure!(started.status == MigrationProgressStatus::Started);
+
+                ensure!(&completed.name == name);
+                ensure!(completed.status == MigrationProgressStatus::Completed);
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_statement_progress() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_BackfillItems.surql",
+                "CREATE item:one;\nCREATE item:two;\nCREATE item:three;",
+            )?;
+
+            let progresses = Mutex::new(Vec::new());
+
+            let configuration = SurrealdbConfiguration::builder()
+                .transactional(false)
+                .statement_progress(true)
+                .build();
+            SurrealdbMigrations::new(configuration)
+                .up_with_progress(|progress| progresses.lock().unwrap().push(progress.status))
+                .await?;
+
+            let statuses = progresses.into_inner().unwrap();
+
+            ensure!(
+                statuses
+                    == vec![
+                        MigrationProgressStatus::Started,
+                        MigrationProgressStatus::StatementExecuted { index: 0, total: 3 },
+                        MigrationProgressStatus::StatementExecuted { index: 1, total: 3 },
+                        MigrationProgressStatus::StatementExecuted { index: 2, total: 3 },
+                        MigrationProgressStatus::Completed,
+                    ]
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_statement_progress_should_not_split_migration_with_variables() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_BackfillItems.surql",
+                "LET $name = 'one';\nCREATE item:one SET name = $name;",
+            )?;
+
+            let progresses = Mutex::new(Vec::new());
+
+            let configuration = SurrealdbConfiguration::builder()
+                .transactional(false)
+                .statement_progress(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner
+                .up_with_progress(|progress| progresses.lock().unwrap().push(progress.status))
+                .await?;
+
+            let statuses = progresses.into_inner().unwrap();
+            ensure!(
+                statuses
+                    == vec![
+                        MigrationProgressStatus::Started,
+                        MigrationProgressStatus::Completed,
+                    ]
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 1);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_statement_progress_should_not_split_migration_with_transaction() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddItems.surql",
+                "BEGIN TRANSACTION;\nCREATE item:one;\nCREATE item:one;\nCOMMIT TRANSACTION;",
+            )?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .transactional(false)
+                .statement_progress(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let result = runner.up_with_progress(|_| {}).await;
+            ensure!(result.is_err());
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            let mut response = client.query("SELECT * FROM item;").await?;
+            let items: Vec<serde_json::Value> = response.take(0)?;
+            ensure!(items.is_empty());
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_logger() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let messages = Arc::new(Mutex::new(Vec::new()));
+            let logger_messages = messages.clone();
+
+            let configuration = SurrealdbConfiguration::default();
+            SurrealdbMigrations::new(configuration)
+                .with_logger(move |message| {
+                    logger_messages.lock().unwrap().push(message.to_string())
+                })
+                .up()
+                .await?;
+
+            let messages = messages.lock().unwrap();
+
+            ensure!(
+                *messages
+                    == vec![
+                        "Schema files successfully executed!",
+                        "Event files successfully executed!",
+                        "Executing migration AddAdminUser...",
+                        "Executing migration AddPost...",
+                        "Executing migration CommentPost...",
+                        "Migration files successfully executed!",
+                    ]
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_should_log_warnings() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/00000101_000000_AddTestData.surql",
+                "CREATE item:test_data;",
+            )?;
+
+            let messages = Arc::new(Mutex::new(Vec::new()));
+            let logger_messages = messages.clone();
+
+            let configuration = SurrealdbConfiguration::builder()
+                .skip(["00000101_000000_AddTestData"])
+                .build();
+            SurrealdbMigrations::new(configuration)
+                .with_logger(move |message| {
+                    logger_messages.lock().unwrap().push(message.to_string())
+                })
+                .up()
+                .await?;
+
+            let messages = messages.lock().unwrap();
+
+            ensure!(messages.contains(
+                &"Warning: the following migrations are skipped: 00000101_000000_AddTestData"
+                    .to_string()
+            ));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_logged_statements() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddSettings.surql",
+                "CREATE setting:admin SET email = '${ADMIN_EMAIL}', password = '${ADMIN_PASSWORD}';",
+            )?;
+
+            let vars = HashMap::from([
+                ("ADMIN_EMAIL".to_string(), "admin@example.com".to_string()),
+                ("ADMIN_PASSWORD".to_string(), "s3cr3t".to_string()),
+            ]);
+
+            let messages = Arc::new(Mutex::new(Vec::new()));
+            let logger_messages = messages.clone();
+
+            let configuration = SurrealdbConfiguration::builder()
+                .log_statements(true)
+                .redacted_vars(["ADMIN_PASSWORD"])
+                .build();
+            SurrealdbMigrations::new(configuration)
+                .with_logger(move |message| {
+                    logger_messages.lock().unwrap().push(message.to_string())
+                })
+                .up_with_vars(vars)
+                .await?;
+
+            let messages = messages.lock().unwrap();
+
+            ensure!(messages.iter().any(|message| message
+                == "Executing statement:\nCREATE setting:admin SET email = 'admin@example.com', password = '***';"));
+            ensure!(!messages.iter().any(|message| message.contains("s3cr3t")));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_hooks() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let events = Arc::new(Mutex::new(Vec::new()));
+            let before_apply_events = events.clone();
+            let after_apply_events = events.clone();
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration)
+                .with_before_apply(move || {
+                    let events = before_apply_events.clone();
+                    async move { events.lock().unwrap().push("before".to_string()) }
+                })
+                .with_after_apply(move |has_applied_migrations| {
+                    let events = after_apply_events.clone();
+                    async move {
+                        let event = format!("after: {}", has_applied_migrations);
+                        events.lock().unwrap().push(event)
+                    }
+                });
+
+            runner.up().await?;
+            runner.up().await?;
+
+            let events = events.lock().unwrap();
+
+            ensure!(*events == vec!["before", "after: true", "before", "after: false"]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_vars() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddSettings.surql",
+                "CREATE setting:admin SET email = '${ADMIN_EMAIL}', updated_at = time::now();",
+            )?;
+
+            let vars =
+                HashMap::from([("ADMIN_EMAIL".to_string(), "admin@example.com".to_string())]);
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up_with_vars(vars).await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 4);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_with_missing_vars() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddSettings.surql",
+                "CREATE setting:admin SET email = '${ADMIN_EMAIL}', threshold = ${THRESHOLD};",
+            )?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            let result = runner.up_with_vars(HashMap::new()).await;
+
+            ensure!(result.is_err());
+
+            let error = result.unwrap_err();
+            let error_chain = format!("{:#}", error);
+            ensure!(
+                error_chain.contains("The following variables are missing: ADMIN_EMAIL, THRESHOLD")
+            );
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_if_version_order_is_invalid_when_validating_before_apply() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write("tests-files/migrations/0002_AddPost.surql", "CREATE post;")?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .version_format(VersionFormat::Sequential)
+                .validate_before_apply(true)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            std::fs::write("tests-files/migrations/0001_AddUser.surql", "CREATE user;")?;
+
+            let result = runner.up().await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::VersionOrderViolation { migrations }) if migrations == ["0001_AddUser"]
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 1);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_with_clock() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration)
+                .with_clock(|| Utc.with_ymd_and_hms(2023, 3, 17, 15, 32, 1).unwrap());
+
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+            ensure!(migrations_applied
+                .iter()
+                .all(|migration| migration.executed_at == "2023-03-17T15:32:01Z"));
+
+            let history = runner.history().await?;
+            ensure!(history
+                .iter()
+                .all(|event| event.executed_at == "2023-03-17T15:32:01Z"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_migration_with_use_header_in_another_database() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            std::fs::write(
+                "tests-files/migrations/99991231_235959_AddSharedSetting.surql",
+                "-- @use ns test db other\nCREATE setting:shared;",
+            )?;
+
+            let client = surrealdb::engine::any::connect("ws://localhost:8000").await?;
+            client
+                .signin(surrealdb::opt::auth::Root {
+                    username: "root",
+                    password: "root",
+                })
+                .await?;
+            client.use_ns("test").use_db("test").await?;
+
+            let runner = SurrealdbMigrations::from_client(client.clone());
+            runner.up().await?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 4);
+
+            let mut response = client.query("SELECT * FROM setting:shared;").await?;
+            let settings: Vec<serde_json::Value> = response.take(0)?;
+            ensure!(settings.is_empty());
+
+            let mut response = client
+                .query("USE NS test DB other;\nSELECT * FROM setting:shared;")
+                .await?;
+            let settings: Vec<serde_json::Value> = response.take(1)?;
+            ensure!(settings.len() == 1);
 
             Ok(())
         })
@@ -0,0 +1,71 @@
+use anyhow::{ensure, Result};
+use serde_json::json;
+use serial_test::serial;
+use surrealdb_migrations::{
+    ListOrder, MigrationError, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn apply_to_several_databases() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let databases = vec!["tenant_1".to_string(), "tenant_2".to_string()];
+            let reports = runner.up_all(&databases).await?;
+
+            ensure!(reports.len() == 2);
+            ensure!(reports
+                .values()
+                .all(|report| report.applied_migrations.len() == 3));
+
+            // The connection is switched back to the database of the configuration
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn report_failures_per_database() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .health_check("RETURN session::db()", json!(["tenant_1"]))
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let databases = vec!["tenant_1".to_string(), "tenant_2".to_string()];
+            let result = runner.up_all(&databases).await;
+
+            let Err(MigrationError::DatabasesFailed { reports, errors }) = result else {
+                return Err(anyhow::anyhow!("Expected some databases to fail"));
+            };
+
+            ensure!(reports.len() == 1);
+            ensure!(reports["tenant_1"].applied_migrations.len() == 3);
+            ensure!(errors.len() == 1);
+            ensure!(matches!(
+                errors["tenant_2"],
+                MigrationError::HealthCheckFailed { .. }
+            ));
+
+            Ok(())
+        })
+    })
+    .await
+}
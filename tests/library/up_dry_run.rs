@@ -0,0 +1,128 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn return_queries_without_applying_them() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let queries = SurrealdbMigrations::new(configuration).up_dry_run().await?;
+
+            // schemas + events + 3 migrations
+            ensure!(queries.len() == 5);
+            ensure!(queries[0].contains("DEFINE TABLE script_migration SCHEMAFULL;"));
+            ensure!(queries[1].contains("DEFINE EVENT publish_post"));
+            ensure!(queries[2].contains("CREATE user:admin"));
+
+            check_surrealdb_empty().await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn return_only_pending_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            apply_migrations_up_to(&first_migration_name)?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let queries = SurrealdbMigrations::new(configuration).up_dry_run().await?;
+
+            // 2 migrations, schemas and events are unchanged since they were applied
+            ensure!(queries.len() == 2);
+            ensure!(queries[0].contains("CREATE post"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn return_all_schemas_if_forced() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+            apply_migrations()?;
+
+            let configuration = SurrealdbConfiguration::builder().force_schema(true).build();
+            let queries = SurrealdbMigrations::new(configuration).up_dry_run().await?;
+
+            // schemas + events
+            ensure!(queries.len() == 2);
+            ensure!(queries[0].contains("DEFINE TABLE script_migration SCHEMAFULL;"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn should_not_create_definition_files() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            SurrealdbMigrations::new(configuration).up_dry_run().await?;
+
+            ensure!(!is_file_exists("tests-files/migrations/definitions")?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn should_not_change_existing_definition_files() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let first_migration_name = get_first_migration_name()?;
+            apply_migrations_up_to(&first_migration_name)?;
+
+            fs_extra::dir::copy(
+                "tests-files/migrations/definitions",
+                "tests-files/definitions-before",
+                &fs_extra::dir::CopyOptions::new().copy_inside(true),
+            )?;
+
+            add_new_schema_file()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            SurrealdbMigrations::new(configuration).up_dry_run().await?;
+
+            ensure!(are_folders_equivalent(
+                "tests-files/migrations/definitions",
+                "tests-files/definitions-before"
+            )?);
+
+            Ok(())
+        })
+    })
+    .await
+}
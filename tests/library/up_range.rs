@@ -0,0 +1,126 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{
+    ListOrder, MigrationError, SurrealdbConfiguration, SurrealdbMigrations,
+};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn apply_range_of_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let migration_names = get_migration_names()?;
+
+            let applied_migrations = runner
+                .up_range(&migration_names[1], &migration_names[2])
+                .await?;
+            ensure!(applied_migrations == migration_names[1..3]);
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_range_overlapping_applied_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let migration_names = get_migration_names()?;
+
+            runner.up_to(&migration_names[1]).await?;
+
+            let result = runner
+                .up_range(&migration_names[0], &migration_names[2])
+                .await;
+            ensure!(matches!(
+                result,
+                Err(MigrationError::RangeAlreadyApplied { migrations })
+                    if migrations == migration_names[..2]
+            ));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_range_in_reverse_order() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let migration_names = get_migration_names()?;
+
+            let result = runner
+                .up_range(&migration_names[2], &migration_names[0])
+                .await;
+            ensure!(result.is_err());
+            ensure!(result.unwrap_err().to_string().contains(&format!(
+                "'{}' must precede '{}'",
+                migration_names[2], migration_names[0]
+            )));
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_apply_range_of_unknown_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let migration_names = get_migration_names()?;
+
+            let result = runner
+                .up_range(&migration_names[0], "20990101_000000_Unknown")
+                .await;
+            ensure!(result.is_err());
+            ensure!(result
+                .unwrap_err()
+                .to_string()
+                .contains("migration '20990101_000000_Unknown' does not exist"));
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,66 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn up_script_does_not_apply_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let script = runner.up_script().await?;
+            ensure!(script.contains("DEFINE TABLE script_migration SCHEMAFULL;"));
+            ensure!(script.contains("CREATE user:admin"));
+            ensure!(script.contains("CREATE script_migration SET script_name = "));
+
+            check_surrealdb_empty().await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_up_script_within_caller_transaction() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let script = runner.up_script().await?;
+
+            let client = create_surrealdb_client(&SurrealdbConfiguration::default()).await?;
+            client
+                .query(format!(
+                    "BEGIN TRANSACTION;\n{}\nCREATE bootstrap:app;\nCOMMIT TRANSACTION;",
+                    script
+                ))
+                .await?
+                .check()?;
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 3);
+
+            let checksum_mismatches = runner.verify().await?;
+            ensure!(checksum_mismatches.is_empty());
+
+            let script = runner.up_script().await?;
+            ensure!(script.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,56 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{ListOrder, SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn apply_next_migrations() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let migration_names = get_migration_names()?;
+
+            let applied_migrations = runner.up_step(1).await?;
+            ensure!(applied_migrations == migration_names[..1]);
+
+            let applied_migrations = runner.up_step(1).await?;
+            ensure!(applied_migrations == migration_names[1..2]);
+
+            let migrations_applied = runner.list(ListOrder::default()).await?;
+            ensure!(migrations_applied.len() == 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_all_migrations_if_fewer_pending() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let applied_migrations = runner.up_step(10).await?;
+            ensure!(applied_migrations == get_migration_names()?);
+
+            let applied_migrations = runner.up_step(1).await?;
+            ensure!(applied_migrations.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
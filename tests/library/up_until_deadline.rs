@@ -0,0 +1,59 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use std::time::{Duration, Instant};
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn apply_all_migrations_before_deadline() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let deadline = Instant::now() + Duration::from_secs(60);
+
+            let applied_migrations = runner.up_until_deadline(deadline).await?;
+            ensure!(applied_migrations == get_migration_names()?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn resume_migrations_after_deadline() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            let applied_migrations = runner.up_until_deadline(Instant::now()).await?;
+            ensure!(applied_migrations.is_empty());
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations.len() == 3);
+
+            let deadline = Instant::now() + Duration::from_secs(60);
+
+            let applied_migrations = runner.up_until_deadline(deadline).await?;
+            ensure!(applied_migrations == get_migration_names()?);
+
+            let pending_migrations = runner.pending().await?;
+            ensure!(pending_migrations.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
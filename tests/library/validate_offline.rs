@@ -0,0 +1,113 @@
+use anyhow::{ensure, Context, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations, VersionFormat};
+
+use crate::helpers::*;
+
+/// Url of a SurrealDB instance that is never reachable, to make sure no connection is made.
+const UNREACHABLE_URL: &str = "ws://127.0.0.1:1";
+
+#[tokio::test]
+#[serial]
+async fn validate_offline_valid_files() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .url(UNREACHABLE_URL)
+        .strict_schema(true)
+        .build();
+    SurrealdbMigrations::new(configuration)
+        .validate_offline()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn validate_offline_detects_invalid_migration_name() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    std::fs::write("tests-files/migrations/AddUser.surql", "CREATE user;")?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .url(UNREACHABLE_URL)
+        .build();
+    let result = SurrealdbMigrations::new(configuration)
+        .validate_offline()
+        .await;
+
+    let error = result.err().context("validate_offline should fail")?;
+    ensure!(
+        error.to_string()
+            == "Migration 'AddUser' does not match the version format 'YYYYMMDD_HHMMSS_Name'"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn validate_offline_detects_migrations_not_sorted_in_version_order() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_empty_template()?;
+
+    std::fs::write("tests-files/migrations/9_AddUser.surql", "CREATE user;")?;
+    std::fs::write("tests-files/migrations/10_AddPost.surql", "CREATE post;")?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .url(UNREACHABLE_URL)
+        .version_format(VersionFormat::Sequential)
+        .build();
+    let result = SurrealdbMigrations::new(configuration)
+        .validate_offline()
+        .await;
+
+    let error = result.err().context("validate_offline should fail")?;
+    ensure!(
+        error.to_string()
+            == "The following migrations are sorted by name after migrations of a later version: 9_AddUser"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn validate_offline_detects_invalid_syntax() -> Result<()> {
+    clear_tests_files()?;
+    scaffold_blog_template()?;
+
+    std::fs::write(
+        "tests-files/migrations/99991231_235959_InvalidSyntax.surql",
+        "CREATE user SET;",
+    )?;
+    std::fs::write(
+        "tests-files/migrations/99991231_235959_InvalidSyntax.down.surql",
+        "DELETE;",
+    )?;
+
+    let configuration = SurrealdbConfiguration::builder()
+        .url(UNREACHABLE_URL)
+        .build();
+    let result = SurrealdbMigrations::new(configuration)
+        .validate_offline()
+        .await;
+
+    let error = result.err().context("validate_offline should fail")?;
+    let lines = error
+        .to_string()
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    ensure!(lines.len() == 2);
+    ensure!(
+        lines[0].starts_with("Cannot parse '")
+            && lines[0].contains("99991231_235959_InvalidSyntax.surql")
+    );
+    ensure!(lines[1].starts_with("Cannot parse '99991231_235959_InvalidSyntax (down script)'"));
+
+    Ok(())
+}
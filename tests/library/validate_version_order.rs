@@ -1,7 +1,8 @@
-use anyhow::{ensure, Result};
-use regex::Regex;
+use anyhow::{ensure, Context, Result};
 use serial_test::serial;
-use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+use surrealdb_migrations::{
+    MigrationError, SurrealdbConfiguration, SurrealdbMigrations, VersionFormat,
+};
 
 use crate::helpers::*;
 
@@ -78,6 +79,7 @@ async fn fails_if_migrations_applied_with_new_migration_before_last_applied() ->
             let configuration = SurrealdbConfiguration::default();
             let runner = SurrealdbMigrations::new(configuration);
 
+            let first_migration_name = get_first_migration_name()?;
             let first_migration_file = get_first_migration_file()?;
             std::fs::remove_file(first_migration_file)?;
 
@@ -88,16 +90,72 @@ async fn fails_if_migrations_applied_with_new_migration_before_last_applied() ->
 
             let result = runner.validate_version_order().await;
 
-            ensure!(result.is_err());
+            ensure!(matches!(
+                result,
+                Err(MigrationError::VersionOrderViolation { migrations }) if migrations == [first_migration_name]
+            ));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn fails_if_sequential_migration_added_before_last_applied() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
+
+            std::fs::write("tests-files/migrations/0002_AddPost.surql", "CREATE post;")?;
+
+            let configuration = SurrealdbConfiguration::builder()
+                .version_format(VersionFormat::Sequential)
+                .build();
+            let runner = SurrealdbMigrations::new(configuration);
+
+            runner.up().await?;
+
+            std::fs::write("tests-files/migrations/0001_AddUser.surql", "CREATE user;")?;
+
+            let result = runner.validate_version_order().await;
+            let error = result.err().context("validate_version_order should fail")?;
+
+            ensure!(
+                error.to_string() == "The following migrations have not been applied: 0001_AddUser"
+            );
+            ensure!(matches!(
+                error,
+                MigrationError::VersionOrderViolation { migrations } if migrations == ["0001_AddUser"]
+            ));
+
+            Ok(())
+        })
+    })
+    .await
+}
 
-            let error_regex = Regex::new(
-                r"The following migrations have not been applied: \d+_\d+_AddAdminUser",
-            )?;
+#[tokio::test]
+#[serial]
+async fn fails_if_migration_does_not_match_version_format() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_empty_template()?;
 
-            let error_str = result.unwrap_err().to_string();
-            let error_str = error_str.as_str();
+            std::fs::write("tests-files/migrations/0001_AddUser.surql", "CREATE user;")?;
 
-            ensure!(error_regex.is_match(error_str));
+            let configuration = SurrealdbConfiguration::default();
+            let result = SurrealdbMigrations::new(configuration)
+                .validate_version_order()
+                .await;
+
+            ensure!(
+                result.unwrap_err().to_string()
+                    == "Migration '0001_AddUser' does not match the version format 'YYYYMMDD_HHMMSS_Name'"
+            );
 
             Ok(())
         })
@@ -0,0 +1,60 @@
+use anyhow::{ensure, Result};
+use serial_test::serial;
+use surrealdb_migrations::{SurrealdbConfiguration, SurrealdbMigrations};
+
+use crate::helpers::*;
+
+#[tokio::test]
+#[serial]
+async fn verify_without_changes() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let checksum_mismatches = runner.verify().await?;
+            ensure!(checksum_mismatches.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn verify_detects_edited_migration() -> Result<()> {
+    run_with_surreal_instance_async(|| {
+        Box::pin(async {
+            clear_tests_files()?;
+            scaffold_blog_template()?;
+
+            let configuration = SurrealdbConfiguration::default();
+            let runner = SurrealdbMigrations::new(configuration);
+            runner.up().await?;
+
+            let first_migration_name = get_first_migration_name()?;
+            let first_migration_file = get_first_migration_file()?;
+            let content = std::fs::read_to_string(&first_migration_file)?;
+            std::fs::write(&first_migration_file, content + "\nCREATE post;")?;
+
+            let checksum_mismatches = runner.verify().await?;
+            ensure!(checksum_mismatches.len() == 1);
+
+            let checksum_mismatch = &checksum_mismatches[0];
+            ensure!(checksum_mismatch.script_name == first_migration_name);
+            ensure!(checksum_mismatch.actual_checksum.is_some());
+            ensure!(
+                checksum_mismatch.actual_checksum.as_ref()
+                    != Some(&checksum_mismatch.expected_checksum)
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}